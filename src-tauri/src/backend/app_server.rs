@@ -1,19 +1,50 @@
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin, Command};
 use tokio::sync::{oneshot, Mutex};
 use tokio::time::timeout;
 
-use crate::backend::events::{AppServerEvent, EventSink};
-use crate::types::WorkspaceEntry;
+use crate::backend::auto_approve::{is_command_approval, request_command, should_auto_approve};
+use crate::backend::events::{AppServerEvent, EventSink, SessionExited, TurnPhase, TurnProgress};
+use crate::backend::workspace_watch::{watch_workspace, WorkspaceWatcher};
+use crate::codex_home::{remove_thread_rollouts, resolve_default_codex_home};
+use crate::logging;
+use crate::types::{
+    validate_codex_args, AutoApprovePolicy, PendingServerRequest, SessionMetrics, ThreadSummary,
+    ThreadSummaryPage, TurnCompletion, TurnUsage, WorkspaceEntry, WorkspaceHealth,
+};
+use crate::utils::{canonical_workspace_path, now_ms};
+
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const TURN_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+/// Upper bound on how long a rate-limit snapshot is served, so usage
+/// percentages stay fresh even when the next reset is hours away.
+const RATE_LIMIT_CACHE_MAX_AGE_MS: i64 = 5 * 60 * 1000;
+/// How many recent stderr lines each session keeps for crash reports.
+const STDERR_TAIL_LINES: usize = 50;
+/// How many of those lines are quoted in a spawn error.
+const STDERR_ERROR_LINES: usize = 10;
+/// How long sessions stay read-only after the abort-all kill switch.
+const ABORT_READ_ONLY_MS: i64 = 10 * 60 * 1000;
+const THREAD_LIST_PAGE_SIZE: u32 = 50;
+/// Stops a filtered listing from walking an enormous thread history.
+const THREAD_LIST_MAX_PAGES: usize = 20;
+
+fn default_request_timeout(method: &str) -> Duration {
+    match method {
+        "turn/start" | "review/start" | "thread/resume" => TURN_REQUEST_TIMEOUT,
+        _ => DEFAULT_REQUEST_TIMEOUT,
+    }
+}
 
 pub(crate) struct WorkspaceSession {
     pub(crate) entry: WorkspaceEntry,
@@ -21,9 +52,328 @@ pub(crate) struct WorkspaceSession {
     pub(crate) stdin: Mutex<ChildStdin>,
     pub(crate) pending: Mutex<HashMap<u64, oneshot::Sender<Value>>>,
     pub(crate) next_id: AtomicU64,
+    pub(crate) pid: Option<u32>,
+    pub(crate) started_at: Instant,
+    pub(crate) last_event_ms: AtomicI64,
+    pub(crate) busy_threads: Mutex<HashSet<String>>,
+    pub(crate) turn_watchers: Mutex<HashMap<String, TurnWatcher>>,
+    pub(crate) thread_usage: Mutex<HashMap<String, ThreadUsage>>,
+    pub(crate) stderr_tail: Mutex<VecDeque<String>>,
+    /// The CODEX_HOME override codex was started with, if any.
+    pub(crate) codex_home: Option<PathBuf>,
+    /// Requests from codex still waiting for a client response, by id.
+    pub(crate) server_requests: Mutex<HashMap<u64, PendingServerRequest>>,
+    /// Live copy of the workspace's auto-approval policy; settings updates
+    /// replace it without reconnecting.
+    pub(crate) auto_approve: Mutex<AutoApprovePolicy>,
+    /// Id of the turn in flight on each busy thread, for interrupting it.
+    pub(crate) running_turns: Mutex<HashMap<String, String>>,
+    /// While in the future, new turns run read-only and nothing is
+    /// auto-approved. Set by the abort-all kill switch.
+    pub(crate) read_only_until_ms: AtomicI64,
+    /// Present while the workspace's `watchFiles` setting is on.
+    pub(crate) file_watcher: Mutex<Option<WorkspaceWatcher>>,
+    /// In-flight requests a client tagged with its own id, so it can cancel
+    /// them with `cancel_request`.
+    pub(crate) cancellable: Mutex<HashMap<String, CancellableRequest>>,
+    /// Last progress sent for each thread with a turn in flight.
+    pub(crate) turn_progress: Mutex<HashMap<String, TurnProgress>>,
+    pub(crate) metrics: RequestCounters,
+}
+
+/// Lock-free counters behind `SessionMetrics`, updated on every request.
+#[derive(Default)]
+pub(crate) struct RequestCounters {
+    requests_sent: AtomicU64,
+    responses_received: AtomicU64,
+    errors: AtomicU64,
+    timeouts: AtomicU64,
+    /// Exponential moving average in microseconds; 0 until the first response.
+    avg_latency_us: AtomicU64,
+}
+
+impl RequestCounters {
+    /// Weight of the newest sample in the moving average, as 1/N.
+    const LATENCY_SMOOTHING: u64 = 8;
+
+    fn record_response(&self, latency: Duration, is_error: bool) {
+        self.responses_received.fetch_add(1, Ordering::Relaxed);
+        if is_error {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        let sample = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX).max(1);
+        let _ = self
+            .avg_latency_us
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |average| {
+                Some(if average == 0 {
+                    sample
+                } else {
+                    average - average / Self::LATENCY_SMOOTHING
+                        + sample / Self::LATENCY_SMOOTHING
+                })
+            });
+    }
+
+    pub(crate) fn snapshot(&self) -> SessionMetrics {
+        let avg_latency_us = self.avg_latency_us.load(Ordering::Relaxed);
+        SessionMetrics {
+            requests_sent: self.requests_sent.load(Ordering::Relaxed),
+            responses_received: self.responses_received.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+            avg_latency_ms: (avg_latency_us > 0).then(|| avg_latency_us as f64 / 1000.0),
+        }
+    }
+}
+
+pub(crate) struct CancellableRequest {
+    codex_id: u64,
+    /// Thread whose running turn is interrupted when the request is canceled.
+    turn_thread_id: Option<String>,
+}
+
+/// Token totals for one thread: what codex last reported and what has
+/// already been attributed to completed turns.
+#[derive(Default)]
+pub(crate) struct ThreadUsage {
+    reported: TurnUsage,
+    counted: TurnUsage,
+}
+
+pub(crate) struct ThreadListOptions {
+    pub(crate) cursor: Option<String>,
+    pub(crate) limit: usize,
+    pub(crate) query: Option<String>,
+    /// Thread ids the user pinned, from the workspace settings.
+    pub(crate) pinned: Vec<String>,
+}
+
+pub(crate) type TurnReceiver = oneshot::Receiver<Result<TurnCompletion, String>>;
+
+/// Accumulates the output of one turn for a caller awaiting its completion.
+pub(crate) struct TurnWatcher {
+    completion: TurnCompletion,
+    tx: oneshot::Sender<Result<TurnCompletion, String>>,
+}
+
+fn response_error(response: &Value) -> Option<String> {
+    response.get("error").map(|error| {
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .map(|message| message.to_string())
+            .unwrap_or_else(|| error.to_string())
+    })
+}
+
+fn normalize_thread_cwd(path: &str) -> String {
+    path.replace('\\', "/").trim_end_matches('/').to_string()
+}
+
+/// Decides whether a thread's cwd belongs to a workspace. Plain string
+/// equality misses symlinked paths (macOS reports `/tmp` as `/private/tmp`),
+/// so unequal cwds are compared again after resolving them, once per cwd.
+struct WorkspaceCwdMatcher {
+    normalized: String,
+    canonical: String,
+    resolved: HashMap<String, bool>,
+}
+
+impl WorkspaceCwdMatcher {
+    fn new(workspace_path: &str) -> Self {
+        Self {
+            normalized: normalize_thread_cwd(workspace_path),
+            canonical: canonical_workspace_path(workspace_path),
+            resolved: HashMap::new(),
+        }
+    }
+
+    fn matches(&mut self, cwd: &str) -> bool {
+        let normalized = normalize_thread_cwd(cwd);
+        if normalized.is_empty() {
+            return false;
+        }
+        if normalized == self.normalized {
+            return true;
+        }
+        if let Some(matches) = self.resolved.get(&normalized) {
+            return *matches;
+        }
+        let matches = canonical_workspace_path(cwd) == self.canonical;
+        self.resolved.insert(normalized, matches);
+        matches
+    }
+}
+
+/// Last activity of a `thread/list` entry in milliseconds; codex reports
+/// seconds, older builds milliseconds.
+fn thread_updated_ms(thread: &Value) -> i64 {
+    let raw = ["updatedAt", "updated_at", "createdAt", "created_at"]
+        .iter()
+        .find_map(|key| thread.get(*key).and_then(Value::as_i64))
+        .unwrap_or(0);
+    if raw > 0 && raw < 1_000_000_000_000 {
+        raw * 1000
+    } else {
+        raw
+    }
+}
+
+fn thread_summary(thread: &Value) -> Option<ThreadSummary> {
+    let id = thread.get("id").and_then(Value::as_str)?;
+    if id.is_empty() {
+        return None;
+    }
+    let text = |key: &str| {
+        thread
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_string()
+    };
+    Some(ThreadSummary {
+        id: id.to_string(),
+        preview: text("preview"),
+        cwd: text("cwd"),
+        updated_at: thread_updated_ms(thread),
+        pinned: false,
+    })
+}
+
+fn thread_matches_query(thread: &Value, query: &str) -> bool {
+    ["preview", "title", "name"].iter().any(|key| {
+        thread
+            .get(*key)
+            .and_then(Value::as_str)
+            .map(|text| text.to_lowercase().contains(query))
+            .unwrap_or(false)
+    })
+}
+
+/// One-line summary of a server request, for clients re-rendering prompts
+/// they never saw arrive.
+fn describe_server_request(method: &str, params: &Value) -> String {
+    let reason = params
+        .get("reason")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|reason| !reason.is_empty());
+    let action = if is_command_approval(method) {
+        let command = request_command(params);
+        if command.is_empty() {
+            "Approve running a command".to_string()
+        } else {
+            format!("Approve running `{command}`")
+        }
+    } else if method.contains("fileChange") || method.contains("applyPatch") {
+        "Approve file changes".to_string()
+    } else {
+        method.to_string()
+    };
+    match reason {
+        Some(reason) => format!("{action}: {reason}"),
+        None => action,
+    }
+}
+
+pub(crate) fn event_thread_id(message: &Value) -> Option<String> {
+    let params = message.get("params")?;
+    let turn = params.get("turn");
+    params
+        .get("threadId")
+        .or_else(|| params.get("thread_id"))
+        .or_else(|| turn.and_then(|turn| turn.get("threadId")))
+        .or_else(|| turn.and_then(|turn| turn.get("thread_id")))
+        .and_then(Value::as_str)
+        .map(|value| value.to_string())
+}
+
+/// The phase a codex notification moves its turn into, plus the tool for
+/// tool phases. `None` for notifications that say nothing about progress.
+fn progress_phase(method: &str, params: Option<&Value>) -> Option<(TurnPhase, Option<String>)> {
+    match method {
+        "turn/started" | "item/reasoning/summaryTextDelta" | "item/reasoning/textDelta" => {
+            Some((TurnPhase::Thinking, None))
+        }
+        "item/agentMessage/delta" => Some((TurnPhase::Writing, None)),
+        "turn/completed" => Some((TurnPhase::Done, None)),
+        "error" => {
+            let will_retry = params
+                .and_then(|params| params.get("willRetry").or_else(|| params.get("will_retry")))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            (!will_retry).then_some((TurnPhase::Done, None))
+        }
+        "item/started" => {
+            let item = params?.get("item")?;
+            let text = |key: &str| item.get(key).and_then(Value::as_str).unwrap_or("");
+            match text("type") {
+                "reasoning" => Some((TurnPhase::Thinking, None)),
+                "agentMessage" => Some((TurnPhase::Writing, None)),
+                "mcpToolCall" => {
+                    let name = [text("server"), text("tool")]
+                        .into_iter()
+                        .filter(|part| !part.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("/");
+                    Some((TurnPhase::Tool, Some(name).filter(|name| !name.is_empty())))
+                }
+                "collabToolCall" | "collabAgentToolCall" => Some((
+                    TurnPhase::Tool,
+                    Some(text("tool").to_string()).filter(|name| !name.is_empty()),
+                )),
+                kind @ ("commandExecution" | "fileChange" | "webSearch" | "imageView") => {
+                    Some((TurnPhase::Tool, Some(kind.to_string())))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
 }
 
 impl WorkspaceSession {
+    pub(crate) async fn health(&self) -> WorkspaceHealth {
+        let connected = {
+            let mut child = self.child.lock().await;
+            matches!(child.try_wait(), Ok(None))
+        };
+        let last_event_ms = self.last_event_ms.load(Ordering::Relaxed);
+        WorkspaceHealth {
+            connected,
+            codex_pid: self.pid,
+            uptime_secs: self.started_at.elapsed().as_secs(),
+            last_event_ms: (last_event_ms > 0).then_some(last_event_ms),
+            pending_requests: self.pending.lock().await.len(),
+        }
+    }
+
+    pub(crate) async fn stderr_tail(&self) -> Vec<String> {
+        self.stderr_tail.lock().await.iter().cloned().collect()
+    }
+
+    async fn record_stderr(&self, line: &str) {
+        let mut tail = self.stderr_tail.lock().await;
+        while tail.len() >= STDERR_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line.to_string());
+    }
+
+    /// Waits briefly for the child to be reaped after its stdout closed.
+    async fn wait_for_exit_code(&self) -> Option<i32> {
+        for _ in 0..20 {
+            {
+                let mut child = self.child.lock().await;
+                if let Ok(Some(status)) = child.try_wait() {
+                    return status.code();
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        None
+    }
+
     async fn write_message(&self, value: Value) -> Result<(), String> {
         let mut stdin = self.stdin.lock().await;
         let mut line = serde_json::to_string(&value).map_err(|e| e.to_string())?;
@@ -35,12 +385,516 @@ impl WorkspaceSession {
     }
 
     pub(crate) async fn send_request(&self, method: &str, params: Value) -> Result<Value, String> {
+        self.send_request_with_timeout(method, params, default_request_timeout(method))
+            .await
+    }
+
+    pub(crate) async fn send_request_with_timeout(
+        &self,
+        method: &str,
+        params: Value,
+        request_timeout: Duration,
+    ) -> Result<Value, String> {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let (tx, rx) = oneshot::channel();
         self.pending.lock().await.insert(id, tx);
-        self.write_message(json!({ "id": id, "method": method, "params": params }))
+        self.write_and_wait(id, rx, method, params, request_timeout)
+            .await
+    }
+
+    /// Sends a request whose waiter is already registered under `id` in
+    /// `pending`, and waits for its response.
+    async fn write_and_wait(
+        &self,
+        id: u64,
+        rx: oneshot::Receiver<Value>,
+        method: &str,
+        params: Value,
+        request_timeout: Duration,
+    ) -> Result<Value, String> {
+        self.metrics.requests_sent.fetch_add(1, Ordering::Relaxed);
+        if let Err(err) = self
+            .write_message(json!({ "id": id, "method": method, "params": params }))
+            .await
+        {
+            self.metrics.errors.fetch_add(1, Ordering::Relaxed);
+            self.pending.lock().await.remove(&id);
+            return Err(err);
+        }
+        let sent_at = Instant::now();
+        match timeout(request_timeout, rx).await {
+            Ok(Ok(response)) => {
+                self.metrics
+                    .record_response(sent_at.elapsed(), response.get("error").is_some());
+                Ok(response)
+            }
+            Ok(Err(_)) => Err("request canceled".to_string()),
+            Err(_) => {
+                self.metrics.timeouts.fetch_add(1, Ordering::Relaxed);
+                self.pending.lock().await.remove(&id);
+                Err(format!(
+                    "Timeout: `{method}` did not respond within {}s",
+                    request_timeout.as_secs()
+                ))
+            }
+        }
+    }
+
+    /// Like `send_request`, but registers the call under a client-chosen
+    /// `request_id` while it is in flight so `cancel_request` can abandon it.
+    pub(crate) async fn send_cancellable_request(
+        &self,
+        method: &str,
+        params: Value,
+        request_id: Option<&str>,
+        turn_thread_id: Option<&str>,
+    ) -> Result<Value, String> {
+        let Some(request_id) = request_id else {
+            return self.send_request(method, params).await;
+        };
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = oneshot::channel();
+        {
+            let mut cancellable = self.cancellable.lock().await;
+            if cancellable.contains_key(request_id) {
+                return Err(format!("request id {request_id} is already in flight"));
+            }
+            // Registered before the id becomes cancellable, so a cancel can
+            // never miss the waiter.
+            self.pending.lock().await.insert(id, tx);
+            cancellable.insert(
+                request_id.to_string(),
+                CancellableRequest {
+                    codex_id: id,
+                    turn_thread_id: turn_thread_id.map(str::to_string),
+                },
+            );
+        }
+        let response = self
+            .write_and_wait(id, rx, method, params, default_request_timeout(method))
+            .await;
+        self.cancellable.lock().await.remove(request_id);
+        response
+    }
+
+    /// Drops the waiter for a tagged request, which then fails with "request
+    /// canceled", and interrupts the turn it started if one is running.
+    pub(crate) async fn cancel_request(&self, request_id: &str) -> Value {
+        let Some(request) = self.cancellable.lock().await.remove(request_id) else {
+            return json!({ "canceled": false, "interrupted": false });
+        };
+        self.pending.lock().await.remove(&request.codex_id);
+        let mut interrupted = false;
+        if let Some(thread_id) = request.turn_thread_id {
+            let turn_id = self.running_turns.lock().await.get(&thread_id).cloned();
+            if let Some(turn_id) = turn_id {
+                let params = json!({ "threadId": thread_id, "turnId": turn_id });
+                interrupted = self
+                    .send_request("turn/interrupt", params)
+                    .await
+                    .is_ok_and(|response| response.get("error").is_none());
+            }
+        }
+        json!({ "canceled": true, "interrupted": interrupted })
+    }
+
+    /// Starts a turn, refusing to run two turns on the same thread at once.
+    pub(crate) async fn start_turn(
+        &self,
+        thread_id: &str,
+        params: Value,
+        request_id: Option<&str>,
+    ) -> Result<Value, String> {
+        if !self.busy_threads.lock().await.insert(thread_id.to_string()) {
+            return Err(format!(
+                "ThreadBusy: thread {thread_id} already has a turn in progress"
+            ));
+        }
+        let response = self
+            .send_cancellable_request("turn/start", params, request_id, Some(thread_id))
+            .await;
+        let failed = match &response {
+            Ok(value) => value.get("error").is_some(),
+            Err(_) => true,
+        };
+        if failed {
+            self.busy_threads.lock().await.remove(thread_id);
+        } else if let Some(turn_id) = response
+            .as_ref()
+            .ok()
+            .and_then(|value| value.get("result")?.get("turn")?.get("id")?.as_str())
+        {
+            self.running_turns
+                .lock()
+                .await
+                .insert(thread_id.to_string(), turn_id.to_string());
+        }
+        response
+    }
+
+    pub(crate) fn read_only_locked(&self) -> bool {
+        self.read_only_until_ms.load(Ordering::Relaxed) > now_ms()
+    }
+
+    /// Sends `turn/interrupt` for every turn this session knows is running
+    /// and keeps new turns read-only until `until_ms`. Returns how many
+    /// interrupts codex accepted.
+    pub(crate) async fn interrupt_all_turns(&self, until_ms: i64) -> usize {
+        self.read_only_until_ms.fetch_max(until_ms, Ordering::Relaxed);
+        let running: Vec<(String, String)> = self
+            .running_turns
+            .lock()
+            .await
+            .iter()
+            .map(|(thread_id, turn_id)| (thread_id.clone(), turn_id.clone()))
+            .collect();
+        let mut interrupted = 0;
+        for (thread_id, turn_id) in running {
+            let params = json!({ "threadId": thread_id, "turnId": turn_id });
+            if let Ok(response) = self.send_request("turn/interrupt", params).await {
+                if response.get("error").is_none() {
+                    interrupted += 1;
+                }
+            }
+        }
+        interrupted
+    }
+
+    /// Registers interest in the next turn on `thread_id`. Call before `start_turn`
+    /// so a fast completion cannot slip past the watcher.
+    pub(crate) async fn watch_turn(&self, thread_id: &str) -> Result<TurnReceiver, String> {
+        let mut watchers = self.turn_watchers.lock().await;
+        if watchers.contains_key(thread_id) {
+            return Err(format!(
+                "ThreadBusy: thread {thread_id} already has a turn in progress"
+            ));
+        }
+        let (tx, rx) = oneshot::channel();
+        watchers.insert(
+            thread_id.to_string(),
+            TurnWatcher {
+                completion: TurnCompletion {
+                    thread_id: thread_id.to_string(),
+                    ..Default::default()
+                },
+                tx,
+            },
+        );
+        Ok(rx)
+    }
+
+    pub(crate) async fn unwatch_turn(&self, thread_id: &str) {
+        self.turn_watchers.lock().await.remove(thread_id);
+    }
+
+    /// Resolves a watched turn once codex reports it finished, or fails if the
+    /// `turn/start` request was rejected or the turn outlives `wait`.
+    pub(crate) async fn finish_watched_turn(
+        &self,
+        thread_id: &str,
+        started: Result<Value, String>,
+        completion: TurnReceiver,
+        wait: Duration,
+    ) -> Result<TurnCompletion, String> {
+        let start_error = match started {
+            Ok(response) => response.get("error").map(|error| {
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .map(|message| message.to_string())
+                    .unwrap_or_else(|| error.to_string())
+            }),
+            Err(err) => Some(err),
+        };
+        if let Some(err) = start_error {
+            self.unwatch_turn(thread_id).await;
+            return Err(err);
+        }
+        match timeout(wait, completion).await {
+            Ok(result) => result.map_err(|_| "turn canceled".to_string())?,
+            Err(_) => {
+                self.unwatch_turn(thread_id).await;
+                Err(format!(
+                    "Timeout: turn on thread {thread_id} did not complete within {}s",
+                    wait.as_secs()
+                ))
+            }
+        }
+    }
+
+    /// Derives a `TurnProgress` from a notification when it moves its thread
+    /// to a new phase or tool; repeats within a phase return `None`.
+    async fn track_progress(&self, message: &Value) -> Option<TurnProgress> {
+        let method = message.get("method").and_then(Value::as_str)?;
+        let params = message.get("params");
+        let (phase, tool_name) = progress_phase(method, params)?;
+        let thread_id = event_thread_id(message)?;
+        let running_turn = self.running_turns.lock().await.get(&thread_id).cloned();
+        let mut progress = self.turn_progress.lock().await;
+        let turn_id = params
+            .and_then(|params| {
+                params
+                    .get("turn")
+                    .and_then(|turn| turn.get("id"))
+                    .or_else(|| params.get("turnId"))
+            })
+            .and_then(Value::as_str)
+            .map(|value| value.to_string())
+            .or_else(|| progress.get(&thread_id).and_then(|last| last.turn_id.clone()))
+            .or(running_turn);
+        let next = TurnProgress {
+            workspace_id: self.entry.id.clone(),
+            thread_id: thread_id.clone(),
+            turn_id,
+            phase,
+            tool_name,
+        };
+        if phase == TurnPhase::Done {
+            progress.remove(&thread_id);
+            return Some(next);
+        }
+        if progress.get(&thread_id) == Some(&next) {
+            return None;
+        }
+        progress.insert(thread_id, next.clone());
+        Some(next)
+    }
+
+    /// Updates per-thread bookkeeping from a codex notification. Returns a
+    /// synthesized follow-up event (turn usage) to emit after the original.
+    async fn observe_notification(&self, message: &Value) -> Option<Value> {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params");
+        let thread_id = event_thread_id(message);
+        match method {
+            "turn/started" => {
+                let thread_id = thread_id?;
+                let turn_id = params
+                    .and_then(|params| params.get("turn"))
+                    .and_then(|turn| turn.get("id"))
+                    .and_then(Value::as_str)?;
+                self.running_turns
+                    .lock()
+                    .await
+                    .insert(thread_id, turn_id.to_string());
+                None
+            }
+            "thread/tokenUsage/updated" => {
+                let thread_id = thread_id?;
+                let token_usage = params.and_then(|params| {
+                    params
+                        .get("tokenUsage")
+                        .or_else(|| params.get("token_usage"))
+                });
+                if let Some(reported) = token_usage
+                    .and_then(|usage| usage.get("total"))
+                    .and_then(TurnUsage::from_value)
+                {
+                    let mut usage = self.thread_usage.lock().await;
+                    usage.entry(thread_id.clone()).or_default().reported = reported;
+                }
+                let mut watchers = self.turn_watchers.lock().await;
+                if let Some(watcher) = watchers.get_mut(&thread_id) {
+                    watcher.completion.token_usage = token_usage.cloned();
+                }
+                None
+            }
+            "item/completed" => {
+                let thread_id = thread_id?;
+                let mut watchers = self.turn_watchers.lock().await;
+                let watcher = watchers.get_mut(&thread_id)?;
+                let item = params.and_then(|params| params.get("item"));
+                if item
+                    .and_then(|item| item.get("type"))
+                    .and_then(Value::as_str)
+                    == Some("agentMessage")
+                {
+                    if let Some(text) = item
+                        .and_then(|item| item.get("text"))
+                        .and_then(Value::as_str)
+                    {
+                        watcher.completion.text = text.to_string();
+                    }
+                }
+                None
+            }
+            "turn/completed" => {
+                let thread_id = thread_id?;
+                let turn = params.and_then(|params| params.get("turn"));
+                let turn_id = turn
+                    .and_then(|turn| turn.get("id"))
+                    .or_else(|| params.and_then(|params| params.get("turnId")))
+                    .and_then(Value::as_str)
+                    .map(|value| value.to_string());
+                self.busy_threads.lock().await.remove(&thread_id);
+                self.running_turns.lock().await.remove(&thread_id);
+                self.clear_thread_server_requests(&thread_id).await;
+                if let Some(mut watcher) = self.turn_watchers.lock().await.remove(&thread_id) {
+                    watcher.completion.turn_id = turn_id.clone();
+                    let _ = watcher.tx.send(Ok(watcher.completion));
+                }
+                let explicit_usage = turn
+                    .and_then(|turn| turn.get("usage"))
+                    .or_else(|| params.and_then(|params| params.get("usage")))
+                    .and_then(TurnUsage::from_value);
+                let (usage, thread_total) = {
+                    let mut thread_usage = self.thread_usage.lock().await;
+                    let entry = thread_usage.entry(thread_id.clone()).or_default();
+                    let usage = explicit_usage
+                        .unwrap_or_else(|| entry.reported.saturating_sub(entry.counted));
+                    entry.counted.accumulate(usage);
+                    (usage, entry.counted)
+                };
+                Some(json!({
+                    "method": "codex/turnUsage",
+                    "params": {
+                        "threadId": thread_id,
+                        "turnId": turn_id,
+                        "usage": usage,
+                        "threadTotal": thread_total,
+                    }
+                }))
+            }
+            "error" => {
+                let will_retry = params
+                    .and_then(|params| params.get("willRetry").or_else(|| params.get("will_retry")))
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                let thread_id = thread_id.filter(|_| !will_retry)?;
+                self.busy_threads.lock().await.remove(&thread_id);
+                self.running_turns.lock().await.remove(&thread_id);
+                self.clear_thread_server_requests(&thread_id).await;
+                if let Some(watcher) = self.turn_watchers.lock().await.remove(&thread_id) {
+                    let message = params
+                        .and_then(|params| params.get("error"))
+                        .and_then(|error| error.get("message"))
+                        .and_then(Value::as_str)
+                        .unwrap_or("turn failed");
+                    let _ = watcher.tx.send(Err(message.to_string()));
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) async fn thread_usage(&self, thread_id: &str) -> TurnUsage {
+        self.thread_usage
+            .lock()
+            .await
+            .get(thread_id)
+            .map(|usage| usage.counted)
+            .unwrap_or_default()
+    }
+
+    /// Pages through `thread/list` from `options.cursor`, keeping only this
+    /// workspace's threads (and those matching `options.query`), until at
+    /// least `options.limit` are found or the list runs out.
+    pub(crate) async fn list_workspace_threads(
+        &self,
+        options: ThreadListOptions,
+    ) -> Result<ThreadSummaryPage, String> {
+        let query = options
+            .query
+            .map(|query| query.trim().to_lowercase())
+            .filter(|query| !query.is_empty());
+        let mut cwd_matcher = WorkspaceCwdMatcher::new(&self.entry.path);
+        let mut threads: Vec<ThreadSummary> = Vec::new();
+        let mut seen = HashSet::new();
+        let mut cursor = options.cursor;
+        for _ in 0..THREAD_LIST_MAX_PAGES {
+            let response = self
+                .send_request(
+                    "thread/list",
+                    json!({ "cursor": cursor, "limit": THREAD_LIST_PAGE_SIZE }),
+                )
+                .await?;
+            if let Some(err) = response_error(&response) {
+                return Err(err);
+            }
+            let result = response.get("result").unwrap_or(&response);
+            let page = result
+                .get("data")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            for thread in page {
+                if let Some(query) = query.as_deref() {
+                    if !thread_matches_query(&thread, query) {
+                        continue;
+                    }
+                }
+                let Some(mut summary) = thread_summary(&thread) else {
+                    continue;
+                };
+                summary.pinned = options.pinned.contains(&summary.id);
+                if !cwd_matcher.matches(&summary.cwd) || !seen.insert(summary.id.clone())
+                {
+                    continue;
+                }
+                threads.push(summary);
+            }
+            cursor = result
+                .get("nextCursor")
+                .or_else(|| result.get("next_cursor"))
+                .and_then(Value::as_str)
+                .map(|value| value.to_string());
+            if cursor.is_none() || threads.len() >= options.limit {
+                break;
+            }
+        }
+        Ok(ThreadSummaryPage {
+            threads,
+            next_cursor: cursor,
+        })
+    }
+
+    /// Returns this workspace's threads whose preview or title contains
+    /// `query`, pinned threads first and then most recently updated.
+    pub(crate) async fn search_threads(
+        &self,
+        query: &str,
+        limit: usize,
+        pinned: Vec<String>,
+    ) -> Result<Vec<ThreadSummary>, String> {
+        if query.trim().is_empty() {
+            return Err("Search query cannot be empty.".to_string());
+        }
+        let mut threads = self
+            .list_workspace_threads(ThreadListOptions {
+                cursor: None,
+                limit,
+                query: Some(query.to_string()),
+                pinned,
+            })
+            .await?
+            .threads;
+        threads.sort_by_key(|thread| (!thread.pinned, std::cmp::Reverse(thread.updated_at)));
+        threads.truncate(limit);
+        Ok(threads)
+    }
+
+    /// Archives the thread through codex and then removes its rollout files,
+    /// so it is gone for good. Refuses while a turn is running on it.
+    pub(crate) async fn delete_thread(&self, thread_id: &str) -> Result<usize, String> {
+        if self.busy_threads.lock().await.contains(thread_id) {
+            return Err(format!(
+                "ThreadBusy: thread {thread_id} has a turn in progress; interrupt it first"
+            ));
+        }
+        // Archiving unloads the thread in codex. A thread that was archived
+        // before answers with an error, which is fine here.
+        self.send_request("thread/archive", json!({ "threadId": thread_id }))
             .await?;
-        rx.await.map_err(|_| "request canceled".to_string())
+        let codex_home = self
+            .codex_home
+            .clone()
+            .or_else(resolve_default_codex_home)
+            .ok_or("Unable to resolve CODEX_HOME")?;
+        let thread_id = thread_id.to_string();
+        tokio::task::spawn_blocking(move || remove_thread_rollouts(&codex_home, &thread_id))
+            .await
+            .map_err(|err| err.to_string())?
     }
 
     pub(crate) async fn send_notification(
@@ -58,8 +912,141 @@ impl WorkspaceSession {
 
     pub(crate) async fn send_response(&self, id: u64, result: Value) -> Result<(), String> {
         self.write_message(json!({ "id": id, "result": result }))
+            .await?;
+        self.server_requests.lock().await.remove(&id);
+        Ok(())
+    }
+
+    pub(crate) async fn set_auto_approve(&self, policy: AutoApprovePolicy) {
+        *self.auto_approve.lock().await = policy;
+    }
+
+    /// Starts or stops watching the workspace folder. A watcher that fails to
+    /// start is logged and the session keeps running unwatched.
+    pub(crate) async fn set_file_watch<E: EventSink>(&self, enabled: bool, event_sink: E) {
+        let mut file_watcher = self.file_watcher.lock().await;
+        if !enabled {
+            file_watcher.take();
+            return;
+        }
+        if file_watcher.is_some() {
+            return;
+        }
+        match watch_workspace(
+            self.entry.id.clone(),
+            PathBuf::from(&self.entry.path),
+            event_sink,
+        )
+        .await
+        {
+            Ok(watcher) => *file_watcher = Some(watcher),
+            Err(err) => logging::warn("workspace-watch", format!("[{}] {err}", self.entry.id)),
+        }
+    }
+
+    /// Accepts a server request on the user's behalf when the workspace policy
+    /// covers it. Returns the event recording the decision, or `None` when the
+    /// request still needs the user.
+    async fn try_auto_approve(&self, id: u64, message: &Value) -> Option<Value> {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        if self.read_only_locked() {
+            return None;
+        }
+        if !should_auto_approve(&*self.auto_approve.lock().await, method, &params) {
+            return None;
+        }
+        self.send_response(id, json!({ "decision": "accept" })).await.ok()?;
+        Some(json!({
+            "method": "codex/autoApproved",
+            "params": {
+                "requestId": id,
+                "threadId": event_thread_id(message),
+                "method": method,
+                "description": describe_server_request(method, &params),
+            }
+        }))
+    }
+
+    async fn track_server_request(&self, id: u64, message: &Value) {
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let request = PendingServerRequest {
+            request_id: id,
+            method: method.to_string(),
+            thread_id: event_thread_id(message),
+            description: describe_server_request(method, &params),
+            params,
+            received_at_ms: now_ms(),
+        };
+        self.server_requests.lock().await.insert(id, request);
+    }
+
+    /// Open server requests, oldest first.
+    pub(crate) async fn pending_server_requests(&self) -> Vec<PendingServerRequest> {
+        let mut requests: Vec<_> = self.server_requests.lock().await.values().cloned().collect();
+        requests.sort_by_key(|request| request.request_id);
+        requests
+    }
+
+    /// A finished turn can no longer act on its prompts.
+    async fn clear_thread_server_requests(&self, thread_id: &str) {
+        self.server_requests
+            .lock()
             .await
+            .retain(|_, request| request.thread_id.as_deref() != Some(thread_id));
+    }
+}
+
+/// Kill switch: interrupts every running turn in `sessions` at once and
+/// locks them read-only for a while. Returns the number of turns aborted and
+/// when the lock lifts.
+pub(crate) async fn abort_all_turns(sessions: Vec<Arc<WorkspaceSession>>) -> Value {
+    let read_only_until_ms = now_ms() + ABORT_READ_ONLY_MS;
+    let tasks: Vec<_> = sessions
+        .into_iter()
+        .map(|session| {
+            tokio::spawn(async move { session.interrupt_all_turns(read_only_until_ms).await })
+        })
+        .collect();
+    let mut aborted = 0;
+    for task in tasks {
+        aborted += task.await.unwrap_or(0);
     }
+    logging::warn("app-server", format!("abort-all interrupted {aborted} running turn(s)"));
+    json!({ "aborted": aborted, "readOnlyUntilMs": read_only_until_ms })
+}
+
+pub(crate) struct CachedRateLimits {
+    pub(crate) response: Value,
+    pub(crate) expires_at_ms: i64,
+}
+
+/// Expires a rate-limit snapshot at the earliest window reset codex reports,
+/// capped at `RATE_LIMIT_CACHE_MAX_AGE_MS` after it was fetched.
+pub(crate) fn rate_limits_expiry_ms(response: &Value, fetched_at_ms: i64) -> i64 {
+    let max_expiry = fetched_at_ms + RATE_LIMIT_CACHE_MAX_AGE_MS;
+    let result = response.get("result").unwrap_or(response);
+    let Some(rate_limits) = result
+        .get("rateLimits")
+        .or_else(|| result.get("rate_limits"))
+    else {
+        return max_expiry;
+    };
+    ["primary", "secondary"]
+        .iter()
+        .filter_map(|window| rate_limits.get(*window))
+        .filter_map(|window| window.get("resetsAt").or_else(|| window.get("resets_at")))
+        .filter_map(Value::as_i64)
+        .map(|resets_at| {
+            if resets_at > 1_000_000_000_000 {
+                resets_at
+            } else {
+                resets_at * 1000
+            }
+        })
+        .filter(|resets_at_ms| *resets_at_ms > fetched_at_ms)
+        .fold(max_expiry, i64::min)
 }
 
 pub(crate) fn build_codex_path_env(codex_bin: Option<&str>) -> Option<String> {
@@ -173,9 +1160,193 @@ pub(crate) async fn check_codex_installation(
     Ok(if version.is_empty() { None } else { Some(version) })
 }
 
+/// Returns whether `codex app-server` is available for the given binary.
+pub(crate) async fn probe_codex_app_server(codex_bin: Option<String>) -> bool {
+    let mut command = build_codex_command_with_bin(codex_bin);
+    command.arg("app-server");
+    command.arg("--help");
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+    match timeout(Duration::from_secs(5), command.output()).await {
+        Ok(result) => result.map(|output| output.status.success()).unwrap_or(false),
+        Err(_) => false,
+    }
+}
+
+fn is_executable_file(path: &Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
+/// Looks up a bare command name in the same PATH codex is spawned with.
+fn find_codex_in_path(name: &str) -> Option<PathBuf> {
+    let path_env = build_codex_path_env(None)?;
+    path_env
+        .split(':')
+        .map(|dir| Path::new(dir).join(name))
+        .find(|candidate| is_executable_file(candidate))
+}
+
+/// Rejects a codex binary that does not resolve to an executable file.
+/// Bare command names are looked up in the PATH used to spawn codex.
+pub(crate) fn validate_codex_bin_path(codex_bin: &str) -> Result<(), String> {
+    let codex_bin = codex_bin.trim();
+    if !codex_bin.contains('/') && !codex_bin.contains('\\') {
+        return match find_codex_in_path(codex_bin) {
+            Some(_) => Ok(()),
+            None => Err(format!("Codex binary not found at {codex_bin}.")),
+        };
+    }
+    let metadata = std::fs::metadata(codex_bin)
+        .map_err(|_| format!("Codex binary not found at {codex_bin}."))?;
+    if !metadata.is_file() {
+        return Err(format!("Codex binary path {codex_bin} is not a file."));
+    }
+    if !is_executable_file(Path::new(codex_bin)) {
+        return Err(format!("Codex binary at {codex_bin} is not executable."));
+    }
+    Ok(())
+}
+
+/// Validates a per-workspace codex binary before it is saved. With `verify`,
+/// also runs it to confirm it is a Codex build that ships `app-server`.
+pub(crate) async fn validate_workspace_codex_bin(
+    codex_bin: Option<&str>,
+    verify: bool,
+) -> Result<(), String> {
+    let codex_bin = match codex_bin.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(codex_bin) => codex_bin,
+        None => return Ok(()),
+    };
+    validate_codex_bin_path(codex_bin)?;
+    if verify {
+        check_codex_installation(Some(codex_bin.to_string())).await?;
+        if !probe_codex_app_server(Some(codex_bin.to_string())).await {
+            return Err(format!(
+                "{codex_bin} does not support `codex app-server`. Update Codex and try again."
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Appends the newest stderr lines to a spawn error so a codex that dies on
+/// startup explains itself instead of just failing to connect.
+fn with_stderr_tail(message: String, tail: &[String]) -> String {
+    if tail.is_empty() {
+        return message;
+    }
+    let start = tail.len().saturating_sub(STDERR_ERROR_LINES);
+    format!("{message}\n\ncodex stderr:\n{}", tail[start..].join("\n"))
+}
+
+/// Stores a freshly spawned session. When another connect for the same
+/// workspace got there first, that session is kept and this one is shut
+/// down, so a workspace never ends up with two codex processes.
+pub(crate) async fn insert_session(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    id: String,
+    session: Arc<WorkspaceSession>,
+) {
+    let duplicate = match sessions.lock().await.entry(id) {
+        Entry::Occupied(_) => Some(session),
+        Entry::Vacant(slot) => {
+            slot.insert(session);
+            None
+        }
+    };
+    if let Some(duplicate) = duplicate {
+        logging::info(
+            "app-server",
+            format!("[{}] already connected; stopping the extra session", duplicate.entry.id),
+        );
+        let _ = duplicate.child.lock().await.kill().await;
+    }
+}
+
+/// Whether a workspace's file watcher is running. Watching can be off by
+/// setting or because the watcher failed to start.
+pub(crate) async fn is_watching(session: Option<&Arc<WorkspaceSession>>) -> bool {
+    match session {
+        Some(session) => session.file_watcher.lock().await.is_some(),
+        None => false,
+    }
+}
+
+/// Latest codex event time per connected workspace, for sorting by activity.
+pub(crate) fn last_activity_by_workspace(
+    sessions: &HashMap<String, Arc<WorkspaceSession>>,
+) -> HashMap<String, i64> {
+    sessions
+        .iter()
+        .filter_map(|(id, session)| {
+            let last_event_ms = session.last_event_ms.load(Ordering::Relaxed);
+            (last_event_ms > 0).then_some((id.clone(), last_event_ms))
+        })
+        .collect()
+}
+
+/// Renders the spawn command for logs, quoting arguments the way a POSIX
+/// shell would need them so the line can be pasted to reproduce a launch.
+fn format_command_line(codex_bin: &str, codex_args: &[String]) -> String {
+    let quote = |arg: &str| {
+        let plain = !arg.is_empty()
+            && arg
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || "-_./=:,@+".contains(ch));
+        if plain {
+            arg.to_string()
+        } else {
+            format!("'{}'", arg.replace('\'', "'\\''"))
+        }
+    };
+    std::iter::once(codex_bin)
+        .chain(std::iter::once("app-server"))
+        .chain(codex_args.iter().map(String::as_str))
+        .map(quote)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Longest part of a malformed line quoted in logs and `codex/parseError`.
+const MALFORMED_PREVIEW_CHARS: usize = 200;
+
+/// Reads one newline-terminated frame however the bytes were split across
+/// pipe reads. Invalid UTF-8 is replaced instead of failing the read, so one
+/// bad line is skipped rather than ending the stream. `None` means EOF.
+async fn read_frame<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+) -> std::io::Result<Option<String>> {
+    buf.clear();
+    if reader.read_until(b'\n', buf).await? == 0 {
+        return Ok(None);
+    }
+    let line = String::from_utf8_lossy(buf);
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+fn frame_preview(line: &str) -> String {
+    match line.char_indices().nth(MALFORMED_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}… ({} bytes)", &line[..end], line.len()),
+        None => line.to_string(),
+    }
+}
+
 pub(crate) async fn spawn_workspace_session<E: EventSink>(
     entry: WorkspaceEntry,
     default_codex_bin: Option<String>,
+    default_codex_args: Vec<String>,
     client_version: String,
     event_sink: E,
     codex_home: Option<PathBuf>,
@@ -186,11 +1357,22 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
         .filter(|value| !value.trim().is_empty())
         .or(default_codex_bin);
     let _ = check_codex_installation(codex_bin.clone()).await?;
+    let codex_args = entry.settings.codex_args.clone().unwrap_or(default_codex_args);
+    validate_codex_args(&codex_args)?;
+    logging::debug(
+        "app-server",
+        format!(
+            "[{}] starting {}",
+            entry.id,
+            format_command_line(codex_bin.as_deref().unwrap_or("codex"), &codex_args)
+        ),
+    );
 
     let mut command = build_codex_command_with_bin(codex_bin);
     command.current_dir(&entry.path);
     command.arg("app-server");
-    if let Some(codex_home) = codex_home {
+    command.args(&codex_args);
+    if let Some(codex_home) = codex_home.as_ref() {
         command.env("CODEX_HOME", codex_home);
     }
     command.stdin(std::process::Stdio::piped());
@@ -202,31 +1384,71 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
     let stdout = child.stdout.take().ok_or("missing stdout")?;
     let stderr = child.stderr.take().ok_or("missing stderr")?;
 
+    let pid = child.id();
     let session = Arc::new(WorkspaceSession {
         entry: entry.clone(),
         child: Mutex::new(child),
         stdin: Mutex::new(stdin),
         pending: Mutex::new(HashMap::new()),
         next_id: AtomicU64::new(1),
+        pid,
+        started_at: Instant::now(),
+        last_event_ms: AtomicI64::new(0),
+        busy_threads: Mutex::new(HashSet::new()),
+        turn_watchers: Mutex::new(HashMap::new()),
+        thread_usage: Mutex::new(HashMap::new()),
+        stderr_tail: Mutex::new(VecDeque::new()),
+        codex_home,
+        server_requests: Mutex::new(HashMap::new()),
+        auto_approve: Mutex::new(entry.settings.auto_approve.clone()),
+        running_turns: Mutex::new(HashMap::new()),
+        read_only_until_ms: AtomicI64::new(0),
+        file_watcher: Mutex::new(None),
+        cancellable: Mutex::new(HashMap::new()),
+        turn_progress: Mutex::new(HashMap::new()),
+        metrics: RequestCounters::default(),
     });
+    if entry.settings.watch_files {
+        session.set_file_watch(true, event_sink.clone()).await;
+    }
 
     let session_clone = Arc::clone(&session);
     let workspace_id = entry.id.clone();
     let event_sink_clone = event_sink.clone();
     tokio::spawn(async move {
-        let mut lines = BufReader::new(stdout).lines();
-        while let Ok(Some(line)) = lines.next_line().await {
+        let mut stdout = BufReader::new(stdout);
+        let mut frame = Vec::new();
+        loop {
+            let line = match read_frame(&mut stdout, &mut frame).await {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(err) => {
+                    logging::warn(
+                        "app-server",
+                        format!("[{workspace_id}] failed to read codex output: {err}"),
+                    );
+                    break;
+                }
+            };
             if line.trim().is_empty() {
                 continue;
             }
+            session_clone
+                .last_event_ms
+                .store(now_ms(), Ordering::Relaxed);
             let value: Value = match serde_json::from_str(&line) {
                 Ok(value) => value,
                 Err(err) => {
+                    let raw = frame_preview(&line);
+                    logging::warn(
+                        "app-server",
+                        format!("[{workspace_id}] skipping malformed codex output ({err}): {raw}"),
+                    );
                     let payload = AppServerEvent {
                         workspace_id: workspace_id.clone(),
                         message: json!({
                             "method": "codex/parseError",
-                            "params": { "error": err.to_string(), "raw": line },
+                            "params": { "error": err.to_string(), "raw": raw },
                         }),
                     };
                     event_sink_clone.emit_app_server_event(payload);
@@ -243,6 +1465,14 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                         let _ = tx.send(value);
                     }
                 } else if has_method {
+                    if let Some(message) = session_clone.try_auto_approve(id, &value).await {
+                        event_sink_clone.emit_app_server_event(AppServerEvent {
+                            workspace_id: workspace_id.clone(),
+                            message,
+                        });
+                        continue;
+                    }
+                    session_clone.track_server_request(id, &value).await;
                     let payload = AppServerEvent {
                         workspace_id: workspace_id.clone(),
                         message: value,
@@ -252,23 +1482,53 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                     let _ = tx.send(value);
                 }
             } else if has_method {
+                let progress = session_clone.track_progress(&value).await;
+                let follow_up = session_clone.observe_notification(&value).await;
                 let payload = AppServerEvent {
                     workspace_id: workspace_id.clone(),
                     message: value,
                 };
                 event_sink_clone.emit_app_server_event(payload);
+                if let Some(progress) = progress {
+                    event_sink_clone.emit_turn_progress(progress);
+                }
+                if let Some(message) = follow_up {
+                    event_sink_clone.emit_app_server_event(AppServerEvent {
+                        workspace_id: workspace_id.clone(),
+                        message,
+                    });
+                }
             }
         }
+        // stdout only closes when codex goes away, so report the exit now
+        // instead of on the next request.
+        let exit_code = session_clone.wait_for_exit_code().await;
+        session_clone.file_watcher.lock().await.take();
+        logging::warn(
+            "app-server",
+            format!(
+                "[{workspace_id}] codex exited with code {}",
+                exit_code.map_or_else(|| "unknown".to_string(), |code| code.to_string())
+            ),
+        );
+        event_sink_clone.emit_session_exited(SessionExited {
+            workspace_id,
+            exit_code,
+            stderr_tail: session_clone.stderr_tail().await,
+        });
     });
 
     let workspace_id = entry.id.clone();
     let event_sink_clone = event_sink.clone();
-    tokio::spawn(async move {
+    let session_clone = Arc::clone(&session);
+    let stderr_task = tokio::spawn(async move {
         let mut lines = BufReader::new(stderr).lines();
         while let Ok(Some(line)) = lines.next_line().await {
             if line.trim().is_empty() {
                 continue;
             }
+            session_clone.record_stderr(&line).await;
+            logging::warn("app-server", format!("[{workspace_id}] {line}"));
             let payload = AppServerEvent {
                 workspace_id: workspace_id.clone(),
                 message: json!({
@@ -294,17 +1554,24 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
     .await;
     let init_response = match init_result {
         Ok(response) => response,
-        Err(_) => {
+        Err(_) => Err(
+            "Codex app-server did not respond to initialize. Check that `codex app-server` works in Terminal."
+                .to_string(),
+        ),
+    };
+    let initialized = match init_response {
+        Ok(_) => session.send_notification("initialized", None).await,
+        Err(err) => Err(err),
+    };
+    if let Err(err) = initialized {
+        {
             let mut child = session.child.lock().await;
             let _ = child.kill().await;
-            return Err(
-                "Codex app-server did not respond to initialize. Check that `codex app-server` works in Terminal."
-                    .to_string(),
-            );
         }
-    };
-    init_response?;
-    session.send_notification("initialized", None).await?;
+        // Let the reader drain whatever codex printed before it died.
+        let _ = timeout(Duration::from_secs(1), stderr_task).await;
+        return Err(with_stderr_tail(err, &session.stderr_tail().await));
+    }
 
     let payload = AppServerEvent {
         workspace_id: entry.id.clone(),
@@ -317,3 +1584,585 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
 
     Ok(session)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Stdio;
+
+    #[test]
+    fn validate_codex_bin_path_requires_an_executable_file() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&temp_dir).expect("create temp dir");
+        let bin = temp_dir.join("codex");
+        let bin_str = bin.to_string_lossy().to_string();
+
+        assert!(validate_codex_bin_path("sh").is_ok());
+        let typo = format!("codx-{}", uuid::Uuid::new_v4());
+        let missing = validate_codex_bin_path(&typo).expect_err("unknown command");
+        assert!(missing.contains("not found"));
+        let missing = validate_codex_bin_path(&bin_str).expect_err("missing binary");
+        assert!(missing.contains("not found"));
+        assert!(validate_codex_bin_path(&temp_dir.to_string_lossy()).is_err());
+
+        std::fs::write(&bin, "#!/bin/sh\n").expect("write stub binary");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let error = validate_codex_bin_path(&bin_str).expect_err("not executable");
+            assert!(error.contains("not executable"));
+            std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755))
+                .expect("chmod stub binary");
+        }
+        assert!(validate_codex_bin_path(&bin_str).is_ok());
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    fn spawn_silent_session() -> Arc<WorkspaceSession> {
+        // `cat` swallows every request without ever answering, like a wedged codex.
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg("cat > /dev/null")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .expect("spawn stub backend");
+        let stdin = child.stdin.take().expect("stub stdin");
+        let pid = child.id();
+        Arc::new(WorkspaceSession {
+            entry: WorkspaceEntry {
+                id: "stub".to_string(),
+                name: "stub".to_string(),
+                path: "/tmp".to_string(),
+                codex_bin: None,
+                kind: Default::default(),
+                parent_id: None,
+                worktree: None,
+                settings: Default::default(),
+            },
+            child: Mutex::new(child),
+            stdin: Mutex::new(stdin),
+            pending: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            pid,
+            started_at: Instant::now(),
+            last_event_ms: AtomicI64::new(0),
+            busy_threads: Mutex::new(HashSet::new()),
+            turn_watchers: Mutex::new(HashMap::new()),
+            thread_usage: Mutex::new(HashMap::new()),
+            stderr_tail: Mutex::new(VecDeque::new()),
+            codex_home: None,
+            server_requests: Mutex::new(HashMap::new()),
+            auto_approve: Mutex::new(AutoApprovePolicy::Never),
+            running_turns: Mutex::new(HashMap::new()),
+            read_only_until_ms: AtomicI64::new(0),
+            file_watcher: Mutex::new(None),
+            cancellable: Mutex::new(HashMap::new()),
+            turn_progress: Mutex::new(HashMap::new()),
+            metrics: RequestCounters::default(),
+        })
+    }
+
+    #[test]
+    fn send_request_times_out_and_clears_pending() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let session = spawn_silent_session();
+            let result = session
+                .send_request_with_timeout("model/list", json!({}), Duration::from_millis(50))
+                .await;
+            let error = result.expect_err("request should time out");
+            assert!(error.starts_with("Timeout"));
+            assert!(session.pending.lock().await.is_empty());
+            let _ = session.child.lock().await.kill().await;
+        });
+    }
+
+    #[test]
+    fn cancel_request_fails_the_tagged_call_immediately() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let session = spawn_silent_session();
+            let waiting = Arc::clone(&session);
+            let call = tokio::spawn(async move {
+                waiting
+                    .send_cancellable_request(
+                        "thread/resume",
+                        json!({ "threadId": "thread-1" }),
+                        Some("resume-1"),
+                        None,
+                    )
+                    .await
+            });
+            while !session.cancellable.lock().await.contains_key("resume-1") {
+                tokio::task::yield_now().await;
+            }
+
+            let canceled = session.cancel_request("resume-1").await;
+            assert_eq!(canceled, json!({ "canceled": true, "interrupted": false }));
+            let result = call.await.expect("join");
+            assert_eq!(result, Err("request canceled".to_string()));
+            assert!(session.pending.lock().await.is_empty());
+            assert!(session.cancellable.lock().await.is_empty());
+            let unknown = session.cancel_request("resume-1").await;
+            assert_eq!(unknown["canceled"], json!(false));
+            let _ = session.child.lock().await.kill().await;
+        });
+    }
+
+    #[test]
+    fn session_metrics_count_timeouts_and_average_latency() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let session = spawn_silent_session();
+            let result = session
+                .send_request_with_timeout("model/list", json!({}), Duration::from_millis(20))
+                .await;
+            assert!(result.is_err());
+            session.metrics.record_response(Duration::from_millis(80), false);
+            session.metrics.record_response(Duration::from_millis(160), true);
+
+            let metrics = session.metrics.snapshot();
+            assert_eq!(metrics.requests_sent, 1);
+            assert_eq!(metrics.timeouts, 1);
+            assert_eq!(metrics.responses_received, 2);
+            assert_eq!(metrics.errors, 1);
+            assert_eq!(metrics.avg_latency_ms, Some(90.0));
+            let _ = session.child.lock().await.kill().await;
+        });
+    }
+
+    #[test]
+    fn frames_are_reassembled_across_arbitrary_read_boundaries() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let large = "x".repeat(256 * 1024);
+            let mut stream = Vec::new();
+            stream.extend_from_slice(b"{\"id\":1,\"result\":{}}\n");
+            stream.extend_from_slice(b"{\"method\":\"turn/started\"\r\n");
+            stream.extend_from_slice(b"{\"method\":\"bad\xff\"}\n");
+            stream.extend_from_slice(format!("{{\"id\":2,\"result\":\"{large}\"}}\n").as_bytes());
+            stream.extend_from_slice(b"{\"method\":\"last\"}");
+
+            // A tiny pipe forces short reads; the writer varies its chunk sizes too.
+            let (mut writer, reader) = tokio::io::duplex(7);
+            let feed = tokio::spawn(async move {
+                let sizes = [1, 5, 2, 13, 3, 64, 4096];
+                let mut offset = 0;
+                for size in sizes.iter().cycle() {
+                    if offset >= stream.len() {
+                        break;
+                    }
+                    let end = (offset + size).min(stream.len());
+                    writer.write_all(&stream[offset..end]).await.expect("write");
+                    offset = end;
+                }
+            });
+
+            let mut reader = BufReader::new(reader);
+            let mut buf = Vec::new();
+            let mut frames = Vec::new();
+            while let Some(line) = read_frame(&mut reader, &mut buf).await.expect("read") {
+                frames.push(line);
+            }
+            feed.await.expect("feed");
+
+            assert_eq!(frames.len(), 5);
+            assert_eq!(frames[0], r#"{"id":1,"result":{}}"#);
+            assert!(serde_json::from_str::<Value>(&frames[1]).is_err());
+            assert_eq!(frames[2], "{\"method\":\"bad\u{fffd}\"}");
+            let response: Value = serde_json::from_str(&frames[3]).expect("large frame");
+            assert_eq!(response["result"].as_str().map(str::len), Some(large.len()));
+            assert_eq!(frames[4], r#"{"method":"last"}"#);
+            let preview = frame_preview(&frames[3]);
+            assert!(preview.len() < 300);
+            assert!(preview.ends_with(&format!("({} bytes)", frames[3].len())));
+        });
+    }
+
+    #[test]
+    fn command_lines_quote_arguments_that_need_it() {
+        let args = vec![
+            "--config".to_string(),
+            "model_provider=\"azure\"".to_string(),
+            "-c".to_string(),
+            "notes=it's here".to_string(),
+        ];
+        assert_eq!(
+            format_command_line("/opt/codex bin/codex", &args),
+            concat!(
+                "'/opt/codex bin/codex' app-server --config ",
+                r#"'model_provider="azure"' -c 'notes=it'\''s here'"#
+            )
+        );
+    }
+
+    #[test]
+    fn thread_search_matches_preview_and_normalizes_timestamps() {
+        let thread = json!({
+            "id": "t1",
+            "preview": "Fix the Login redirect",
+            "cwd": "C:\\repo\\",
+            "updatedAt": 1_700_000_000,
+        });
+        assert!(thread_matches_query(&thread, "login"));
+        assert!(!thread_matches_query(&thread, "logout"));
+        assert!(thread_matches_query(&json!({ "title": "Release notes" }), "notes"));
+        assert_eq!(thread_updated_ms(&thread), 1_700_000_000_000);
+        let created = json!({ "createdAt": 1_700_000_000_123i64 });
+        assert_eq!(thread_updated_ms(&created), 1_700_000_000_123);
+        assert_eq!(normalize_thread_cwd("C:\\repo\\"), "C:/repo");
+
+        let summary = thread_summary(&thread).expect("summary");
+        assert_eq!(summary.id, "t1");
+        assert_eq!(summary.cwd, "C:\\repo\\");
+        assert!(thread_summary(&json!({ "id": "", "preview": "x" })).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cwd_matcher_follows_symlinked_workspace_paths() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-cwd-{}", std::process::id()));
+        let real = root.join("real");
+        let link = root.join("link");
+        std::fs::create_dir_all(&real).expect("create real dir");
+        std::os::unix::fs::symlink(&real, &link).expect("create symlink");
+
+        let mut matcher = WorkspaceCwdMatcher::new(&link.to_string_lossy());
+        assert!(matcher.matches(&format!("{}/", link.to_string_lossy())));
+        assert!(matcher.matches(&real.to_string_lossy()));
+        assert!(!matcher.matches(&root.to_string_lossy()));
+        assert!(!matcher.matches(""));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn spawn_errors_quote_the_last_stderr_lines() {
+        assert_eq!(with_stderr_tail("failed".to_string(), &[]), "failed");
+        let tail: Vec<String> = (0..15).map(|index| format!("line {index}")).collect();
+        let message = with_stderr_tail("failed".to_string(), &tail);
+        assert!(message.starts_with("failed\n\ncodex stderr:\nline 5\n"));
+        assert!(message.ends_with("line 14"));
+        assert!(!message.contains("line 4\n"));
+    }
+
+    #[test]
+    fn stderr_tail_keeps_only_the_newest_lines() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let session = spawn_silent_session();
+            for index in 0..(STDERR_TAIL_LINES + 5) {
+                session.record_stderr(&format!("line {index}")).await;
+            }
+            let tail = session.stderr_tail().await;
+            assert_eq!(tail.len(), STDERR_TAIL_LINES);
+            assert_eq!(tail.first().map(String::as_str), Some("line 5"));
+
+            let _ = session.child.lock().await.kill().await;
+            assert_eq!(session.wait_for_exit_code().await, None);
+        });
+    }
+
+    #[test]
+    fn server_requests_stay_listed_until_answered() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let session = spawn_silent_session();
+            let approval = |id: u64, thread: &str| {
+                json!({
+                    "id": id,
+                    "method": "item/commandExecution/requestApproval",
+                    "params": { "threadId": thread, "command": ["rm", "-rf", "build"] }
+                })
+            };
+            session.track_server_request(7, &approval(7, "thread-1")).await;
+            session.track_server_request(3, &approval(3, "thread-2")).await;
+
+            let pending = session.pending_server_requests().await;
+            assert_eq!(
+                pending.iter().map(|request| request.request_id).collect::<Vec<_>>(),
+                vec![3, 7]
+            );
+            assert_eq!(pending[0].thread_id.as_deref(), Some("thread-2"));
+            assert_eq!(pending[0].description, "Approve running `rm -rf build`");
+
+            session.send_response(3, json!({ "decision": "accept" })).await.expect("respond");
+            session
+                .observe_notification(&json!({
+                    "method": "turn/completed",
+                    "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } }
+                }))
+                .await;
+            assert!(session.pending_server_requests().await.is_empty());
+
+            let _ = session.child.lock().await.kill().await;
+        });
+    }
+
+    #[test]
+    fn abort_all_turns_locks_sessions_read_only() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let session = spawn_silent_session();
+            session
+                .observe_notification(&json!({
+                    "method": "turn/started",
+                    "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } }
+                }))
+                .await;
+            assert_eq!(
+                session.running_turns.lock().await.get("thread-1").map(String::as_str),
+                Some("turn-1")
+            );
+            session
+                .observe_notification(&json!({
+                    "method": "turn/completed",
+                    "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } }
+                }))
+                .await;
+            assert!(session.running_turns.lock().await.is_empty());
+
+            assert!(!session.read_only_locked());
+            let result = abort_all_turns(vec![Arc::clone(&session)]).await;
+            assert_eq!(result["aborted"], 0);
+            assert!(session.read_only_locked());
+            session.set_auto_approve(AutoApprovePolicy::ReadOnlyCommands).await;
+            let approval = json!({
+                "id": 1,
+                "method": "item/commandExecution/requestApproval",
+                "params": { "threadId": "thread-1", "command": "ls" }
+            });
+            assert!(session.try_auto_approve(1, &approval).await.is_none());
+
+            let _ = session.child.lock().await.kill().await;
+        });
+    }
+
+    #[test]
+    fn second_turn_on_busy_thread_is_rejected_until_completion() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let session = spawn_silent_session();
+            session
+                .busy_threads
+                .lock()
+                .await
+                .insert("thread-1".to_string());
+
+            let error = session
+                .start_turn("thread-1", json!({ "threadId": "thread-1" }), None)
+                .await
+                .expect_err("busy thread");
+            assert!(error.starts_with("ThreadBusy"));
+
+            session
+                .observe_notification(&json!({
+                    "method": "turn/completed",
+                    "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } }
+                }))
+                .await;
+            assert!(session.busy_threads.lock().await.is_empty());
+            let _ = session.child.lock().await.kill().await;
+        });
+    }
+
+    #[test]
+    fn watched_turn_resolves_with_final_agent_text_and_usage() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let session = spawn_silent_session();
+            let rx = session.watch_turn("thread-1").await.expect("watch");
+            assert!(session.watch_turn("thread-1").await.is_err());
+
+            for message in [
+                json!({
+                    "method": "item/completed",
+                    "params": {
+                        "threadId": "thread-1",
+                        "item": { "id": "a", "type": "agentMessage", "text": "draft" }
+                    }
+                }),
+                json!({
+                    "method": "item/completed",
+                    "params": {
+                        "threadId": "thread-1",
+                        "item": { "id": "b", "type": "agentMessage", "text": "final" }
+                    }
+                }),
+                json!({
+                    "method": "thread/tokenUsage/updated",
+                    "params": { "threadId": "thread-1", "tokenUsage": { "total": 42 } }
+                }),
+                json!({
+                    "method": "turn/completed",
+                    "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } }
+                }),
+            ] {
+                session.observe_notification(&message).await;
+            }
+
+            let completion = rx.await.expect("sender").expect("completion");
+            assert_eq!(completion.text, "final");
+            assert_eq!(completion.turn_id.as_deref(), Some("turn-1"));
+            assert_eq!(completion.token_usage, Some(json!({ "total": 42 })));
+            let _ = session.child.lock().await.kill().await;
+        });
+    }
+
+    #[test]
+    fn turn_progress_reports_phase_changes_once() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let session = spawn_silent_session();
+            let delta =
+                |method: &str| json!({ "method": method, "params": { "threadId": "thread-1" } });
+            let mut phases = Vec::new();
+            for message in [
+                json!({
+                    "method": "turn/started",
+                    "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } }
+                }),
+                delta("item/reasoning/textDelta"),
+                json!({
+                    "method": "item/started",
+                    "params": {
+                        "threadId": "thread-1",
+                        "item": { "type": "mcpToolCall", "server": "docs", "tool": "search" }
+                    }
+                }),
+                json!({
+                    "method": "item/started",
+                    "params": { "threadId": "thread-1", "item": { "type": "commandExecution" } }
+                }),
+                delta("item/agentMessage/delta"),
+                delta("item/agentMessage/delta"),
+                json!({
+                    "method": "turn/completed",
+                    "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } }
+                }),
+            ] {
+                if let Some(progress) = session.track_progress(&message).await {
+                    assert_eq!(progress.turn_id.as_deref(), Some("turn-1"));
+                    phases.push((progress.phase, progress.tool_name));
+                }
+            }
+
+            assert_eq!(
+                phases,
+                vec![
+                    (TurnPhase::Thinking, None),
+                    (TurnPhase::Tool, Some("docs/search".to_string())),
+                    (TurnPhase::Tool, Some("commandExecution".to_string())),
+                    (TurnPhase::Writing, None),
+                    (TurnPhase::Done, None),
+                ]
+            );
+            assert!(session.turn_progress.lock().await.is_empty());
+            let _ = session.child.lock().await.kill().await;
+        });
+    }
+
+    #[test]
+    fn turn_usage_is_derived_from_reported_thread_totals() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let session = spawn_silent_session();
+            let report = |input: u64, output: u64| {
+                json!({
+                    "method": "thread/tokenUsage/updated",
+                    "params": {
+                        "threadId": "thread-1",
+                        "tokenUsage": {
+                            "total": { "inputTokens": input, "outputTokens": output }
+                        }
+                    }
+                })
+            };
+            let completed = json!({
+                "method": "turn/completed",
+                "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } }
+            });
+
+            session.observe_notification(&report(100, 20)).await;
+            let event = session
+                .observe_notification(&completed)
+                .await
+                .expect("usage event");
+            assert_eq!(event["params"]["usage"]["total"], json!(120));
+
+            session.observe_notification(&report(150, 30)).await;
+            let event = session
+                .observe_notification(&completed)
+                .await
+                .expect("usage event");
+            assert_eq!(event["params"]["usage"]["inputTokens"], json!(50));
+            assert_eq!(event["params"]["usage"]["outputTokens"], json!(10));
+            assert_eq!(session.thread_usage("thread-1").await.total, 180);
+            let _ = session.child.lock().await.kill().await;
+        });
+    }
+
+    #[test]
+    fn rate_limit_cache_expires_at_earliest_reset() {
+        let fetched_at_ms = 1_700_000_000_000;
+        let response = json!({
+            "result": {
+                "rateLimits": {
+                    "primary": { "usedPercent": 10, "resetsAt": 1_700_000_060 },
+                    "secondary": { "usedPercent": 5, "resetsAt": 1_700_500_000 }
+                }
+            }
+        });
+        assert_eq!(
+            rate_limits_expiry_ms(&response, fetched_at_ms),
+            1_700_000_060_000
+        );
+        assert_eq!(
+            rate_limits_expiry_ms(&json!({ "result": {} }), fetched_at_ms),
+            fetched_at_ms + RATE_LIMIT_CACHE_MAX_AGE_MS
+        );
+    }
+
+    #[test]
+    fn turn_requests_get_a_longer_timeout() {
+        assert!(default_request_timeout("turn/start") > default_request_timeout("model/list"));
+        assert_eq!(
+            default_request_timeout("model/list"),
+            DEFAULT_REQUEST_TIMEOUT
+        );
+    }
+}
@@ -16,7 +16,76 @@ pub(crate) struct TerminalOutput {
     pub(crate) data: String,
 }
 
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct TerminalExit {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    #[serde(rename = "terminalId")]
+    pub(crate) terminal_id: String,
+}
+
+/// Sent as soon as a codex app-server process goes away, so listeners can
+/// mark the workspace disconnected without waiting for the next request.
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct SessionExited {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    #[serde(rename = "exitCode")]
+    pub(crate) exit_code: Option<i32>,
+    #[serde(rename = "stderrTail")]
+    pub(crate) stderr_tail: Vec<String>,
+}
+
+/// Debounced batch of file changes under a watched workspace. Paths are
+/// relative to the workspace root and use `/` separators.
+#[derive(Debug, Serialize, Clone, Default, PartialEq)]
+pub(crate) struct WorkspaceFilesChanged {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    pub(crate) added: Vec<String>,
+    pub(crate) modified: Vec<String>,
+    pub(crate) deleted: Vec<String>,
+}
+
+/// Sent after watched changes that can affect `git status`, at most once per
+/// debounce window.
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct GitStatusChanged {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum TurnPhase {
+    Thinking,
+    Tool,
+    Writing,
+    Done,
+}
+
+/// Coarse progress of a running turn, derived from codex item events so
+/// clients can show what the agent is doing without parsing items. Only
+/// sent when the phase or tool changes.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct TurnProgress {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    #[serde(rename = "threadId")]
+    pub(crate) thread_id: String,
+    #[serde(rename = "turnId")]
+    pub(crate) turn_id: Option<String>,
+    pub(crate) phase: TurnPhase,
+    #[serde(rename = "toolName")]
+    pub(crate) tool_name: Option<String>,
+}
+
 pub(crate) trait EventSink: Clone + Send + Sync + 'static {
     fn emit_app_server_event(&self, event: AppServerEvent);
     fn emit_terminal_output(&self, event: TerminalOutput);
+    fn emit_terminal_exit(&self, event: TerminalExit);
+    fn emit_session_exited(&self, event: SessionExited);
+    fn emit_workspace_files_changed(&self, event: WorkspaceFilesChanged);
+    fn emit_git_status_changed(&self, event: GitStatusChanged);
+    fn emit_turn_progress(&self, event: TurnProgress);
 }
@@ -1,2 +1,4 @@
 pub(crate) mod app_server;
+pub(crate) mod auto_approve;
 pub(crate) mod events;
+pub(crate) mod workspace_watch;
@@ -0,0 +1,379 @@
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::event::{EventKind, ModifyKind};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::backend::events::{EventSink, GitStatusChanged, WorkspaceFilesChanged};
+
+/// Changes arriving within this window after the first one are reported
+/// together, so a burst of agent edits becomes a single event.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+/// Longer window for `git-status-changed`, since each one makes clients rerun
+/// `git status` and large agent edits arrive as many separate batches.
+const GIT_STATUS_DEBOUNCE: Duration = Duration::from_millis(500);
+/// Entries under `.git` that change what `git status` reports: staging,
+/// commits and checkouts.
+const GIT_STATE_ENTRIES: &[&str] = &["index", "HEAD", "refs", "packed-refs"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FileChange {
+    Added,
+    Modified,
+    Deleted,
+}
+
+/// Folds a later change into the one already recorded for the same path.
+/// `None` means the path is back where it started, as when a temp file is
+/// created and removed inside one batch.
+fn merge_change(previous: Option<FileChange>, next: FileChange) -> Option<FileChange> {
+    match (previous, next) {
+        (None, next) => Some(next),
+        (Some(FileChange::Added), FileChange::Deleted) => None,
+        (Some(FileChange::Added), _) => Some(FileChange::Added),
+        (Some(FileChange::Deleted), FileChange::Deleted) => Some(FileChange::Deleted),
+        (Some(FileChange::Deleted), _) => Some(FileChange::Modified),
+        (Some(FileChange::Modified), FileChange::Deleted) => Some(FileChange::Deleted),
+        (Some(FileChange::Modified), _) => Some(FileChange::Modified),
+    }
+}
+
+fn classify(kind: &EventKind, path: &Path) -> Option<FileChange> {
+    match kind {
+        EventKind::Create(_) => Some(FileChange::Added),
+        EventKind::Remove(_) => Some(FileChange::Deleted),
+        // Renames report each side on its own; whether the path still exists
+        // tells which side this one is.
+        EventKind::Modify(ModifyKind::Name(_)) => Some(if path.exists() {
+            FileChange::Added
+        } else {
+            FileChange::Deleted
+        }),
+        EventKind::Modify(ModifyKind::Metadata(_)) => None,
+        EventKind::Modify(_) | EventKind::Any => Some(FileChange::Modified),
+        EventKind::Access(_) | EventKind::Other => None,
+    }
+}
+
+/// The directories git keeps the index, HEAD and refs in: the git dir and,
+/// for linked worktrees, the shared common dir. Either can sit outside the
+/// workspace, as for worktrees or a workspace that is a repo subfolder.
+/// Empty when the workspace is not in a repo.
+async fn resolve_git_dirs(root: &Path) -> Vec<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--absolute-git-dir", "--git-common-dir"])
+        .current_dir(root)
+        .output()
+        .await;
+    let Ok(output) = output else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let dir = root.join(line.trim());
+        let dir = dir.canonicalize().unwrap_or(dir);
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Uses the workspace's top-level `.gitignore` and the repo's
+/// `info/exclude`.
+fn build_ignore(root: &Path, git_dir: Option<&Path>) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    let _ = builder.add(root.join(".gitignore"));
+    let git_dir = git_dir.map_or_else(|| root.join(".git"), Path::to_path_buf);
+    let _ = builder.add(git_dir.join("info/exclude"));
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+/// Whether a path relative to a git dir is one `git status` depends on.
+fn is_git_state_path(git_path: &Path) -> bool {
+    match git_path.components().next() {
+        Some(Component::Normal(entry)) => GIT_STATE_ENTRIES.iter().any(|name| entry == *name),
+        _ => false,
+    }
+}
+
+struct ChangeBatch<'a> {
+    root: &'a Path,
+    /// Git dirs watched on their own because they are outside `root`.
+    git_dirs: &'a [PathBuf],
+    ignore: &'a Gitignore,
+    changes: BTreeMap<String, FileChange>,
+    git_state_changed: bool,
+}
+
+impl<'a> ChangeBatch<'a> {
+    fn new(root: &'a Path, git_dirs: &'a [PathBuf], ignore: &'a Gitignore) -> Self {
+        Self {
+            root,
+            git_dirs,
+            ignore,
+            changes: BTreeMap::new(),
+            git_state_changed: false,
+        }
+    }
+
+    fn record(&mut self, path: &Path, change: FileChange) {
+        if let Some(git_path) = self
+            .git_dirs
+            .iter()
+            .find_map(|dir| path.strip_prefix(dir).ok())
+        {
+            self.git_state_changed |= is_git_state_path(git_path);
+            return;
+        }
+        let Ok(relative) = path.strip_prefix(self.root) else {
+            return;
+        };
+        if let Ok(git_path) = relative.strip_prefix(".git") {
+            self.git_state_changed |= is_git_state_path(git_path);
+            return;
+        }
+        if relative.as_os_str().is_empty()
+            || relative
+                .components()
+                .any(|component| component == Component::Normal(".git".as_ref()))
+        {
+            return;
+        }
+        // Directories show up through the files inside them.
+        if change != FileChange::Deleted && path.is_dir() {
+            return;
+        }
+        if self
+            .ignore
+            .matched_path_or_any_parents(relative, path.is_dir())
+            .is_ignore()
+        {
+            return;
+        }
+        let key = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        match merge_change(self.changes.get(&key).copied(), change) {
+            Some(merged) => {
+                self.changes.insert(key, merged);
+            }
+            None => {
+                self.changes.remove(&key);
+            }
+        }
+    }
+
+    fn record_event(&mut self, event: &Event) {
+        for path in &event.paths {
+            if let Some(change) = classify(&event.kind, path) {
+                self.record(path, change);
+            }
+        }
+    }
+
+    /// Working tree edits that survive the ignore rules, or index and ref
+    /// updates, can change `git status`.
+    fn affects_git_status(&self) -> bool {
+        self.git_state_changed || !self.changes.is_empty()
+    }
+
+    fn into_event(self, workspace_id: &str) -> Option<WorkspaceFilesChanged> {
+        if self.changes.is_empty() {
+            return None;
+        }
+        let mut event = WorkspaceFilesChanged {
+            workspace_id: workspace_id.to_string(),
+            ..WorkspaceFilesChanged::default()
+        };
+        for (path, change) in self.changes {
+            match change {
+                FileChange::Added => event.added.push(path),
+                FileChange::Modified => event.modified.push(path),
+                FileChange::Deleted => event.deleted.push(path),
+            }
+        }
+        Some(event)
+    }
+}
+
+async fn forward_changes<E: EventSink>(
+    workspace_id: String,
+    root: PathBuf,
+    external_git_dirs: Vec<PathBuf>,
+    ignore: Gitignore,
+    mut events: mpsc::UnboundedReceiver<Event>,
+    git_status: Option<mpsc::UnboundedSender<()>>,
+    event_sink: E,
+) {
+    while let Some(first) = events.recv().await {
+        let mut batch = ChangeBatch::new(&root, &external_git_dirs, &ignore);
+        batch.record_event(&first);
+        let deadline = tokio::time::Instant::now() + DEBOUNCE;
+        while let Ok(Some(event)) = tokio::time::timeout_at(deadline, events.recv()).await {
+            batch.record_event(&event);
+        }
+        if batch.affects_git_status() {
+            if let Some(git_status) = git_status.as_ref() {
+                let _ = git_status.send(());
+            }
+        }
+        if let Some(changed) = batch.into_event(&workspace_id) {
+            event_sink.emit_workspace_files_changed(changed);
+        }
+    }
+}
+
+async fn forward_git_status<E: EventSink>(
+    workspace_id: String,
+    mut signals: mpsc::UnboundedReceiver<()>,
+    event_sink: E,
+) {
+    while signals.recv().await.is_some() {
+        let deadline = tokio::time::Instant::now() + GIT_STATUS_DEBOUNCE;
+        while let Ok(Some(())) = tokio::time::timeout_at(deadline, signals.recv()).await {}
+        event_sink.emit_git_status_changed(GitStatusChanged {
+            workspace_id: workspace_id.clone(),
+        });
+    }
+}
+
+/// Keeps a workspace folder watched. Dropping it stops both the OS watcher
+/// and the tasks that batch its events.
+pub(crate) struct WorkspaceWatcher {
+    _watcher: RecommendedWatcher,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Drop for WorkspaceWatcher {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+pub(crate) async fn watch_workspace<E: EventSink>(
+    workspace_id: String,
+    root: PathBuf,
+    event_sink: E,
+) -> Result<WorkspaceWatcher, String> {
+    let git_dirs = resolve_git_dirs(&root).await;
+    let ignore = build_ignore(&root, git_dirs.first().map(PathBuf::as_path));
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.clone());
+    let external_git_dirs: Vec<PathBuf> = git_dirs
+        .iter()
+        .filter(|dir| !dir.starts_with(&canonical_root))
+        .cloned()
+        .collect();
+    let (tx, rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+        if let Ok(event) = result {
+            let _ = tx.send(event);
+        }
+    })
+    .map_err(|err| format!("Failed to start file watcher: {err}"))?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|err| format!("Failed to watch {}: {err}", root.display()))?;
+    for dir in &external_git_dirs {
+        watcher
+            .watch(dir, RecursiveMode::Recursive)
+            .map_err(|err| format!("Failed to watch {}: {err}", dir.display()))?;
+    }
+    let mut tasks = Vec::new();
+    let git_status = (!git_dirs.is_empty()).then(|| {
+        let (status_tx, status_rx) = mpsc::unbounded_channel();
+        tasks.push(tokio::spawn(forward_git_status(
+            workspace_id.clone(),
+            status_rx,
+            event_sink.clone(),
+        )));
+        status_tx
+    });
+    tasks.push(tokio::spawn(forward_changes(
+        workspace_id,
+        root,
+        external_git_dirs,
+        ignore,
+        rx,
+        git_status,
+        event_sink,
+    )));
+    Ok(WorkspaceWatcher {
+        _watcher: watcher,
+        tasks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_ignore, merge_change, ChangeBatch, FileChange};
+
+    #[test]
+    fn merged_changes_keep_the_net_effect() {
+        use FileChange::{Added, Deleted, Modified};
+        assert_eq!(merge_change(Some(Added), Modified), Some(Added));
+        assert_eq!(merge_change(Some(Added), Deleted), None);
+        assert_eq!(merge_change(Some(Deleted), Added), Some(Modified));
+        assert_eq!(merge_change(Some(Modified), Deleted), Some(Deleted));
+        assert_eq!(merge_change(None, Modified), Some(Modified));
+    }
+
+    #[test]
+    fn change_batches_skip_ignored_and_git_paths() {
+        let root =
+            std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("src")).expect("create src");
+        std::fs::write(root.join(".gitignore"), "target/\n*.log\n").expect("write gitignore");
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").expect("write file");
+        let ignore = build_ignore(&root, None);
+
+        let mut batch = ChangeBatch::new(&root, &[], &ignore);
+        batch.record(&root.join("src/main.rs"), FileChange::Modified);
+        batch.record(&root.join("src"), FileChange::Added);
+        batch.record(&root.join("target/debug/app"), FileChange::Added);
+        batch.record(&root.join("build.log"), FileChange::Modified);
+        batch.record(&root.join(".git/index"), FileChange::Modified);
+        batch.record(&root.join("notes.tmp"), FileChange::Added);
+        batch.record(&root.join("notes.tmp"), FileChange::Deleted);
+        batch.record(&root.join("old.rs"), FileChange::Deleted);
+        let event = batch.into_event("ws").expect("changes");
+
+        assert_eq!(event.workspace_id, "ws");
+        assert!(event.added.is_empty());
+        assert_eq!(event.modified, vec!["src/main.rs".to_string()]);
+        assert_eq!(event.deleted, vec!["old.rs".to_string()]);
+        assert!(ChangeBatch::new(&root, &[], &ignore)
+            .into_event("ws")
+            .is_none());
+
+        let mut git_only = ChangeBatch::new(&root, &[], &ignore);
+        git_only.record(&root.join(".git/objects/ab/cdef"), FileChange::Added);
+        git_only.record(&root.join("build.log"), FileChange::Modified);
+        assert!(!git_only.affects_git_status());
+        git_only.record(&root.join(".git/index"), FileChange::Added);
+        assert!(git_only.affects_git_status());
+        assert!(git_only.into_event("ws").is_none());
+
+        // A linked worktree keeps its git dir inside the main repo's.
+        let external = vec![std::env::temp_dir().join("main-repo/.git/worktrees/feature")];
+        let mut worktree = ChangeBatch::new(&root, &external, &ignore);
+        worktree.record(&external[0].join("logs/HEAD"), FileChange::Modified);
+        assert!(!worktree.affects_git_status());
+        worktree.record(&external[0].join("index"), FileChange::Modified);
+        assert!(worktree.affects_git_status());
+        assert!(worktree.into_event("ws").is_none());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
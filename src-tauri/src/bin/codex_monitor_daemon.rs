@@ -5,6 +5,11 @@ mod backend;
 mod codex_home;
 #[path = "../codex_config.rs"]
 mod codex_config;
+#[allow(dead_code)]
+#[path = "../local_http.rs"]
+mod local_http;
+#[path = "../logging.rs"]
+mod logging;
 #[path = "../rules.rs"]
 mod rules;
 #[path = "../storage.rs"]
@@ -12,6 +17,10 @@ mod storage;
 #[allow(dead_code)]
 #[path = "../types.rs"]
 mod types;
+#[path = "../utils.rs"]
+mod utils;
+#[path = "../workspace_files.rs"]
+mod workspace_files;
 
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
@@ -19,22 +28,52 @@ use std::env;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use ignore::WalkBuilder;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::process::Command;
-use tokio::sync::{broadcast, mpsc, Mutex};
+use tokio::sync::{broadcast, mpsc, Mutex, Semaphore};
+use tokio::task::JoinSet;
 use uuid::Uuid;
 
-use backend::app_server::{spawn_workspace_session, WorkspaceSession};
-use backend::events::{AppServerEvent, EventSink, TerminalOutput};
-use storage::{read_settings, read_workspaces, write_settings, write_workspaces};
+use backend::app_server::{
+    abort_all_turns, insert_session, is_watching, last_activity_by_workspace,
+    rate_limits_expiry_ms, spawn_workspace_session, validate_workspace_codex_bin, CachedRateLimits,
+    ThreadListOptions, WorkspaceSession,
+};
+use backend::events::{
+    AppServerEvent, EventSink, GitStatusChanged, SessionExited, TerminalExit, TerminalOutput,
+    TurnProgress, WorkspaceFilesChanged,
+};
+use local_http::{read_request_head, write_response};
+use storage::{
+    apply_config_bundle, apply_workspace_reorder, build_config_bundle, read_or_create_runner_id,
+    read_settings, read_workspaces, replaced_workspace_ids, write_config, write_settings,
+    write_workspaces,
+};
+use utils::{
+    canonical_workspace_path, hostname, linked_worktree_root, normalize_git_path, now_ms,
+    runner_platform,
+};
+use workspace_files::{
+    read_workspace_file_inner, search_workspace_inner, DEFAULT_MAX_FILE_BYTES,
+};
 use types::{
-    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
+    cap_access_mode, format_settings_errors, validate_access_mode, validate_codex_args, AppSettings,
+    ConfigBundle, ModelInfo, PendingServerRequest, RunnerInfo, SessionMetrics, ThreadDefaults,
+    ThreadSummary, ThreadSummaryPage, TurnOptions, TurnUsage, WorkspaceConnectResult,
+    WorkspaceEntry, WorkspaceFileContent, WorkspaceFileListing, WorkspaceHealth, WorkspaceInfo,
+    WorkspaceKind, WorkspaceListOptions, WorkspaceSearchResult, WorkspaceSettings, WorktreeInfo,
 };
 
 const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:4732";
+const CONNECT_ALL_CONCURRENCY: usize = 4;
+const BLOCKING_TURN_TIMEOUT: Duration = Duration::from_secs(600);
+const DEFAULT_THREAD_LIST_LIMIT: u32 = 20;
+/// Caps HTTP `/rpc` bodies; RPC params are small JSON objects.
+const MAX_HTTP_BODY_BYTES: usize = 4 * 1024 * 1024;
 
 #[derive(Clone)]
 struct DaemonEventSink {
@@ -46,6 +85,19 @@ enum DaemonEvent {
     AppServer(AppServerEvent),
     #[allow(dead_code)]
     TerminalOutput(TerminalOutput),
+    #[allow(dead_code)]
+    TerminalExit(TerminalExit),
+    SettingsChanged(Box<AppSettings>),
+    SessionExited(SessionExited),
+    WorkspaceFilesChanged(WorkspaceFilesChanged),
+    GitStatusChanged(GitStatusChanged),
+    TurnProgress(TurnProgress),
+}
+
+impl DaemonEventSink {
+    fn emit_settings_changed(&self, settings: AppSettings) {
+        let _ = self.tx.send(DaemonEvent::SettingsChanged(Box::new(settings)));
+    }
 }
 
 impl EventSink for DaemonEventSink {
@@ -56,12 +108,34 @@ impl EventSink for DaemonEventSink {
     fn emit_terminal_output(&self, event: TerminalOutput) {
         let _ = self.tx.send(DaemonEvent::TerminalOutput(event));
     }
+
+    fn emit_terminal_exit(&self, event: TerminalExit) {
+        let _ = self.tx.send(DaemonEvent::TerminalExit(event));
+    }
+
+    fn emit_session_exited(&self, event: SessionExited) {
+        let _ = self.tx.send(DaemonEvent::SessionExited(event));
+    }
+
+    fn emit_workspace_files_changed(&self, event: WorkspaceFilesChanged) {
+        let _ = self.tx.send(DaemonEvent::WorkspaceFilesChanged(event));
+    }
+
+    fn emit_git_status_changed(&self, event: GitStatusChanged) {
+        let _ = self.tx.send(DaemonEvent::GitStatusChanged(event));
+    }
+
+    fn emit_turn_progress(&self, event: TurnProgress) {
+        let _ = self.tx.send(DaemonEvent::TurnProgress(event));
+    }
 }
 
 struct DaemonConfig {
     listen: SocketAddr,
     token: Option<String>,
     data_dir: PathBuf,
+    /// Opt-in loopback address for the HTTP `/rpc` endpoint.
+    http_listen: Option<SocketAddr>,
 }
 
 struct DaemonState {
@@ -71,6 +145,11 @@ struct DaemonState {
     storage_path: PathBuf,
     settings_path: PathBuf,
     app_settings: Mutex<AppSettings>,
+    thread_defaults: Mutex<HashMap<String, ThreadDefaults>>,
+    rate_limits_cache: Mutex<HashMap<String, CachedRateLimits>>,
+    models_cache: Mutex<HashMap<String, Value>>,
+    runner_id: String,
+    started_at_ms: i64,
     event_sink: DaemonEventSink,
 }
 
@@ -80,6 +159,7 @@ impl DaemonState {
         let settings_path = config.data_dir.join("settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
+        let runner_id = read_or_create_runner_id(&config.data_dir.join("runner-id"));
         Self {
             data_dir: config.data_dir.clone(),
             workspaces: Mutex::new(workspaces),
@@ -87,6 +167,11 @@ impl DaemonState {
             storage_path,
             settings_path,
             app_settings: Mutex::new(app_settings),
+            thread_defaults: Mutex::new(HashMap::new()),
+            rate_limits_cache: Mutex::new(HashMap::new()),
+            models_cache: Mutex::new(HashMap::new()),
+            runner_id,
+            started_at_ms: now_ms(),
             event_sink,
         }
     }
@@ -105,16 +190,18 @@ impl DaemonState {
         let _ = child.kill().await;
     }
 
-    async fn list_workspaces(&self) -> Vec<WorkspaceInfo> {
+    async fn list_workspaces(&self, options: WorkspaceListOptions) -> Vec<WorkspaceInfo> {
         let workspaces = self.workspaces.lock().await;
         let sessions = self.sessions.lock().await;
         let mut result = Vec::new();
         for entry in workspaces.values() {
+            let watching = is_watching(sessions.get(&entry.id)).await;
             result.push(WorkspaceInfo {
                 id: entry.id.clone(),
                 name: entry.name.clone(),
                 path: entry.path.clone(),
                 connected: sessions.contains_key(&entry.id),
+                watching,
                 codex_bin: entry.codex_bin.clone(),
                 kind: entry.kind.clone(),
                 parent_id: entry.parent_id.clone(),
@@ -123,41 +210,106 @@ impl DaemonState {
             });
         }
         sort_workspaces(&mut result);
+        options.apply(&mut result, &last_activity_by_workspace(&sessions));
         result
     }
 
+    async fn runner_info(&self) -> RunnerInfo {
+        let workspace_count = self.workspaces.lock().await.len();
+        let connected_count = self.sessions.lock().await.len();
+        RunnerInfo {
+            runner_id: self.runner_id.clone(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: runner_platform(),
+            os: env::consts::OS.to_string(),
+            hostname: hostname(),
+            started_at_ms: self.started_at_ms,
+            workspace_count,
+            connected_count,
+            cloud_provider: None,
+        }
+    }
+
     async fn add_workspace(
         &self,
         path: String,
         codex_bin: Option<String>,
+        reject_duplicate: bool,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
-        let name = PathBuf::from(&path)
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap_or("Workspace")
-            .to_string();
+        let candidates: Vec<WorkspaceEntry> = {
+            let workspaces = self.workspaces.lock().await;
+            workspaces.values().cloned().collect()
+        };
+        let canonical_path = canonical_workspace_path(&path);
+        if let Some(existing) = candidates
+            .iter()
+            .find(|entry| canonical_workspace_path(&entry.path) == canonical_path)
+        {
+            if reject_duplicate {
+                return Err(format!("This folder is already added as \"{}\".", existing.name));
+            }
+            let session = self.sessions.lock().await.get(&existing.id).cloned();
+            let connected = session.is_some();
+            let watching = is_watching(session.as_ref()).await;
+            return Ok(WorkspaceInfo {
+                id: existing.id.clone(),
+                name: existing.name.clone(),
+                path: existing.path.clone(),
+                connected,
+                watching,
+                codex_bin: existing.codex_bin.clone(),
+                kind: existing.kind.clone(),
+                parent_id: existing.parent_id.clone(),
+                worktree: existing.worktree.clone(),
+                settings: existing.settings.clone(),
+            });
+        }
 
-        let entry = WorkspaceEntry {
-            id: Uuid::new_v4().to_string(),
-            name: name.clone(),
-            path: path.clone(),
-            codex_bin,
-            kind: WorkspaceKind::Main,
-            parent_id: None,
-            worktree: None,
-            settings: WorkspaceSettings::default(),
+        let worktree_parent = find_worktree_parent(&path, &candidates).await;
+        let entry = match &worktree_parent {
+            Some((parent, branch)) => WorkspaceEntry {
+                id: Uuid::new_v4().to_string(),
+                name: branch.clone(),
+                path: path.clone(),
+                codex_bin: codex_bin.or_else(|| parent.codex_bin.clone()),
+                kind: WorkspaceKind::Worktree,
+                parent_id: Some(parent.id.clone()),
+                worktree: Some(WorktreeInfo {
+                    branch: branch.clone(),
+                }),
+                settings: WorkspaceSettings::default(),
+            },
+            None => {
+                let name = PathBuf::from(&path)
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("Workspace")
+                    .to_string();
+                WorkspaceEntry {
+                    id: Uuid::new_v4().to_string(),
+                    name,
+                    path: path.clone(),
+                    codex_bin,
+                    kind: WorkspaceKind::Main,
+                    parent_id: None,
+                    worktree: None,
+                    settings: WorkspaceSettings::default(),
+                }
+            }
         };
 
-        let default_bin = {
+        let (default_bin, default_args) = {
             let settings = self.app_settings.lock().await;
-            settings.codex_bin.clone()
+            (settings.codex_bin.clone(), settings.codex_args.clone())
         };
 
-        let codex_home = codex_home::resolve_workspace_codex_home(&entry, None);
+        let parent_path = worktree_parent.as_ref().map(|(parent, _)| parent.path.as_str());
+        let codex_home = codex_home::resolve_workspace_codex_home(&entry, parent_path);
         let session = spawn_workspace_session(
             entry.clone(),
             default_bin,
+            default_args,
             client_version,
             self.event_sink.clone(),
             codex_home,
@@ -171,6 +323,7 @@ impl DaemonState {
         };
         write_workspaces(&self.storage_path, &list)?;
 
+        let watching = is_watching(Some(&session)).await;
         self.sessions.lock().await.insert(entry.id.clone(), session);
 
         Ok(WorkspaceInfo {
@@ -178,6 +331,7 @@ impl DaemonState {
             name: entry.name,
             path: entry.path,
             connected: true,
+            watching,
             codex_bin: entry.codex_bin,
             kind: entry.kind,
             parent_id: entry.parent_id,
@@ -252,15 +406,16 @@ impl DaemonState {
             settings: WorkspaceSettings::default(),
         };
 
-        let default_bin = {
+        let (default_bin, default_args) = {
             let settings = self.app_settings.lock().await;
-            settings.codex_bin.clone()
+            (settings.codex_bin.clone(), settings.codex_args.clone())
         };
 
         let codex_home = codex_home::resolve_workspace_codex_home(&entry, Some(&parent_entry.path));
         let session = spawn_workspace_session(
             entry.clone(),
             default_bin,
+            default_args,
             client_version,
             self.event_sink.clone(),
             codex_home,
@@ -274,6 +429,7 @@ impl DaemonState {
         };
         write_workspaces(&self.storage_path, &list)?;
 
+        let watching = is_watching(Some(&session)).await;
         self.sessions.lock().await.insert(entry.id.clone(), session);
 
         Ok(WorkspaceInfo {
@@ -281,6 +437,7 @@ impl DaemonState {
             name: entry.name,
             path: entry.path,
             connected: true,
+            watching,
             codex_bin: entry.codex_bin,
             kind: entry.kind,
             parent_id: entry.parent_id,
@@ -395,11 +552,30 @@ impl DaemonState {
         Ok(())
     }
 
+    async fn reorder_workspaces(
+        &self,
+        ordered_ids: Vec<String>,
+    ) -> Result<Vec<WorkspaceInfo>, String> {
+        let list = {
+            let mut workspaces = self.workspaces.lock().await;
+            apply_workspace_reorder(&mut workspaces, &ordered_ids);
+            workspaces.values().cloned().collect::<Vec<_>>()
+        };
+        write_workspaces(&self.storage_path, &list)?;
+        Ok(self.list_workspaces(WorkspaceListOptions::default()).await)
+    }
+
     async fn update_workspace_settings(
         &self,
         id: String,
         settings: WorkspaceSettings,
     ) -> Result<WorkspaceInfo, String> {
+        if let Some(mode) = settings.max_access_mode.as_deref() {
+            validate_access_mode(mode)?;
+        }
+        if let Some(args) = settings.codex_args.as_deref() {
+            validate_codex_args(args)?;
+        }
         let (entry_snapshot, list) = {
             let mut workspaces = self.workspaces.lock().await;
             let entry_snapshot = match workspaces.get_mut(&id) {
@@ -414,12 +590,59 @@ impl DaemonState {
         };
         write_workspaces(&self.storage_path, &list)?;
 
-        let connected = self.sessions.lock().await.contains_key(&id);
+        let session = self.sessions.lock().await.get(&id).cloned();
+        let connected = session.is_some();
+        if let Some(session) = &session {
+            session
+                .set_auto_approve(entry_snapshot.settings.auto_approve.clone())
+                .await;
+            session
+                .set_file_watch(entry_snapshot.settings.watch_files, self.event_sink.clone())
+                .await;
+        }
+        let watching = is_watching(session.as_ref()).await;
+        Ok(WorkspaceInfo {
+            id: entry_snapshot.id,
+            name: entry_snapshot.name,
+            path: entry_snapshot.path,
+            connected,
+            watching,
+            codex_bin: entry_snapshot.codex_bin,
+            kind: entry_snapshot.kind,
+            parent_id: entry_snapshot.parent_id,
+            worktree: entry_snapshot.worktree,
+            settings: entry_snapshot.settings,
+        })
+    }
+
+    async fn rename_workspace(&self, id: String, name: String) -> Result<WorkspaceInfo, String> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err("Workspace name cannot be empty.".to_string());
+        }
+        let (entry_snapshot, list) = {
+            let mut workspaces = self.workspaces.lock().await;
+            let entry_snapshot = match workspaces.get_mut(&id) {
+                Some(entry) => {
+                    entry.name = name;
+                    entry.clone()
+                }
+                None => return Err("workspace not found".to_string()),
+            };
+            let list: Vec<_> = workspaces.values().cloned().collect();
+            (entry_snapshot, list)
+        };
+        write_workspaces(&self.storage_path, &list)?;
+
+        let session = self.sessions.lock().await.get(&id).cloned();
+        let connected = session.is_some();
+        let watching = is_watching(session.as_ref()).await;
         Ok(WorkspaceInfo {
             id: entry_snapshot.id,
             name: entry_snapshot.name,
             path: entry_snapshot.path,
             connected,
+            watching,
             codex_bin: entry_snapshot.codex_bin,
             kind: entry_snapshot.kind,
             parent_id: entry_snapshot.parent_id,
@@ -432,7 +655,9 @@ impl DaemonState {
         &self,
         id: String,
         codex_bin: Option<String>,
+        verify: bool,
     ) -> Result<WorkspaceInfo, String> {
+        validate_workspace_codex_bin(codex_bin.as_deref(), verify).await?;
         let (entry_snapshot, list) = {
             let mut workspaces = self.workspaces.lock().await;
             let entry_snapshot = match workspaces.get_mut(&id) {
@@ -447,12 +672,15 @@ impl DaemonState {
         };
         write_workspaces(&self.storage_path, &list)?;
 
-        let connected = self.sessions.lock().await.contains_key(&id);
+        let session = self.sessions.lock().await.get(&id).cloned();
+        let connected = session.is_some();
+        let watching = is_watching(session.as_ref()).await;
         Ok(WorkspaceInfo {
             id: entry_snapshot.id,
             name: entry_snapshot.name,
             path: entry_snapshot.path,
             connected,
+            watching,
             codex_bin: entry_snapshot.codex_bin,
             kind: entry_snapshot.kind,
             parent_id: entry_snapshot.parent_id,
@@ -461,6 +689,81 @@ impl DaemonState {
         })
     }
 
+    async fn connect_all_workspaces(
+        &self,
+        client_version: String,
+    ) -> HashMap<String, WorkspaceConnectResult> {
+        let (default_bin, default_args) = {
+            let settings = self.app_settings.lock().await;
+            (settings.codex_bin.clone(), settings.codex_args.clone())
+        };
+        let entries: Vec<WorkspaceEntry> = {
+            let workspaces = self.workspaces.lock().await;
+            let sessions = self.sessions.lock().await;
+            workspaces
+                .values()
+                .filter(|entry| !entry.kind.is_worktree() && !sessions.contains_key(&entry.id))
+                .cloned()
+                .collect()
+        };
+
+        let limiter = Arc::new(Semaphore::new(CONNECT_ALL_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for entry in entries {
+            let limiter = Arc::clone(&limiter);
+            let default_bin = default_bin.clone();
+            let default_args = default_args.clone();
+            let client_version = client_version.clone();
+            let event_sink = self.event_sink.clone();
+            tasks.spawn(async move {
+                let _permit = limiter.acquire_owned().await;
+                let id = entry.id.clone();
+                let codex_home = codex_home::resolve_workspace_codex_home(&entry, None);
+                let session = spawn_workspace_session(
+                    entry,
+                    default_bin,
+                    default_args,
+                    client_version,
+                    event_sink,
+                    codex_home,
+                )
+                .await;
+                (id, session)
+            });
+        }
+
+        let mut results = HashMap::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (id, session) = match joined {
+                Ok(joined) => joined,
+                Err(err) => {
+                    logging::error("daemon", format!("connect task failed: {err}"));
+                    continue;
+                }
+            };
+            let result = match session {
+                Ok(session) => {
+                    insert_session(&self.sessions, id.clone(), session).await;
+                    Ok(())
+                }
+                Err(err) => Err(err),
+            };
+            results.insert(id, WorkspaceConnectResult::from(result));
+        }
+        results
+    }
+
+    async fn disconnect_all_workspaces(&self) {
+        let sessions: Vec<_> = {
+            let mut sessions = self.sessions.lock().await;
+            sessions.drain().map(|(_, session)| session).collect()
+        };
+        for session in sessions {
+            let mut child = session.child.lock().await;
+            let _ = child.kill().await;
+        }
+    }
+
     async fn connect_workspace(&self, id: String, client_version: String) -> Result<(), String> {
         {
             let sessions = self.sessions.lock().await;
@@ -477,9 +780,9 @@ impl DaemonState {
                 .ok_or("workspace not found")?
         };
 
-        let default_bin = {
+        let (default_bin, default_args) = {
             let settings = self.app_settings.lock().await;
-            settings.codex_bin.clone()
+            (settings.codex_bin.clone(), settings.codex_args.clone())
         };
 
         let parent_path = if entry.kind.is_worktree() {
@@ -496,35 +799,155 @@ impl DaemonState {
         let session = spawn_workspace_session(
             entry,
             default_bin,
+            default_args,
             client_version,
             self.event_sink.clone(),
             codex_home,
         )
         .await?;
 
-        self.sessions.lock().await.insert(id, session);
+        insert_session(&self.sessions, id, session).await;
         Ok(())
     }
 
-    async fn update_app_settings(&self, settings: AppSettings) -> Result<AppSettings, String> {
+    async fn update_app_settings(&self, mut settings: AppSettings) -> Result<AppSettings, String> {
+        let errors = settings.validate();
+        if !errors.is_empty() {
+            return Err(format_settings_errors(&errors));
+        }
         let _ = codex_config::write_collab_enabled(settings.experimental_collab_enabled);
         let _ = codex_config::write_steer_enabled(settings.experimental_steer_enabled);
         let _ = codex_config::write_unified_exec_enabled(settings.experimental_unified_exec_enabled);
         write_settings(&self.settings_path, &settings)?;
         let mut current = self.app_settings.lock().await;
         *current = settings.clone();
+        self.event_sink.emit_settings_changed(settings.clone());
         Ok(settings)
     }
 
+    async fn export_config(&self, include_secrets: bool) -> ConfigBundle {
+        let settings = self.app_settings.lock().await.clone();
+        let workspaces = self.workspaces.lock().await.clone();
+        build_config_bundle(&settings, &workspaces, include_secrets, now_ms())
+    }
+
+    async fn import_config(
+        &self,
+        bundle: ConfigBundle,
+        merge: bool,
+    ) -> Result<ConfigBundle, String> {
+        let current_settings = self.app_settings.lock().await.clone();
+        let (settings, replaced_ids) = {
+            let mut workspaces = self.workspaces.lock().await;
+            let (settings, next) =
+                apply_config_bundle(&current_settings, &workspaces, bundle, merge)?;
+            let list: Vec<_> = next.values().cloned().collect();
+            write_config(&self.settings_path, &settings, &self.storage_path, &list)?;
+            let replaced_ids = replaced_workspace_ids(&workspaces, &next);
+            *workspaces = next;
+            (settings, replaced_ids)
+        };
+        *self.app_settings.lock().await = settings.clone();
+        for id in replaced_ids {
+            if let Some(session) = self.sessions.lock().await.remove(&id) {
+                let mut child = session.child.lock().await;
+                let _ = child.kill().await;
+            }
+        }
+        let _ = codex_config::write_collab_enabled(settings.experimental_collab_enabled);
+        let _ = codex_config::write_steer_enabled(settings.experimental_steer_enabled);
+        let _ = codex_config::write_unified_exec_enabled(settings.experimental_unified_exec_enabled);
+        self.event_sink.emit_settings_changed(settings);
+        Ok(self.export_config(false).await)
+    }
+
     async fn get_session(&self, workspace_id: &str) -> Result<Arc<WorkspaceSession>, String> {
-        let sessions = self.sessions.lock().await;
-        sessions
-            .get(workspace_id)
-            .cloned()
-            .ok_or("workspace not connected".to_string())
+        let session = {
+            let sessions = self.sessions.lock().await;
+            sessions
+                .get(workspace_id)
+                .cloned()
+                .ok_or("workspace not connected".to_string())?
+        };
+        let exit_status = {
+            let mut child = session.child.lock().await;
+            child.try_wait().map_err(|err| err.to_string())?
+        };
+        let Some(exit_status) = exit_status else {
+            return Ok(session);
+        };
+
+        {
+            let mut sessions = self.sessions.lock().await;
+            if let Some(current) = sessions.get(workspace_id) {
+                if !Arc::ptr_eq(current, &session) {
+                    return Ok(Arc::clone(current));
+                }
+            }
+            sessions.remove(workspace_id);
+        }
+        let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
+        self.connect_workspace(workspace_id.to_string(), client_version)
+            .await?;
+        let session = {
+            let sessions = self.sessions.lock().await;
+            sessions
+                .get(workspace_id)
+                .cloned()
+                .ok_or("workspace not connected".to_string())?
+        };
+        self.event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message: json!({
+                "method": "codex/restarted",
+                "params": {
+                    "workspaceId": workspace_id,
+                    "exitCode": exit_status.code(),
+                }
+            }),
+        });
+        Ok(session)
+    }
+
+    async fn workspace_health(&self, workspace_id: &str) -> WorkspaceHealth {
+        let session = {
+            let sessions = self.sessions.lock().await;
+            sessions.get(workspace_id).cloned()
+        };
+        match session {
+            Some(session) => session.health().await,
+            None => WorkspaceHealth::default(),
+        }
+    }
+
+    async fn workspace_stderr(&self, workspace_id: &str) -> Vec<String> {
+        let session = {
+            let sessions = self.sessions.lock().await;
+            sessions.get(workspace_id).cloned()
+        };
+        match session {
+            Some(session) => session.stderr_tail().await,
+            None => Vec::new(),
+        }
     }
 
-    async fn list_workspace_files(&self, workspace_id: String) -> Result<Vec<String>, String> {
+    async fn thread_usage(&self, workspace_id: &str, thread_id: &str) -> TurnUsage {
+        let session = {
+            let sessions = self.sessions.lock().await;
+            sessions.get(workspace_id).cloned()
+        };
+        match session {
+            Some(session) => session.thread_usage(thread_id).await,
+            None => TurnUsage::default(),
+        }
+    }
+
+    async fn list_workspace_files(
+        &self,
+        workspace_id: String,
+        max_depth: Option<usize>,
+        max_entries: Option<usize>,
+    ) -> Result<WorkspaceFileListing, String> {
         let entry = {
             let workspaces = self.workspaces.lock().await;
             workspaces
@@ -534,7 +957,53 @@ impl DaemonState {
         };
 
         let root = PathBuf::from(entry.path);
-        Ok(list_workspace_files_inner(&root, 20000))
+        let max_entries = max_entries.unwrap_or(20000);
+        tokio::task::spawn_blocking(move || {
+            list_workspace_files_inner(&root, max_depth, max_entries)
+        })
+        .await
+        .map_err(|err| err.to_string())
+    }
+
+    async fn read_workspace_file(
+        &self,
+        workspace_id: String,
+        relative_path: String,
+        max_bytes: Option<usize>,
+    ) -> Result<WorkspaceFileContent, String> {
+        let entry = {
+            let workspaces = self.workspaces.lock().await;
+            workspaces
+                .get(&workspace_id)
+                .cloned()
+                .ok_or("workspace not found")?
+        };
+
+        let root = PathBuf::from(entry.path);
+        let max_bytes = max_bytes.unwrap_or(DEFAULT_MAX_FILE_BYTES);
+        tokio::task::spawn_blocking(move || {
+            read_workspace_file_inner(&root, &relative_path, max_bytes)
+        })
+        .await
+        .map_err(|err| err.to_string())?
+    }
+
+    async fn search_workspace(
+        &self,
+        workspace_id: String,
+        query: String,
+        is_regex: bool,
+        max_results: usize,
+    ) -> Result<WorkspaceSearchResult, String> {
+        let entry = {
+            let workspaces = self.workspaces.lock().await;
+            workspaces
+                .get(&workspace_id)
+                .cloned()
+                .ok_or("workspace not found")?
+        };
+
+        search_workspace_inner(PathBuf::from(entry.path), query, is_regex, max_results).await
     }
 
     async fn start_thread(&self, workspace_id: String) -> Result<Value, String> {
@@ -546,12 +1015,19 @@ impl DaemonState {
         session.send_request("thread/start", params).await
     }
 
-    async fn resume_thread(&self, workspace_id: String, thread_id: String) -> Result<Value, String> {
+    async fn resume_thread(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+        request_id: Option<String>,
+    ) -> Result<Value, String> {
         let session = self.get_session(&workspace_id).await?;
         let params = json!({
             "threadId": thread_id
         });
-        session.send_request("thread/resume", params).await
+        session
+            .send_cancellable_request("thread/resume", params, request_id.as_deref(), None)
+            .await
     }
 
     async fn list_threads(
@@ -568,25 +1044,149 @@ impl DaemonState {
         session.send_request("thread/list", params).await
     }
 
+    async fn pinned_threads(&self, workspace_id: &str) -> Vec<String> {
+        let workspaces = self.workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .map(|entry| entry.settings.pinned_threads.clone())
+            .unwrap_or_default()
+    }
+
+    async fn set_thread_pinned(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+        pinned: bool,
+    ) -> Result<WorkspaceInfo, String> {
+        let (entry_snapshot, list) = {
+            let mut workspaces = self.workspaces.lock().await;
+            let entry = workspaces
+                .get_mut(&workspace_id)
+                .ok_or("workspace not found")?;
+            entry
+                .settings
+                .pinned_threads
+                .retain(|existing| existing != &thread_id);
+            if pinned {
+                entry.settings.pinned_threads.push(thread_id);
+            }
+            let entry_snapshot = entry.clone();
+            let list: Vec<_> = workspaces.values().cloned().collect();
+            (entry_snapshot, list)
+        };
+        write_workspaces(&self.storage_path, &list)?;
+
+        let session = self.sessions.lock().await.get(&workspace_id).cloned();
+        let connected = session.is_some();
+        let watching = is_watching(session.as_ref()).await;
+        Ok(WorkspaceInfo {
+            id: entry_snapshot.id,
+            name: entry_snapshot.name,
+            path: entry_snapshot.path,
+            codex_bin: entry_snapshot.codex_bin,
+            connected,
+            watching,
+            kind: entry_snapshot.kind,
+            parent_id: entry_snapshot.parent_id,
+            worktree: entry_snapshot.worktree,
+            settings: entry_snapshot.settings,
+        })
+    }
+
+    async fn list_workspace_threads(
+        &self,
+        workspace_id: String,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<ThreadSummaryPage, String> {
+        let session = self.get_session(&workspace_id).await?;
+        session
+            .list_workspace_threads(ThreadListOptions {
+                cursor,
+                limit: limit.unwrap_or(DEFAULT_THREAD_LIST_LIMIT) as usize,
+                query: None,
+                pinned: self.pinned_threads(&workspace_id).await,
+            })
+            .await
+    }
+
+    async fn search_threads(
+        &self,
+        workspace_id: String,
+        query: String,
+        limit: Option<u32>,
+    ) -> Result<Vec<ThreadSummary>, String> {
+        let session = self.get_session(&workspace_id).await?;
+        let limit = limit.unwrap_or(DEFAULT_THREAD_LIST_LIMIT) as usize;
+        let pinned = self.pinned_threads(&workspace_id).await;
+        session.search_threads(&query, limit, pinned).await
+    }
+
     async fn archive_thread(&self, workspace_id: String, thread_id: String) -> Result<Value, String> {
         let session = self.get_session(&workspace_id).await?;
         let params = json!({ "threadId": thread_id });
         session.send_request("thread/archive", params).await
     }
 
+    async fn delete_thread(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+    ) -> Result<Value, String> {
+        let session = self.get_session(&workspace_id).await?;
+        let removed = session.delete_thread(&thread_id).await?;
+        Ok(json!({ "threadId": thread_id, "deleted": true, "removedFiles": removed }))
+    }
+
     async fn send_user_message(
         &self,
         workspace_id: String,
         thread_id: String,
         text: String,
-        model: Option<String>,
-        effort: Option<String>,
-        access_mode: Option<String>,
-        images: Option<Vec<String>>,
-        collaboration_mode: Option<Value>,
+        options: TurnOptions,
     ) -> Result<Value, String> {
+        let TurnOptions {
+            model,
+            effort,
+            access_mode,
+            images,
+            collaboration_mode,
+            request_id,
+        } = options;
+        let models = self
+            .models_cache
+            .lock()
+            .await
+            .get(&workspace_id)
+            .map(ModelInfo::list_from_response);
+        let ThreadDefaults {
+            model,
+            effort,
+            access_mode,
+        } = {
+            let mut thread_defaults = self.thread_defaults.lock().await;
+            thread_defaults
+                .entry(thread_id.clone())
+                .or_default()
+                .resolve_for_turn(model, effort, access_mode, models.as_deref())?
+        };
         let session = self.get_session(&workspace_id).await?;
-        let access_mode = access_mode.unwrap_or_else(|| "current".to_string());
+        // The abort-all kill switch keeps every new turn read-only for a while.
+        let access_mode = if session.read_only_locked() {
+            "read-only".to_string()
+        } else {
+            access_mode.unwrap_or_else(|| "current".to_string())
+        };
+        let max_access_mode = self
+            .workspaces
+            .lock()
+            .await
+            .get(&workspace_id)
+            .and_then(|entry| entry.settings.max_access_mode.clone());
+        let (access_mode, downgraded_from) = {
+            let (capped, downgraded) = cap_access_mode(&access_mode, max_access_mode.as_deref());
+            (capped, downgraded.then_some(access_mode))
+        };
         let sandbox_policy = match access_mode.as_str() {
             "full-access" => json!({
                 "type": "dangerFullAccess"
@@ -642,7 +1242,30 @@ impl DaemonState {
             "effort": effort,
             "collaborationMode": collaboration_mode,
         });
-        session.send_request("turn/start", params).await
+        let mut response = session
+            .start_turn(&thread_id, params, request_id.as_deref())
+            .await?;
+        if let (Some(requested), Some(object)) = (downgraded_from, response.as_object_mut()) {
+            object.insert(
+                "accessModeDowngrade".to_string(),
+                json!({ "requested": requested, "applied": access_mode }),
+            );
+        }
+        Ok(response)
+    }
+
+    async fn set_thread_defaults(
+        &self,
+        thread_id: String,
+        model: Option<String>,
+        effort: Option<String>,
+        access_mode: Option<String>,
+    ) -> ThreadDefaults {
+        let mut thread_defaults = self.thread_defaults.lock().await;
+        thread_defaults
+            .entry(thread_id)
+            .or_default()
+            .resolve(model, effort, access_mode)
     }
 
     async fn turn_interrupt(
@@ -678,9 +1301,21 @@ impl DaemonState {
             .await
     }
 
-    async fn model_list(&self, workspace_id: String) -> Result<Value, String> {
+    async fn model_list(&self, workspace_id: String, force: bool) -> Result<Value, String> {
+        if !force {
+            if let Some(response) = self.models_cache.lock().await.get(&workspace_id) {
+                return Ok(response.clone());
+            }
+        }
         let session = self.get_session(&workspace_id).await?;
-        session.send_request("model/list", json!({})).await
+        let response = session.send_request("model/list", json!({})).await?;
+        if response.get("error").is_none() {
+            self.models_cache
+                .lock()
+                .await
+                .insert(workspace_id, response.clone());
+        }
+        Ok(response)
     }
 
     async fn collaboration_mode_list(&self, workspace_id: String) -> Result<Value, String> {
@@ -690,11 +1325,34 @@ impl DaemonState {
             .await
     }
 
-    async fn account_rate_limits(&self, workspace_id: String) -> Result<Value, String> {
+    async fn account_rate_limits(
+        &self,
+        workspace_id: String,
+        force: bool,
+    ) -> Result<Value, String> {
+        if !force {
+            let cache = self.rate_limits_cache.lock().await;
+            if let Some(cached) = cache.get(&workspace_id) {
+                if cached.expires_at_ms > now_ms() {
+                    return Ok(cached.response.clone());
+                }
+            }
+        }
         let session = self.get_session(&workspace_id).await?;
-        session
+        let response = session
             .send_request("account/rateLimits/read", Value::Null)
-            .await
+            .await?;
+        if response.get("error").is_none() {
+            let fetched_at_ms = now_ms();
+            self.rate_limits_cache.lock().await.insert(
+                workspace_id,
+                CachedRateLimits {
+                    response: response.clone(),
+                    expires_at_ms: rate_limits_expiry_ms(&response, fetched_at_ms),
+                },
+            );
+        }
+        Ok(response)
     }
 
     async fn skills_list(&self, workspace_id: String) -> Result<Value, String> {
@@ -716,6 +1374,33 @@ impl DaemonState {
         Ok(json!({ "ok": true }))
     }
 
+    async fn abort_all_turns(&self) -> Value {
+        let sessions: Vec<_> = self.sessions.lock().await.values().cloned().collect();
+        abort_all_turns(sessions).await
+    }
+
+    async fn list_pending_server_requests(
+        &self,
+        workspace_id: String,
+    ) -> Result<Vec<PendingServerRequest>, String> {
+        let session = self.get_session(&workspace_id).await?;
+        Ok(session.pending_server_requests().await)
+    }
+
+    async fn cancel_request(
+        &self,
+        workspace_id: String,
+        request_id: String,
+    ) -> Result<Value, String> {
+        let session = self.get_session(&workspace_id).await?;
+        Ok(session.cancel_request(&request_id).await)
+    }
+
+    async fn session_metrics(&self, workspace_id: String) -> Result<SessionMetrics, String> {
+        let session = self.get_session(&workspace_id).await?;
+        Ok(session.metrics.snapshot())
+    }
+
     async fn remember_approval_rule(
         &self,
         workspace_id: String,
@@ -775,16 +1460,18 @@ fn should_skip_dir(name: &str) -> bool {
     )
 }
 
-fn normalize_git_path(path: &str) -> String {
-    path.replace('\\', "/")
-}
-
-fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
-    let mut results = Vec::new();
+fn list_workspace_files_inner(
+    root: &PathBuf,
+    max_depth: Option<usize>,
+    max_entries: usize,
+) -> WorkspaceFileListing {
+    let mut files = Vec::new();
+    let mut truncated = false;
     let walker = WalkBuilder::new(root)
         .hidden(false)
         .follow_links(false)
         .require_git(false)
+        .max_depth(max_depth)
         .filter_entry(|entry| {
             if entry.depth() == 0 {
                 return true;
@@ -805,19 +1492,43 @@ fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
         if !entry.file_type().is_some_and(|ft| ft.is_file()) {
             continue;
         }
+        if files.len() >= max_entries {
+            truncated = true;
+            break;
+        }
         if let Ok(rel_path) = entry.path().strip_prefix(root) {
             let normalized = normalize_git_path(&rel_path.to_string_lossy());
             if !normalized.is_empty() {
-                results.push(normalized);
+                files.push(normalized);
             }
         }
-        if results.len() >= max_files {
-            break;
-        }
     }
 
-    results.sort();
-    results
+    files.sort();
+    WorkspaceFileListing { files, truncated }
+}
+
+/// Finds the main workspace that `path` is a linked git worktree of, along
+/// with the branch checked out there.
+async fn find_worktree_parent(
+    path: &str,
+    candidates: &[WorkspaceEntry],
+) -> Option<(WorkspaceEntry, String)> {
+    let output = run_git_command(
+        &PathBuf::from(path),
+        &["rev-parse", "--git-dir", "--git-common-dir", "--abbrev-ref", "HEAD"],
+    )
+    .await
+    .ok()?;
+    let mut lines = output.lines();
+    let git_dir = lines.next()?;
+    let common_dir = lines.next()?;
+    let branch = lines.next()?.trim().to_string();
+    let root = linked_worktree_root(path, git_dir, common_dir)?;
+    let parent = candidates
+        .iter()
+        .find(|entry| !entry.kind.is_worktree() && canonical_workspace_path(&entry.path) == root)?;
+    Some((parent.clone(), branch))
 }
 
 async fn run_git_command(repo_path: &PathBuf, args: &[&str]) -> Result<String, String> {
@@ -949,8 +1660,8 @@ fn default_data_dir() -> PathBuf {
 fn usage() -> String {
     format!(
         "\
-USAGE:\n  codex-monitor-daemon [--listen <addr>] [--data-dir <path>] [--token <token> | --insecure-no-auth]\n\n\
-OPTIONS:\n  --listen <addr>        Bind address (default: {DEFAULT_LISTEN_ADDR})\n  --data-dir <path>      Data dir holding workspaces.json/settings.json\n  --token <token>        Shared token required by clients\n  --insecure-no-auth      Disable auth (dev only)\n  -h, --help             Show this help\n"
+USAGE:\n  codex-monitor-daemon [--listen <addr>] [--data-dir <path>] [--token <token> | --insecure-no-auth] [--http-listen <addr>]\n\n\
+OPTIONS:\n  --listen <addr>        Bind address (default: {DEFAULT_LISTEN_ADDR})\n  --data-dir <path>      Data dir holding workspaces.json/settings.json\n  --token <token>        Shared token required by clients\n  --insecure-no-auth      Disable auth (dev only)\n  --http-listen <addr>   Also serve POST /rpc over HTTP on a loopback address\n  -h, --help             Show this help\n"
     )
 }
 
@@ -964,6 +1675,7 @@ fn parse_args() -> Result<DaemonConfig, String> {
         .filter(|value| !value.is_empty());
     let mut insecure_no_auth = false;
     let mut data_dir: Option<PathBuf> = None;
+    let mut http_listen: Option<SocketAddr> = None;
 
     let mut args = env::args().skip(1);
     while let Some(arg) = args.next() {
@@ -992,6 +1704,14 @@ fn parse_args() -> Result<DaemonConfig, String> {
                 }
                 data_dir = Some(PathBuf::from(trimmed));
             }
+            "--http-listen" => {
+                let value = args.next().ok_or("--http-listen requires a value")?;
+                let addr = value.parse::<SocketAddr>().map_err(|err| err.to_string())?;
+                if !addr.ip().is_loopback() {
+                    return Err("--http-listen must be a loopback address".to_string());
+                }
+                http_listen = Some(addr);
+            }
             "--insecure-no-auth" => {
                 insecure_no_auth = true;
                 token = None;
@@ -1011,6 +1731,7 @@ fn parse_args() -> Result<DaemonConfig, String> {
         listen,
         token,
         data_dir: data_dir.unwrap_or_else(default_data_dir),
+        http_listen,
     })
 }
 
@@ -1032,6 +1753,35 @@ fn build_result_response(id: Option<u64>, result: Value) -> Option<String> {
     }))
 }
 
+/// Runs a batch of requests one after another and returns their responses in
+/// the same order. A failing call only fails its own entry; entries without
+/// an id are treated as notifications and get no response.
+async fn handle_rpc_batch(
+    state: &DaemonState,
+    requests: Vec<Value>,
+    client_version: String,
+) -> Vec<Value> {
+    let mut responses = Vec::with_capacity(requests.len());
+    for request in requests {
+        let id = request.get("id").and_then(|value| value.as_u64());
+        let method = request
+            .get("method")
+            .and_then(|value| value.as_str())
+            .unwrap_or("")
+            .to_string();
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+        let result = handle_rpc_request(state, &method, params, client_version.clone()).await;
+        let Some(id) = id else {
+            continue;
+        };
+        responses.push(match result {
+            Ok(result) => json!({ "id": id, "result": result }),
+            Err(message) => json!({ "id": id, "error": { "message": message } }),
+        });
+    }
+    responses
+}
+
 fn build_event_notification(event: DaemonEvent) -> Option<String> {
     let payload = match event {
         DaemonEvent::AppServer(payload) => json!({
@@ -1042,6 +1792,30 @@ fn build_event_notification(event: DaemonEvent) -> Option<String> {
             "method": "terminal-output",
             "params": payload,
         }),
+        DaemonEvent::TerminalExit(payload) => json!({
+            "method": "terminal-closed",
+            "params": payload,
+        }),
+        DaemonEvent::SettingsChanged(payload) => json!({
+            "method": "settings-changed",
+            "params": payload,
+        }),
+        DaemonEvent::SessionExited(payload) => json!({
+            "method": "session-exited",
+            "params": payload,
+        }),
+        DaemonEvent::WorkspaceFilesChanged(payload) => json!({
+            "method": "workspace-files-changed",
+            "params": payload,
+        }),
+        DaemonEvent::GitStatusChanged(payload) => json!({
+            "method": "git-status-changed",
+            "params": payload,
+        }),
+        DaemonEvent::TurnProgress(payload) => json!({
+            "method": "turn-progress",
+            "params": payload,
+        }),
     };
     serde_json::to_string(&payload).ok()
 }
@@ -1078,6 +1852,13 @@ fn parse_optional_string(value: &Value, key: &str) -> Option<String> {
     }
 }
 
+fn parse_optional_u64(value: &Value, key: &str) -> Option<u64> {
+    match value {
+        Value::Object(map) => map.get(key).and_then(|value| value.as_u64()),
+        _ => None,
+    }
+}
+
 fn parse_optional_u32(value: &Value, key: &str) -> Option<u32> {
     match value {
         Value::Object(map) => map.get(key).and_then(|value| value.as_u64()).and_then(|v| {
@@ -1091,6 +1872,13 @@ fn parse_optional_u32(value: &Value, key: &str) -> Option<u32> {
     }
 }
 
+fn parse_optional_bool(value: &Value, key: &str) -> Option<bool> {
+    match value {
+        Value::Object(map) => map.get(key).and_then(|value| value.as_bool()),
+        _ => None,
+    }
+}
+
 fn parse_optional_string_array(value: &Value, key: &str) -> Option<Vec<String>> {
     match value {
         Value::Object(map) => map.get(key).and_then(|value| value.as_array()).map(|items| {
@@ -1107,13 +1895,112 @@ fn parse_string_array(value: &Value, key: &str) -> Result<Vec<String>, String> {
     parse_optional_string_array(value, key).ok_or_else(|| format!("missing `{key}`"))
 }
 
-fn parse_optional_value(value: &Value, key: &str) -> Option<Value> {
-    match value {
-        Value::Object(map) => map.get(key).cloned(),
-        _ => None,
+struct RpcMethodSpec {
+    name: &'static str,
+    required: &'static [&'static str],
+    optional: &'static [&'static str],
+}
+
+const fn rpc_method(
+    name: &'static str,
+    required: &'static [&'static str],
+    optional: &'static [&'static str],
+) -> RpcMethodSpec {
+    RpcMethodSpec {
+        name,
+        required,
+        optional,
     }
 }
 
+/// Bumped when a method is removed or renamed, or its params change in a
+/// way older clients would send wrong, so clients can tell an older runner
+/// apart from a bug. New methods and new optional params don't bump it;
+/// clients find those through `__describe`.
+const PROTOCOL_VERSION: u32 = 1;
+
+/// Every method `handle_rpc_request` understands, as reported by
+/// `__describe`. A test checks the two list the same methods.
+const RPC_METHODS: &[RpcMethodSpec] = &[
+    rpc_method("ping", &[], &[]),
+    rpc_method("__describe", &[], &[]),
+    rpc_method("list_workspaces", &[], &["kindFilter", "connectedOnly", "sort"]),
+    rpc_method("runner_info", &[], &[]),
+    rpc_method("add_workspace", &["path"], &["codex_bin", "rejectDuplicate"]),
+    rpc_method("add_worktree", &["parentId", "branch"], &[]),
+    rpc_method("connect_workspace", &["id"], &[]),
+    rpc_method("connect_all_workspaces", &[], &[]),
+    rpc_method("disconnect_all_workspaces", &[], &[]),
+    rpc_method("remove_workspace", &["id"], &[]),
+    rpc_method("remove_worktree", &["id"], &[]),
+    rpc_method("update_workspace_settings", &["id", "settings"], &[]),
+    rpc_method("reorder_workspaces", &["orderedIds"], &[]),
+    rpc_method("get_logs", &[], &["level", "limit"]),
+    rpc_method("rename_workspace", &["id", "name"], &[]),
+    rpc_method("update_workspace_codex_bin", &["id"], &["codex_bin", "verify"]),
+    rpc_method("list_workspace_files", &["workspaceId"], &["maxDepth", "maxEntries"]),
+    rpc_method("read_workspace_file", &["workspaceId", "relativePath"], &["maxBytes"]),
+    rpc_method("search_workspace", &["workspaceId", "query"], &["isRegex", "maxResults"]),
+    rpc_method("get_app_settings", &[], &[]),
+    rpc_method("update_app_settings", &["settings"], &[]),
+    rpc_method("export_config", &[], &["includeSecrets"]),
+    rpc_method("import_config", &["bundle"], &["merge"]),
+    rpc_method("validate_app_settings", &["settings"], &[]),
+    rpc_method("workspace_health", &["workspaceId"], &[]),
+    rpc_method("workspace_stderr", &["workspaceId"], &[]),
+    rpc_method("thread_usage", &["workspaceId", "threadId"], &[]),
+    rpc_method("start_thread", &["workspaceId"], &[]),
+    rpc_method("resume_thread", &["workspaceId", "threadId"], &["requestId"]),
+    rpc_method("list_threads", &["workspaceId"], &["cursor", "limit"]),
+    rpc_method("list_workspace_threads", &["workspaceId"], &["cursor", "limit"]),
+    rpc_method("set_thread_pinned", &["workspaceId", "threadId", "pinned"], &[]),
+    rpc_method("search_threads", &["workspaceId", "query"], &["limit"]),
+    rpc_method("archive_thread", &["workspaceId", "threadId"], &[]),
+    rpc_method("delete_thread", &["workspaceId", "threadId"], &[]),
+    rpc_method(
+        "send_user_message",
+        &["workspaceId", "threadId", "text"],
+        &["model", "effort", "accessMode", "images", "collaborationMode", "requestId"],
+    ),
+    rpc_method(
+        "send_user_message_blocking",
+        &["workspaceId", "threadId", "text"],
+        &["model", "effort", "accessMode", "images", "collaborationMode", "timeoutMs"],
+    ),
+    rpc_method("set_thread_defaults", &["threadId"], &["model", "effort", "accessMode"]),
+    rpc_method("turn_interrupt", &["workspaceId", "threadId", "turnId"], &[]),
+    rpc_method("start_review", &["workspaceId", "threadId", "target"], &["delivery"]),
+    rpc_method("model_list", &["workspaceId"], &["force"]),
+    rpc_method("models_list", &["workspaceId"], &["force"]),
+    rpc_method("collaboration_mode_list", &["workspaceId"], &[]),
+    rpc_method("account_rate_limits", &["workspaceId"], &["force"]),
+    rpc_method("skills_list", &["workspaceId"], &[]),
+    rpc_method("respond_to_server_request", &["workspaceId", "requestId", "result"], &[]),
+    rpc_method("list_pending_server_requests", &["workspaceId"], &[]),
+    rpc_method("cancel_request", &["workspaceId", "requestId"], &[]),
+    rpc_method("session_metrics", &["workspaceId"], &[]),
+    rpc_method("abort_all_turns", &[], &[]),
+    rpc_method("remember_approval_rule", &["workspaceId", "command"], &[]),
+];
+
+fn describe_rpc_methods() -> Value {
+    let methods: Vec<Value> = RPC_METHODS
+        .iter()
+        .map(|spec| {
+            json!({
+                "name": spec.name,
+                "required": spec.required,
+                "optional": spec.optional,
+            })
+        })
+        .collect();
+    json!({
+        "protocolVersion": PROTOCOL_VERSION,
+        "version": env!("CARGO_PKG_VERSION"),
+        "methods": methods,
+    })
+}
+
 async fn handle_rpc_request(
     state: &DaemonState,
     method: &str,
@@ -1121,15 +2008,27 @@ async fn handle_rpc_request(
     client_version: String,
 ) -> Result<Value, String> {
     match method {
-        "ping" => Ok(json!({ "ok": true })),
+        "ping" => Ok(json!({ "ok": true, "protocolVersion": PROTOCOL_VERSION })),
+        "__describe" => Ok(describe_rpc_methods()),
         "list_workspaces" => {
-            let workspaces = state.list_workspaces().await;
+            let options: WorkspaceListOptions = match params {
+                Value::Null => WorkspaceListOptions::default(),
+                params => serde_json::from_value(params).map_err(|err| err.to_string())?,
+            };
+            let workspaces = state.list_workspaces(options).await;
             serde_json::to_value(workspaces).map_err(|err| err.to_string())
         }
+        "runner_info" => {
+            let info = state.runner_info().await;
+            serde_json::to_value(info).map_err(|err| err.to_string())
+        }
         "add_workspace" => {
             let path = parse_string(&params, "path")?;
             let codex_bin = parse_optional_string(&params, "codex_bin");
-            let workspace = state.add_workspace(path, codex_bin, client_version).await?;
+            let reject_duplicate = parse_optional_bool(&params, "rejectDuplicate").unwrap_or(false);
+            let workspace = state
+                .add_workspace(path, codex_bin, reject_duplicate, client_version)
+                .await?;
             serde_json::to_value(workspace).map_err(|err| err.to_string())
         }
         "add_worktree" => {
@@ -1145,6 +2044,14 @@ async fn handle_rpc_request(
             state.connect_workspace(id, client_version).await?;
             Ok(json!({ "ok": true }))
         }
+        "connect_all_workspaces" => {
+            let results = state.connect_all_workspaces(client_version).await;
+            serde_json::to_value(results).map_err(|err| err.to_string())
+        }
+        "disconnect_all_workspaces" => {
+            state.disconnect_all_workspaces().await;
+            Ok(json!({ "ok": true }))
+        }
         "remove_workspace" => {
             let id = parse_string(&params, "id")?;
             state.remove_workspace(id).await?;
@@ -1166,17 +2073,65 @@ async fn handle_rpc_request(
             let workspace = state.update_workspace_settings(id, settings).await?;
             serde_json::to_value(workspace).map_err(|err| err.to_string())
         }
+        "reorder_workspaces" => {
+            let ordered_ids = parse_string_array(&params, "orderedIds")?;
+            let workspaces = state.reorder_workspaces(ordered_ids).await?;
+            serde_json::to_value(workspaces).map_err(|err| err.to_string())
+        }
+        "get_logs" => {
+            let min_level = match parse_optional_string(&params, "level") {
+                Some(value) => Some(
+                    logging::LogLevel::parse(&value)
+                        .ok_or_else(|| format!("Unknown log level `{value}`."))?,
+                ),
+                None => None,
+            };
+            let limit = parse_optional_u32(&params, "limit").unwrap_or(500) as usize;
+            let logs = logging::log_buffer().recent(min_level, limit);
+            serde_json::to_value(logs).map_err(|err| err.to_string())
+        }
+        "rename_workspace" => {
+            let id = parse_string(&params, "id")?;
+            let name = parse_string(&params, "name")?;
+            let workspace = state.rename_workspace(id, name).await?;
+            serde_json::to_value(workspace).map_err(|err| err.to_string())
+        }
         "update_workspace_codex_bin" => {
             let id = parse_string(&params, "id")?;
             let codex_bin = parse_optional_string(&params, "codex_bin");
-            let workspace = state.update_workspace_codex_bin(id, codex_bin).await?;
+            let verify = parse_optional_bool(&params, "verify").unwrap_or(false);
+            let workspace = state.update_workspace_codex_bin(id, codex_bin, verify).await?;
             serde_json::to_value(workspace).map_err(|err| err.to_string())
         }
         "list_workspace_files" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
-            let files = state.list_workspace_files(workspace_id).await?;
+            let max_depth = parse_optional_u32(&params, "maxDepth").map(|value| value as usize);
+            let max_entries =
+                parse_optional_u32(&params, "maxEntries").map(|value| value as usize);
+            let files = state
+                .list_workspace_files(workspace_id, max_depth, max_entries)
+                .await?;
             serde_json::to_value(files).map_err(|err| err.to_string())
         }
+        "read_workspace_file" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let relative_path = parse_string(&params, "relativePath")?;
+            let max_bytes = parse_optional_u32(&params, "maxBytes").map(|value| value as usize);
+            let file = state
+                .read_workspace_file(workspace_id, relative_path, max_bytes)
+                .await?;
+            serde_json::to_value(file).map_err(|err| err.to_string())
+        }
+        "search_workspace" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let query = parse_string(&params, "query")?;
+            let is_regex = parse_optional_bool(&params, "isRegex").unwrap_or(false);
+            let max_results = parse_optional_u32(&params, "maxResults").unwrap_or(200) as usize;
+            let result = state
+                .search_workspace(workspace_id, query, is_regex, max_results)
+                .await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
         "get_app_settings" => {
             let mut settings = state.app_settings.lock().await.clone();
             if let Ok(Some(collab_enabled)) = codex_config::read_collab_enabled() {
@@ -1200,6 +2155,47 @@ async fn handle_rpc_request(
             let updated = state.update_app_settings(settings).await?;
             serde_json::to_value(updated).map_err(|err| err.to_string())
         }
+        "export_config" => {
+            let include_secrets = parse_optional_bool(&params, "includeSecrets").unwrap_or(false);
+            let bundle = state.export_config(include_secrets).await;
+            serde_json::to_value(bundle).map_err(|err| err.to_string())
+        }
+        "import_config" => {
+            let bundle_value = match &params {
+                Value::Object(map) => map.get("bundle").cloned().unwrap_or(Value::Null),
+                _ => Value::Null,
+            };
+            let bundle: ConfigBundle =
+                serde_json::from_value(bundle_value).map_err(|err| err.to_string())?;
+            let merge = parse_optional_bool(&params, "merge").unwrap_or(false);
+            let imported = state.import_config(bundle, merge).await?;
+            serde_json::to_value(imported).map_err(|err| err.to_string())
+        }
+        "validate_app_settings" => {
+            let settings_value = match params {
+                Value::Object(map) => map.get("settings").cloned().unwrap_or(Value::Null),
+                _ => Value::Null,
+            };
+            let mut settings: AppSettings =
+                serde_json::from_value(settings_value).map_err(|err| err.to_string())?;
+            serde_json::to_value(settings.validate()).map_err(|err| err.to_string())
+        }
+        "workspace_health" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let health = state.workspace_health(&workspace_id).await;
+            serde_json::to_value(health).map_err(|err| err.to_string())
+        }
+        "workspace_stderr" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let lines = state.workspace_stderr(&workspace_id).await;
+            serde_json::to_value(lines).map_err(|err| err.to_string())
+        }
+        "thread_usage" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            let usage = state.thread_usage(&workspace_id, &thread_id).await;
+            serde_json::to_value(usage).map_err(|err| err.to_string())
+        }
         "start_thread" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             state.start_thread(workspace_id).await
@@ -1207,7 +2203,8 @@ async fn handle_rpc_request(
         "resume_thread" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let thread_id = parse_string(&params, "threadId")?;
-            state.resume_thread(workspace_id, thread_id).await
+            let request_id = parse_optional_string(&params, "requestId");
+            state.resume_thread(workspace_id, thread_id, request_id).await
         }
         "list_threads" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
@@ -1215,32 +2212,81 @@ async fn handle_rpc_request(
             let limit = parse_optional_u32(&params, "limit");
             state.list_threads(workspace_id, cursor, limit).await
         }
+        "list_workspace_threads" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let cursor = parse_optional_string(&params, "cursor");
+            let limit = parse_optional_u32(&params, "limit");
+            let page = state
+                .list_workspace_threads(workspace_id, cursor, limit)
+                .await?;
+            serde_json::to_value(page).map_err(|err| err.to_string())
+        }
+        "set_thread_pinned" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            let pinned = parse_optional_bool(&params, "pinned").ok_or("missing `pinned`")?;
+            let workspace = state
+                .set_thread_pinned(workspace_id, thread_id, pinned)
+                .await?;
+            serde_json::to_value(workspace).map_err(|err| err.to_string())
+        }
+        "search_threads" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let query = parse_string(&params, "query")?;
+            let limit = parse_optional_u32(&params, "limit");
+            let results = state.search_threads(workspace_id, query, limit).await?;
+            serde_json::to_value(results).map_err(|err| err.to_string())
+        }
         "archive_thread" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let thread_id = parse_string(&params, "threadId")?;
             state.archive_thread(workspace_id, thread_id).await
         }
+        "delete_thread" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            state.delete_thread(workspace_id, thread_id).await
+        }
         "send_user_message" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let thread_id = parse_string(&params, "threadId")?;
             let text = parse_string(&params, "text")?;
+            let options: TurnOptions =
+                serde_json::from_value(params).map_err(|err| err.to_string())?;
+            state
+                .send_user_message(workspace_id, thread_id, text, options)
+                .await
+        }
+        "send_user_message_blocking" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            let text = parse_string(&params, "text")?;
+            let wait = parse_optional_u64(&params, "timeoutMs")
+                .map(Duration::from_millis)
+                .unwrap_or(BLOCKING_TURN_TIMEOUT);
+            let options = TurnOptions {
+                request_id: None,
+                ..serde_json::from_value(params).map_err(|err| err.to_string())?
+            };
+            let session = state.get_session(&workspace_id).await?;
+            let completion = session.watch_turn(&thread_id).await?;
+            let started = state
+                .send_user_message(workspace_id, thread_id.clone(), text, options)
+                .await;
+            let result = session
+                .finish_watched_turn(&thread_id, started, completion, wait)
+                .await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "set_thread_defaults" => {
+            let thread_id = parse_string(&params, "threadId")?;
             let model = parse_optional_string(&params, "model");
             let effort = parse_optional_string(&params, "effort");
             let access_mode = parse_optional_string(&params, "accessMode");
-            let images = parse_optional_string_array(&params, "images");
-            let collaboration_mode = parse_optional_value(&params, "collaborationMode");
-            state
-                .send_user_message(
-                    workspace_id,
-                    thread_id,
-                    text,
-                    model,
-                    effort,
-                    access_mode,
-                    images,
-                    collaboration_mode,
-                )
-                .await
+            let defaults = state
+                .set_thread_defaults(thread_id, model, effort, access_mode)
+                .await;
+            serde_json::to_value(defaults).map_err(|err| err.to_string())
         }
         "turn_interrupt" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
@@ -1261,7 +2307,15 @@ async fn handle_rpc_request(
         }
         "model_list" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
-            state.model_list(workspace_id).await
+            let force = parse_optional_bool(&params, "force").unwrap_or(false);
+            state.model_list(workspace_id, force).await
+        }
+        "models_list" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let force = parse_optional_bool(&params, "force").unwrap_or(false);
+            let response = state.model_list(workspace_id, force).await?;
+            serde_json::to_value(ModelInfo::list_from_response(&response))
+                .map_err(|err| err.to_string())
         }
         "collaboration_mode_list" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
@@ -1269,7 +2323,8 @@ async fn handle_rpc_request(
         }
         "account_rate_limits" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
-            state.account_rate_limits(workspace_id).await
+            let force = parse_optional_bool(&params, "force").unwrap_or(false);
+            state.account_rate_limits(workspace_id, force).await
         }
         "skills_list" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
@@ -1287,6 +2342,22 @@ async fn handle_rpc_request(
                 .respond_to_server_request(workspace_id, request_id, result)
                 .await
         }
+        "abort_all_turns" => Ok(state.abort_all_turns().await),
+        "list_pending_server_requests" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let requests = state.list_pending_server_requests(workspace_id).await?;
+            serde_json::to_value(requests).map_err(|err| err.to_string())
+        }
+        "cancel_request" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let request_id = parse_string(&params, "requestId")?;
+            state.cancel_request(workspace_id, request_id).await
+        }
+        "session_metrics" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let metrics = state.session_metrics(workspace_id).await?;
+            serde_json::to_value(metrics).map_err(|err| err.to_string())
+        }
         "remember_approval_rule" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let command = parse_string_array(&params, "command")?;
@@ -1358,6 +2429,26 @@ async fn handle_client(
             Err(_) => continue,
         };
 
+        if let Value::Array(requests) = message {
+            let responses: Vec<Value> = if authenticated {
+                let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
+                handle_rpc_batch(&state, requests, client_version).await
+            } else {
+                // Authentication has to happen in its own request.
+                requests
+                    .iter()
+                    .filter_map(|request| request.get("id").and_then(|value| value.as_u64()))
+                    .map(|id| json!({ "id": id, "error": { "message": "unauthorized" } }))
+                    .collect()
+            };
+            if !responses.is_empty() {
+                if let Ok(response) = serde_json::to_string(&responses) {
+                    let _ = out_tx.send(response);
+                }
+            }
+            continue;
+        }
+
         let id = message.get("id").and_then(|value| value.as_u64());
         let method = message
             .get("method")
@@ -1413,6 +2504,82 @@ async fn handle_client(
     write_task.abort();
 }
 
+/// Serves one request on the opt-in HTTP endpoint. `POST /rpc` takes
+/// `{method, params}` and runs it through the same dispatcher as the TCP
+/// transport, so scripts see exactly the same behavior.
+async fn handle_http_client(socket: TcpStream, token: Arc<String>, state: Arc<DaemonState>) {
+    let (reader, mut writer) = socket.into_split();
+    let mut reader = BufReader::new(reader);
+    let (status, body) = match handle_http_rpc(&mut reader, &token, &state).await {
+        Ok(result) => (200, json!({ "result": result })),
+        Err((status, message)) => (status, json!({ "error": { "message": message } })),
+    };
+    let _ = write_response(&mut writer, status, "application/json", &body.to_string()).await;
+}
+
+async fn handle_http_rpc<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    token: &str,
+    state: &DaemonState,
+) -> Result<Value, (u16, String)> {
+    let request = read_request_head(reader).await.map_err(|err| (400, err))?;
+    // Browsers attach an Origin to cross-site requests; curl and scripts do
+    // not, so this keeps web pages from driving the daemon.
+    if request.header("origin").is_some() {
+        return Err((403, "cross-origin requests are not allowed".to_string()));
+    }
+    if request.path != "/rpc" {
+        return Err((404, "not found".to_string()));
+    }
+    if request.method != "POST" {
+        return Err((405, "use POST".to_string()));
+    }
+    if request.bearer_token() != Some(token) {
+        return Err((401, "unauthorized".to_string()));
+    }
+    let length = request
+        .header("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .ok_or_else(|| (400, "missing Content-Length".to_string()))?;
+    if length > MAX_HTTP_BODY_BYTES {
+        return Err((413, "request body too large".to_string()));
+    }
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|err| (400, err.to_string()))?;
+    let message: Value = serde_json::from_slice(&body)
+        .map_err(|err| (400, format!("invalid JSON body: {err}")))?;
+    let method = message
+        .get("method")
+        .and_then(|value| value.as_str())
+        .ok_or_else(|| (400, "missing method".to_string()))?;
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
+    handle_rpc_request(state, method, params, client_version)
+        .await
+        .map_err(|message| (400, message))
+}
+
+async fn serve_http(listener: TcpListener, token: String, state: Arc<DaemonState>) {
+    let token = Arc::new(token);
+    loop {
+        match listener.accept().await {
+            Ok((socket, _addr)) => {
+                tokio::spawn(handle_http_client(
+                    socket,
+                    Arc::clone(&token),
+                    Arc::clone(&state),
+                ));
+            }
+            Err(err) => {
+                logging::error("daemon", format!("failed to accept HTTP connection: {err}"));
+            }
+        }
+    }
+}
+
 fn main() {
     let config = match parse_args() {
         Ok(config) => config,
@@ -1438,16 +2605,31 @@ fn main() {
         let listener = TcpListener::bind(config.listen)
             .await
             .unwrap_or_else(|err| panic!("failed to bind {}: {err}", config.listen));
-        eprintln!(
-            "codex-monitor-daemon listening on {} (data dir: {})",
-            config.listen,
-            state
-                .storage_path
-                .parent()
-                .unwrap_or(&state.storage_path)
-                .display()
+        logging::info(
+            "daemon",
+            format!(
+                "codex-monitor-daemon listening on {} (data dir: {})",
+                config.listen,
+                state
+                    .storage_path
+                    .parent()
+                    .unwrap_or(&state.storage_path)
+                    .display()
+            ),
         );
 
+        if let Some(http_listen) = config.http_listen {
+            let http_listener = TcpListener::bind(http_listen)
+                .await
+                .unwrap_or_else(|err| panic!("failed to bind {http_listen}: {err}"));
+            let token = Uuid::new_v4().simple().to_string();
+            logging::info("daemon", format!("HTTP RPC listening on http://{http_listen}/rpc"));
+            // Printed directly so the token stays out of the log buffer that
+            // RPC clients can read back.
+            eprintln!("HTTP RPC token: {token}");
+            tokio::spawn(serve_http(http_listener, token, Arc::clone(&state)));
+        }
+
         loop {
             match listener.accept().await {
                 Ok((socket, _addr)) => {
@@ -1458,8 +2640,43 @@ fn main() {
                         handle_client(socket, config, state, events).await;
                     });
                 }
-                Err(_) => continue,
+                Err(err) => {
+                    logging::error("daemon", format!("failed to accept connection: {err}"));
+                }
             }
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::RPC_METHODS;
+
+    /// The method names matched in `handle_rpc_request`, read from this
+    /// file's source.
+    fn handled_methods() -> BTreeSet<&'static str> {
+        let source = include_str!("codex_monitor_daemon.rs");
+        let start = source
+            .find("async fn handle_rpc_request(")
+            .expect("handler");
+        let handler = &source[start..];
+        let end = handler.find("\n}\n").expect("handler end");
+        handler[..end]
+            .lines()
+            .filter_map(|line| line.strip_prefix("        \""))
+            .filter_map(|arm| arm.split_once("\" =>"))
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn rpc_method_table_matches_the_handler() {
+        let described: BTreeSet<&str> = RPC_METHODS.iter().map(|spec| spec.name).collect();
+        assert_eq!(described.len(), RPC_METHODS.len(), "duplicate method");
+        let handled = handled_methods();
+        assert!(handled.contains("send_user_message"));
+        assert_eq!(described, handled);
+    }
+}
@@ -10,18 +10,29 @@ use tokio::time::timeout;
 
 pub(crate) use crate::backend::app_server::WorkspaceSession;
 use crate::backend::app_server::{
-    build_codex_command_with_bin, build_codex_path_env, check_codex_installation,
-    spawn_workspace_session as spawn_workspace_session_inner,
+    abort_all_turns as abort_all_turns_inner, build_codex_path_env, check_codex_installation,
+    probe_codex_app_server, rate_limits_expiry_ms,
+    spawn_workspace_session as spawn_workspace_session_inner, CachedRateLimits, ThreadListOptions,
 };
+use crate::backend::events::{AppServerEvent, EventSink};
 use crate::codex_home::{resolve_default_codex_home, resolve_workspace_codex_home};
 use crate::event_sink::TauriEventSink;
 use crate::rules;
 use crate::state::AppState;
-use crate::types::WorkspaceEntry;
+use crate::types::{
+    cap_access_mode, ModelInfo, PendingServerRequest, SessionMetrics, ThreadDefaults,
+    ThreadSummary, ThreadSummaryPage, TurnCompletion, TurnOptions, TurnUsage, WorkspaceEntry,
+    WorkspaceHealth,
+};
+use crate::utils::now_ms;
+
+const BLOCKING_TURN_TIMEOUT: Duration = Duration::from_secs(600);
+const DEFAULT_THREAD_LIST_LIMIT: u32 = 20;
 
 pub(crate) async fn spawn_workspace_session(
     entry: WorkspaceEntry,
     default_codex_bin: Option<String>,
+    default_codex_args: Vec<String>,
     app_handle: AppHandle,
     codex_home: Option<PathBuf>,
 ) -> Result<Arc<WorkspaceSession>, String> {
@@ -30,6 +41,7 @@ pub(crate) async fn spawn_workspace_session(
     spawn_workspace_session_inner(
         entry,
         default_codex_bin,
+        default_codex_args,
         client_version,
         event_sink,
         codex_home,
@@ -37,6 +49,59 @@ pub(crate) async fn spawn_workspace_session(
     .await
 }
 
+/// Returns the workspace session, transparently respawning codex if its process has exited.
+pub(crate) async fn get_or_respawn_session(
+    workspace_id: &str,
+    state: &AppState,
+    app: &AppHandle,
+) -> Result<Arc<WorkspaceSession>, String> {
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions
+            .get(workspace_id)
+            .cloned()
+            .ok_or("workspace not connected")?
+    };
+    let exit_status = {
+        let mut child = session.child.lock().await;
+        child.try_wait().map_err(|e| e.to_string())?
+    };
+    let Some(exit_status) = exit_status else {
+        return Ok(session);
+    };
+
+    {
+        let mut sessions = state.sessions.lock().await;
+        // Another caller may have already replaced the dead session.
+        if let Some(current) = sessions.get(workspace_id) {
+            if !Arc::ptr_eq(current, &session) {
+                return Ok(Arc::clone(current));
+            }
+        }
+        sessions.remove(workspace_id);
+    }
+    crate::workspaces::connect_workspace_inner(workspace_id.to_string(), state, app.clone())
+        .await?;
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions
+            .get(workspace_id)
+            .cloned()
+            .ok_or("workspace not connected")?
+    };
+    TauriEventSink::new(app.clone()).emit_app_server_event(AppServerEvent {
+        workspace_id: workspace_id.to_string(),
+        message: json!({
+            "method": "codex/restarted",
+            "params": {
+                "workspaceId": workspace_id,
+                "exitCode": exit_status.code(),
+            }
+        }),
+    });
+    Ok(session)
+}
+
 #[tauri::command]
 pub(crate) async fn codex_doctor(
     codex_bin: Option<String>,
@@ -52,15 +117,7 @@ pub(crate) async fn codex_doctor(
         .or(default_bin);
     let path_env = build_codex_path_env(resolved.as_deref());
     let version = check_codex_installation(resolved.clone()).await?;
-    let mut command = build_codex_command_with_bin(resolved.clone());
-    command.arg("app-server");
-    command.arg("--help");
-    command.stdout(std::process::Stdio::piped());
-    command.stderr(std::process::Stdio::piped());
-    let app_server_ok = match timeout(Duration::from_secs(5), command.output()).await {
-        Ok(result) => result.map(|output| output.status.success()).unwrap_or(false),
-        Err(_) => false,
-    };
+    let app_server_ok = probe_codex_app_server(resolved.clone()).await;
     let (node_ok, node_version, node_details) = {
         let mut node_command = Command::new("node");
         if let Some(ref path_env) = path_env {
@@ -129,15 +186,59 @@ pub(crate) async fn codex_doctor(
     }))
 }
 
+#[tauri::command]
+pub(crate) async fn workspace_health(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceHealth, String> {
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions.get(&workspace_id).cloned()
+    };
+    match session {
+        Some(session) => Ok(session.health().await),
+        None => Ok(WorkspaceHealth::default()),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn workspace_stderr(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions.get(&workspace_id).cloned()
+    };
+    match session {
+        Some(session) => Ok(session.stderr_tail().await),
+        None => Ok(Vec::new()),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn thread_usage(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+) -> Result<TurnUsage, String> {
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions.get(&workspace_id).cloned()
+    };
+    match session {
+        Some(session) => Ok(session.thread_usage(&thread_id).await),
+        None => Ok(TurnUsage::default()),
+    }
+}
+
 #[tauri::command]
 pub(crate) async fn start_thread(
     workspace_id: String,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
     let params = json!({
         "cwd": session.entry.path,
         "approvalPolicy": "on-request"
@@ -149,16 +250,17 @@ pub(crate) async fn start_thread(
 pub(crate) async fn resume_thread(
     workspace_id: String,
     thread_id: String,
+    request_id: Option<String>,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
     let params = json!({
         "threadId": thread_id
     });
-    session.send_request("thread/resume", params).await
+    session
+        .send_cancellable_request("thread/resume", params, request_id.as_deref(), None)
+        .await
 }
 
 #[tauri::command]
@@ -167,11 +269,9 @@ pub(crate) async fn list_threads(
     cursor: Option<String>,
     limit: Option<u32>,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
     let params = json!({
         "cursor": cursor,
         "limit": limit,
@@ -179,16 +279,67 @@ pub(crate) async fn list_threads(
     session.send_request("thread/list", params).await
 }
 
+async fn pinned_threads(state: &AppState, workspace_id: &str) -> Vec<String> {
+    let workspaces = state.workspaces.lock().await;
+    workspaces
+        .get(workspace_id)
+        .map(|entry| entry.settings.pinned_threads.clone())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub(crate) async fn list_workspace_threads(
+    workspace_id: String,
+    cursor: Option<String>,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadSummaryPage, String> {
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
+    session
+        .list_workspace_threads(ThreadListOptions {
+            cursor,
+            limit: limit.unwrap_or(DEFAULT_THREAD_LIST_LIMIT) as usize,
+            query: None,
+            pinned: pinned_threads(&state, &workspace_id).await,
+        })
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn search_threads(
+    workspace_id: String,
+    query: String,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<ThreadSummary>, String> {
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
+    let limit = limit.unwrap_or(DEFAULT_THREAD_LIST_LIMIT) as usize;
+    let pinned = pinned_threads(&state, &workspace_id).await;
+    session.search_threads(&query, limit, pinned).await
+}
+
+#[tauri::command]
+pub(crate) async fn delete_thread(
+    workspace_id: String,
+    thread_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
+    let removed = session.delete_thread(&thread_id).await?;
+    Ok(json!({ "threadId": thread_id, "deleted": true, "removedFiles": removed }))
+}
+
 #[tauri::command]
 pub(crate) async fn archive_thread(
     workspace_id: String,
     thread_id: String,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
     let params = json!({
         "threadId": thread_id
     });
@@ -200,18 +351,53 @@ pub(crate) async fn send_user_message(
     workspace_id: String,
     thread_id: String,
     text: String,
-    model: Option<String>,
-    effort: Option<String>,
-    access_mode: Option<String>,
-    images: Option<Vec<String>>,
-    collaboration_mode: Option<Value>,
+    options: Option<TurnOptions>,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
+    let TurnOptions {
+        model,
+        effort,
+        access_mode,
+        images,
+        collaboration_mode,
+        request_id,
+    } = options.unwrap_or_default();
+    let models = state
+        .models_cache
+        .lock()
+        .await
+        .get(&workspace_id)
+        .map(ModelInfo::list_from_response);
+    let defaults = {
+        let mut thread_defaults = state.thread_defaults.lock().await;
+        thread_defaults
+            .entry(thread_id.clone())
+            .or_default()
+            .resolve_for_turn(model, effort, access_mode, models.as_deref())?
+    };
+    let ThreadDefaults {
+        model,
+        effort,
+        access_mode,
+    } = defaults;
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
+    // The abort-all kill switch keeps every new turn read-only for a while.
+    let access_mode = if session.read_only_locked() {
+        "read-only".to_string()
+    } else {
+        access_mode.unwrap_or_else(|| "current".to_string())
+    };
+    let max_access_mode = state
+        .workspaces
+        .lock()
+        .await
         .get(&workspace_id)
-        .ok_or("workspace not connected")?;
-    let access_mode = access_mode.unwrap_or_else(|| "current".to_string());
+        .and_then(|entry| entry.settings.max_access_mode.clone());
+    let (access_mode, downgraded_from) = {
+        let (capped, downgraded) = cap_access_mode(&access_mode, max_access_mode.as_deref());
+        (capped, downgraded.then_some(access_mode))
+    };
     let sandbox_policy = match access_mode.as_str() {
         "full-access" => json!({
             "type": "dangerFullAccess"
@@ -267,18 +453,66 @@ pub(crate) async fn send_user_message(
         "effort": effort,
         "collaborationMode": collaboration_mode,
     });
-    session.send_request("turn/start", params).await
+    let mut response = session
+        .start_turn(&thread_id, params, request_id.as_deref())
+        .await?;
+    if let (Some(requested), Some(object)) = (downgraded_from, response.as_object_mut()) {
+        object.insert(
+            "accessModeDowngrade".to_string(),
+            json!({ "requested": requested, "applied": access_mode }),
+        );
+    }
+    Ok(response)
+}
+
+#[tauri::command]
+pub(crate) async fn send_user_message_blocking(
+    workspace_id: String,
+    thread_id: String,
+    text: String,
+    options: Option<TurnOptions>,
+    timeout_ms: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<TurnCompletion, String> {
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
+    let completion = session.watch_turn(&thread_id).await?;
+    let options = TurnOptions {
+        request_id: None,
+        ..options.unwrap_or_default()
+    };
+    let started =
+        send_user_message(workspace_id, thread_id.clone(), text, Some(options), state, app).await;
+    let wait = timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(BLOCKING_TURN_TIMEOUT);
+    session
+        .finish_watched_turn(&thread_id, started, completion, wait)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn set_thread_defaults(
+    thread_id: String,
+    model: Option<String>,
+    effort: Option<String>,
+    access_mode: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ThreadDefaults, String> {
+    let mut thread_defaults = state.thread_defaults.lock().await;
+    Ok(thread_defaults
+        .entry(thread_id)
+        .or_default()
+        .resolve(model, effort, access_mode))
 }
 
 #[tauri::command]
 pub(crate) async fn collaboration_mode_list(
     workspace_id: String,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
     session
         .send_request("collaborationMode/list", json!({}))
         .await
@@ -290,11 +524,9 @@ pub(crate) async fn turn_interrupt(
     thread_id: String,
     turn_id: String,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
     let params = json!({
         "threadId": thread_id,
         "turnId": turn_id,
@@ -302,6 +534,12 @@ pub(crate) async fn turn_interrupt(
     session.send_request("turn/interrupt", params).await
 }
 
+#[tauri::command]
+pub(crate) async fn abort_all_turns(state: State<'_, AppState>) -> Result<Value, String> {
+    let sessions: Vec<_> = state.sessions.lock().await.values().cloned().collect();
+    Ok(abort_all_turns_inner(sessions).await)
+}
+
 #[tauri::command]
 pub(crate) async fn start_review(
     workspace_id: String,
@@ -309,11 +547,9 @@ pub(crate) async fn start_review(
     target: Value,
     delivery: Option<String>,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
     let mut params = Map::new();
     params.insert("threadId".to_string(), json!(thread_id));
     params.insert("target".to_string(), target);
@@ -328,39 +564,87 @@ pub(crate) async fn start_review(
 #[tauri::command]
 pub(crate) async fn model_list(
     workspace_id: String,
+    force: Option<bool>,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    cached_model_list(&workspace_id, force.unwrap_or(false), &state, &app).await
+}
+
+#[tauri::command]
+pub(crate) async fn models_list(
+    workspace_id: String,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<ModelInfo>, String> {
+    let response = cached_model_list(&workspace_id, force.unwrap_or(false), &state, &app).await?;
+    Ok(ModelInfo::list_from_response(&response))
+}
+
+async fn cached_model_list(
+    workspace_id: &str,
+    force: bool,
+    state: &AppState,
+    app: &AppHandle,
+) -> Result<Value, String> {
+    if !force {
+        if let Some(response) = state.models_cache.lock().await.get(workspace_id) {
+            return Ok(response.clone());
+        }
+    }
+    let session = get_or_respawn_session(workspace_id, state, app).await?;
     let params = json!({});
-    session.send_request("model/list", params).await
+    let response = session.send_request("model/list", params).await?;
+    if response.get("error").is_none() {
+        state
+            .models_cache
+            .lock()
+            .await
+            .insert(workspace_id.to_string(), response.clone());
+    }
+    Ok(response)
 }
 
 #[tauri::command]
 pub(crate) async fn account_rate_limits(
     workspace_id: String,
+    force: Option<bool>,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
-    session
+    if !force.unwrap_or(false) {
+        let cache = state.rate_limits_cache.lock().await;
+        if let Some(cached) = cache.get(&workspace_id) {
+            if cached.expires_at_ms > now_ms() {
+                return Ok(cached.response.clone());
+            }
+        }
+    }
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
+    let response = session
         .send_request("account/rateLimits/read", Value::Null)
-        .await
+        .await?;
+    if response.get("error").is_none() {
+        let fetched_at_ms = now_ms();
+        state.rate_limits_cache.lock().await.insert(
+            workspace_id,
+            CachedRateLimits {
+                response: response.clone(),
+                expires_at_ms: rate_limits_expiry_ms(&response, fetched_at_ms),
+            },
+        );
+    }
+    Ok(response)
 }
 
 #[tauri::command]
 pub(crate) async fn skills_list(
     workspace_id: String,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<Value, String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
     let params = json!({
         "cwd": session.entry.path
     });
@@ -373,14 +657,60 @@ pub(crate) async fn respond_to_server_request(
     request_id: u64,
     result: Value,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<(), String> {
-    let sessions = state.sessions.lock().await;
-    let session = sessions
-        .get(&workspace_id)
-        .ok_or("workspace not connected")?;
+    let session = get_or_respawn_session(&workspace_id, &state, &app).await?;
     session.send_response(request_id, result).await
 }
 
+/// Cancels a request started with a client-chosen `request_id`; see
+/// `WorkspaceSession::cancel_request`.
+#[tauri::command]
+pub(crate) async fn cancel_request(
+    workspace_id: String,
+    request_id: String,
+    state: State<'_, AppState>,
+) -> Result<Value, String> {
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not connected")?
+    };
+    Ok(session.cancel_request(&request_id).await)
+}
+
+#[tauri::command]
+pub(crate) async fn session_metrics(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<SessionMetrics, String> {
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not connected")?
+    };
+    Ok(session.metrics.snapshot())
+}
+
+#[tauri::command]
+pub(crate) async fn list_pending_server_requests(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<PendingServerRequest>, String> {
+    let session = {
+        let sessions = state.sessions.lock().await;
+        sessions
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not connected")?
+    };
+    Ok(session.pending_server_requests().await)
+}
+
 #[tauri::command]
 pub(crate) async fn remember_approval_rule(
     workspace_id: String,
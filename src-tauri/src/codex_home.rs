@@ -1,5 +1,5 @@
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::types::WorkspaceEntry;
 
@@ -31,7 +31,49 @@ pub(crate) fn resolve_default_codex_home() -> Option<PathBuf> {
     resolve_home_dir().map(|home| home.join(".codex"))
 }
 
-fn resolve_home_dir() -> Option<PathBuf> {
+/// Deletes every rollout file recorded for `thread_id`, whether live under
+/// `sessions/` or archived under `archived_sessions/`. Returns how many
+/// files were removed.
+pub(crate) fn remove_thread_rollouts(codex_home: &Path, thread_id: &str) -> Result<usize, String> {
+    if thread_id.is_empty()
+        || !thread_id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err(format!("Invalid thread id `{thread_id}`."));
+    }
+    let suffix = format!("-{thread_id}.jsonl");
+    let mut pending: Vec<PathBuf> = ["sessions", "archived_sessions"]
+        .iter()
+        .map(|dir| codex_home.join(dir))
+        .collect();
+    let mut removed = 0;
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with("rollout-") && name.ends_with(&suffix) {
+                std::fs::remove_file(&path)
+                    .map_err(|err| format!("Failed to delete {}: {err}", path.display()))?;
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+pub(crate) fn resolve_home_dir() -> Option<PathBuf> {
     if let Ok(value) = env::var("HOME") {
         if !value.trim().is_empty() {
             return Some(PathBuf::from(value));
@@ -44,3 +86,31 @@ fn resolve_home_dir() -> Option<PathBuf> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::remove_thread_rollouts;
+    use uuid::Uuid;
+
+    #[test]
+    fn remove_thread_rollouts_deletes_live_and_archived_files_only() {
+        let home = std::env::temp_dir().join(format!("codex-monitor-home-{}", Uuid::new_v4()));
+        let day = home.join("sessions/2025/01/02");
+        let archived = home.join("archived_sessions");
+        std::fs::create_dir_all(&day).expect("create sessions dir");
+        std::fs::create_dir_all(&archived).expect("create archive dir");
+        let thread_id = "0194a0c1-1111-7000-8000-000000000001";
+        std::fs::write(day.join(format!("rollout-2025-01-02T10-00-00-{thread_id}.jsonl")), "")
+            .expect("write live rollout");
+        std::fs::write(archived.join(format!("rollout-2025-01-01T09-00-00-{thread_id}.jsonl")), "")
+            .expect("write archived rollout");
+        let other = day.join("rollout-2025-01-02T11-00-00-other.jsonl");
+        std::fs::write(&other, "").expect("write other rollout");
+
+        assert_eq!(remove_thread_rollouts(&home, thread_id), Ok(2));
+        assert!(other.exists());
+        assert_eq!(remove_thread_rollouts(&home, thread_id), Ok(0));
+        assert!(remove_thread_rollouts(&home, "../escape").is_err());
+        let _ = std::fs::remove_dir_all(&home);
+    }
+}
@@ -0,0 +1,195 @@
+use serde_json::{json, Value};
+use tauri::{AppHandle, State};
+
+use crate::codex::codex_doctor;
+use crate::codex_home::resolve_home_dir;
+use crate::logging::{LogEntry, LogLevel};
+use crate::state::AppState;
+use crate::types::{AppSettings, WorkspaceHealth};
+use crate::utils::now_ms;
+use crate::workspaces::runner_info;
+
+const DEFAULT_LOG_LIMIT: usize = 500;
+const REDACTED: &str = "[redacted]";
+
+#[tauri::command]
+pub(crate) async fn get_logs(
+    level: Option<String>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<LogEntry>, String> {
+    let min_level = match level.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => Some(LogLevel::parse(value).ok_or_else(|| {
+            format!("Unknown log level `{value}`. Use debug, info, warn or error.")
+        })?),
+        None => None,
+    };
+    Ok(state
+        .logs
+        .recent(min_level, limit.unwrap_or(DEFAULT_LOG_LIMIT)))
+}
+
+/// Serializes settings for a bug report with every secret replaced, so the
+/// bundle can be attached to a public issue as-is. Codex args can carry API
+/// keys through `-c` overrides, so only their count is kept.
+fn redacted_settings(settings: &AppSettings) -> Value {
+    let mut value = serde_json::to_value(settings).unwrap_or(Value::Null);
+    if let Some(token) = value.get_mut("remoteBackendToken") {
+        if !token.is_null() {
+            *token = Value::String(REDACTED.to_string());
+        }
+    }
+    if let Some(Value::Array(args)) = value.get_mut("codexArgs") {
+        for arg in args {
+            *arg = Value::String(REDACTED.to_string());
+        }
+    }
+    value
+}
+
+/// The spawn command line logged when a session starts repeats the codex
+/// args verbatim, so everything after the workspace id is dropped.
+fn redacted_logs(mut entries: Vec<LogEntry>) -> Vec<LogEntry> {
+    const SPAWN_MARKER: &str = "] starting ";
+    for entry in &mut entries {
+        if entry.tag != "app-server" {
+            continue;
+        }
+        if let Some(index) = entry.message.find(SPAWN_MARKER) {
+            entry.message.truncate(index + SPAWN_MARKER.len());
+            entry.message.push_str(REDACTED);
+        }
+    }
+    entries
+}
+
+/// Rewrites the home directory to `~` in every string, since paths under it
+/// usually include the account name.
+fn replace_home_dir(value: &mut Value, home: &str) {
+    match value {
+        Value::String(text) if text.contains(home) => *text = text.replace(home, "~"),
+        Value::Array(items) => {
+            for item in items {
+                replace_home_dir(item, home);
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                replace_home_dir(item, home);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn collect_diagnostics(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Value, String> {
+    let runner = runner_info(app, state.clone()).await?;
+    let doctor = match codex_doctor(None, state.clone()).await {
+        Ok(result) => result,
+        Err(err) => json!({ "ok": false, "details": err }),
+    };
+    let settings = redacted_settings(&*state.app_settings.lock().await);
+    let mut entries: Vec<_> = state.workspaces.lock().await.values().cloned().collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+    let sessions = state.sessions.lock().await.clone();
+    let mut workspaces = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let health = match sessions.get(&entry.id) {
+            Some(session) => session.health().await,
+            None => WorkspaceHealth::default(),
+        };
+        workspaces.push(json!({
+            "id": entry.id,
+            "name": entry.name,
+            "path": entry.path,
+            "kind": entry.kind,
+            "codexBin": entry.codex_bin,
+            "health": health,
+        }));
+    }
+    let mut diagnostics = json!({
+        "collectedAtMs": now_ms(),
+        "runner": runner,
+        "cloudProvider": runner.cloud_provider,
+        "doctor": doctor,
+        "settings": settings,
+        "workspaces": workspaces,
+        "logs": redacted_logs(state.logs.recent(None, DEFAULT_LOG_LIMIT)),
+    });
+    if let Some(home) = resolve_home_dir() {
+        let home = home.to_string_lossy();
+        let home = home.trim_end_matches(['/', '\\']);
+        if !home.is_empty() {
+            replace_home_dir(&mut diagnostics, home);
+        }
+    }
+    Ok(diagnostics)
+}
+
+#[tauri::command]
+pub(crate) async fn save_diagnostics(
+    path: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let diagnostics = collect_diagnostics(app, state).await?;
+    let data = serde_json::to_string_pretty(&diagnostics).map_err(|e| e.to_string())?;
+    std::fs::write(&path, data).map_err(|err| format!("Failed to write {path}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{redacted_logs, redacted_settings, replace_home_dir};
+    use crate::logging::{LogEntry, LogLevel};
+    use crate::types::AppSettings;
+    use serde_json::json;
+
+    #[test]
+    fn redacted_settings_hides_the_remote_token() {
+        let settings = AppSettings {
+            remote_backend_token: Some("secret".to_string()),
+            codex_args: vec!["-c".to_string(), "api_key=sk-live".to_string()],
+            ..AppSettings::default()
+        };
+        let value = redacted_settings(&settings);
+        assert_eq!(value["remoteBackendToken"], "[redacted]");
+        assert_eq!(value["codexArgs"], json!(["[redacted]", "[redacted]"]));
+        assert!(!value.to_string().contains("secret"));
+        assert!(!value.to_string().contains("sk-live"));
+
+        let value = redacted_settings(&AppSettings::default());
+        assert!(value["remoteBackendToken"].is_null());
+    }
+
+    #[test]
+    fn diagnostics_hide_spawn_args_and_home_paths() {
+        let entry = |tag: &str, message: &str| LogEntry {
+            timestamp_ms: 0,
+            level: LogLevel::Debug,
+            tag: tag.to_string(),
+            message: message.to_string(),
+        };
+        let logs = redacted_logs(vec![
+            entry("app-server", "[ws-1] starting codex -c api_key=sk-live"),
+            entry("app-server", "[ws-1] session exited"),
+            entry("git", "[ws-1] starting fetch"),
+        ]);
+        assert_eq!(logs[0].message, "[ws-1] starting [redacted]");
+        assert_eq!(logs[1].message, "[ws-1] session exited");
+        assert_eq!(logs[2].message, "[ws-1] starting fetch");
+
+        let mut value = json!({
+            "path": "/home/alice/projects/app",
+            "nested": [{ "codexBin": "/home/alice/.local/bin/codex" }],
+            "other": "/opt/codex",
+        });
+        replace_home_dir(&mut value, "/home/alice");
+        assert_eq!(value["path"], "~/projects/app");
+        assert_eq!(value["nested"][0]["codexBin"], "~/.local/bin/codex");
+        assert_eq!(value["other"], "/opt/codex");
+    }
+}
@@ -1,19 +1,20 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use serde::Serialize;
 use tauri::{AppHandle, Emitter, Manager, State};
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::sync::oneshot;
 
+use crate::logging;
 use crate::state::AppState;
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{FromSample, Sample, SampleFormat, SizedSample};
 use sha2::{Digest, Sha256};
-use whisper_rs::get_lang_id;
+use whisper_rs::{get_lang_id, get_lang_str};
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
 #[cfg(target_os = "macos")]
@@ -175,6 +176,18 @@ pub(crate) enum DictationEvent {
     Canceled { message: String },
 }
 
+/// Emitted as `dictation-result` once a transcription completes, so the UI can
+/// see which language Whisper settled on and how long decoding took.
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct DictationResult {
+    pub(crate) text: String,
+    #[serde(rename = "detectedLanguage")]
+    pub(crate) detected_language: Option<String>,
+    pub(crate) confidence: Option<f32>,
+    #[serde(rename = "durationMs")]
+    pub(crate) duration_ms: u64,
+}
+
 pub(crate) struct DictationSessionHandle {
     pub(crate) stop: mpsc::Sender<()>,
     pub(crate) stopped: oneshot::Receiver<()>,
@@ -225,6 +238,15 @@ fn model_info(model_id: &str) -> Option<&'static DictationModelInfo> {
     MODEL_CATALOG.iter().find(|info| info.id == model_id)
 }
 
+fn sha256_hex(hasher: Sha256) -> String {
+    let mut hash_hex = String::with_capacity(64);
+    for byte in hasher.finalize() {
+        use std::fmt::Write;
+        let _ = write!(&mut hash_hex, "{:02x}", byte);
+    }
+    hash_hex
+}
+
 fn model_path(app: &AppHandle, model_id: &str) -> Result<PathBuf, String> {
     let info = model_info(model_id)
         .ok_or_else(|| format!("Unknown dictation model: {model_id}"))?;
@@ -234,7 +256,56 @@ fn model_path(app: &AppHandle, model_id: &str) -> Result<PathBuf, String> {
 fn model_temp_path(app: &AppHandle, model_id: &str) -> Result<PathBuf, String> {
     let info = model_info(model_id)
         .ok_or_else(|| format!("Unknown dictation model: {model_id}"))?;
-    Ok(model_dir(app).join(format!("{}.partial", info.filename)))
+    Ok(model_dir(app).join(format!("{}.part", info.filename)))
+}
+
+/// Hashes an interrupted download so a resumed transfer can keep feeding the
+/// same hasher. Returns a fresh hasher when there is nothing to resume.
+async fn hash_partial_download(path: &Path) -> Result<(Sha256, u64), String> {
+    let mut hasher = Sha256::new();
+    let mut file = match tokio::fs::File::open(path).await {
+        Ok(file) => file,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok((hasher, 0)),
+        Err(error) => return Err(error.to_string()),
+    };
+    let mut buffer = vec![0u8; 1024 * 1024];
+    let mut length = 0u64;
+    loop {
+        let read = file.read(&mut buffer).await.map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        length += read as u64;
+    }
+    Ok((hasher, length))
+}
+
+/// Requests the model, asking for the remaining bytes when `resume_from` is
+/// non-zero. A server that rejects the range gets a plain request instead.
+async fn request_model(
+    client: &reqwest::Client,
+    url: &str,
+    resume_from: u64,
+) -> Result<reqwest::Response, String> {
+    let mut request = client.get(url);
+    if resume_from > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={resume_from}-"));
+    }
+    let mut response = request
+        .send()
+        .await
+        .map_err(|error| format!("Failed to download model: {error}"))?;
+    if resume_from > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|error| format!("Failed to download model: {error}"))?;
+    }
+    response
+        .error_for_status()
+        .map_err(|error| format!("Model download failed: {error}"))
 }
 
 fn missing_status(model_id: &str) -> DictationModelStatus {
@@ -351,6 +422,18 @@ async fn refresh_status(
         dictation.model_status = ready_status(model_id, &path);
     } else {
         dictation.model_status = missing_status(model_id);
+        // Surface an interrupted download so the UI can offer to resume it.
+        let partial_bytes = model_temp_path(app, model_id)
+            .ok()
+            .and_then(|temp_path| std::fs::metadata(temp_path).ok())
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        if partial_bytes > 0 {
+            dictation.model_status.progress = Some(DictationDownloadProgress {
+                downloaded_bytes: partial_bytes,
+                total_bytes: None,
+            });
+        }
     }
     dictation.model_status.clone()
 }
@@ -381,6 +464,11 @@ pub(crate) async fn dictation_download_model(
     {
         return Ok(current);
     }
+    let resumed_bytes = current
+        .progress
+        .as_ref()
+        .map(|progress| progress.downloaded_bytes)
+        .unwrap_or(0);
 
     let cancel_flag = Arc::new(AtomicBool::new(false));
     {
@@ -400,7 +488,7 @@ pub(crate) async fn dictation_download_model(
             state: DictationModelState::Downloading,
             model_id: model_id.clone(),
             progress: Some(DictationDownloadProgress {
-                downloaded_bytes: 0,
+                downloaded_bytes: resumed_bytes,
                 total_bytes: None,
             }),
             error: None,
@@ -492,29 +580,21 @@ pub(crate) async fn dictation_download_model(
                 return;
             }
         };
-        let response = match client.get(url).send().await {
-            Ok(response) => response,
-            Err(error) => {
-                let status = DictationModelStatus {
-                    state: DictationModelState::Error,
-                    model_id: model_id_clone.clone(),
-                    progress: None,
-                    error: Some(format!("Failed to download model: {error}")),
-                    path: None,
-                };
-                update_status(&app_handle, &state, status).await;
-                clear_download_state(&state).await;
-                return;
+        let (mut hasher, mut resume_from) = match hash_partial_download(&temp_path).await {
+            Ok(partial) => partial,
+            Err(_) => {
+                let _ = tokio::fs::remove_file(&temp_path).await;
+                (Sha256::new(), 0)
             }
         };
-        let response = match response.error_for_status() {
+        let response = match request_model(&client, url, resume_from).await {
             Ok(response) => response,
             Err(error) => {
                 let status = DictationModelStatus {
                     state: DictationModelState::Error,
                     model_id: model_id_clone.clone(),
                     progress: None,
-                    error: Some(format!("Model download failed: {error}")),
+                    error: Some(error),
                     path: None,
                 };
                 update_status(&app_handle, &state, status).await;
@@ -523,9 +603,24 @@ pub(crate) async fn dictation_download_model(
             }
         };
 
-        let total = response.content_length();
-        let mut downloaded = 0u64;
-        let mut file = match tokio::fs::File::create(&temp_path).await {
+        // Servers that ignore the range send the whole file, so start over.
+        let resumed =
+            resume_from > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+        if !resumed {
+            hasher = Sha256::new();
+            resume_from = 0;
+        }
+        let total = response.content_length().map(|length| length + resume_from);
+        let mut downloaded = resume_from;
+        let opened = if resumed {
+            tokio::fs::OpenOptions::new()
+                .append(true)
+                .open(&temp_path)
+                .await
+        } else {
+            tokio::fs::File::create(&temp_path).await
+        };
+        let mut file = match opened {
             Ok(file) => file,
             Err(error) => {
                 let status = DictationModelStatus {
@@ -542,7 +637,6 @@ pub(crate) async fn dictation_download_model(
         };
 
         let mut response = response;
-        let mut hasher = Sha256::new();
         let mut last_progress = Instant::now();
         loop {
             let cancel = {
@@ -565,12 +659,16 @@ pub(crate) async fn dictation_download_model(
                 Ok(Some(chunk)) => chunk,
                 Ok(None) => break,
                 Err(error) => {
-                    let _ = tokio::fs::remove_file(&temp_path).await;
+                    // Keep what arrived so the next attempt can resume.
+                    let _ = file.flush().await;
                     let status = DictationModelStatus {
                         state: DictationModelState::Error,
                         model_id: model_id_clone.clone(),
-                        progress: None,
-                        error: Some(format!("Model download failed: {error}")),
+                        progress: Some(DictationDownloadProgress {
+                            downloaded_bytes: downloaded,
+                            total_bytes: total,
+                        }),
+                        error: Some(format!("Model download interrupted: {error}")),
                         path: None,
                     };
                     update_status(&app_handle, &state, status).await;
@@ -611,33 +709,34 @@ pub(crate) async fn dictation_download_model(
             }
         }
 
-        let hash = hasher.finalize();
-        let mut hash_hex = String::with_capacity(64);
-        for byte in hash {
-            use std::fmt::Write;
-            let _ = write!(&mut hash_hex, "{:02x}", byte);
-        }
-        if hash_hex != expected_sha {
+        if let Err(error) = file.flush().await {
             let _ = tokio::fs::remove_file(&temp_path).await;
             let status = DictationModelStatus {
                 state: DictationModelState::Error,
                 model_id: model_id_clone.clone(),
                 progress: None,
-                error: Some("Model hash mismatch; download canceled.".to_string()),
+                error: Some(format!("Failed to finalize model: {error}")),
                 path: None,
             };
             update_status(&app_handle, &state, status).await;
             clear_download_state(&state).await;
             return;
         }
+        drop(file);
 
-        if let Err(error) = file.flush().await {
+        // Verify only after the file is fully flushed so a truncated download
+        // can never be moved into place.
+        let hash_hex = sha256_hex(hasher);
+        if hash_hex != expected_sha {
             let _ = tokio::fs::remove_file(&temp_path).await;
             let status = DictationModelStatus {
                 state: DictationModelState::Error,
                 model_id: model_id_clone.clone(),
                 progress: None,
-                error: Some(format!("Failed to finalize model: {error}")),
+                error: Some(format!(
+                    "Model checksum mismatch (expected {expected_sha}, got {hash_hex}); \
+                     the download was deleted."
+                )),
                 path: None,
             };
             update_status(&app_handle, &state, status).await;
@@ -953,6 +1052,7 @@ pub(crate) async fn dictation_stop(
 
         let preferred = preferred_language.clone();
 
+        let started_at = Instant::now();
         let result = tokio::task::spawn_blocking(move || {
             transcribe_audio(samples, sample_rate, &context, preferred)
         })
@@ -969,15 +1069,26 @@ pub(crate) async fn dictation_stop(
         }
 
         match outcome {
-            Ok(text) => {
-                if !text.trim().is_empty() {
+            Ok(mut result) => {
+                result.duration_ms = started_at.elapsed().as_millis() as u64;
+                logging::info(
+                    "dictation",
+                    format!(
+                        "transcribed in {}ms (language={})",
+                        result.duration_ms,
+                        result.detected_language.as_deref().unwrap_or("unknown")
+                    ),
+                );
+                let _ = app_handle.emit("dictation-result", &result);
+                if !result.text.trim().is_empty() {
                     emit_event(
                         &app_handle,
-                        DictationEvent::Transcript { text },
+                        DictationEvent::Transcript { text: result.text },
                     );
                 }
             }
             Err(message) => {
+                logging::error("dictation", format!("transcription failed: {message}"));
                 emit_event(
                     &app_handle,
                     DictationEvent::Error { message },
@@ -1202,9 +1313,12 @@ fn start_capture_thread(
         }
     });
 
-    eprintln!(
-        "dictation: capture started (rate={}Hz, channels={}, format={:?})",
-        sample_rate, channels, sample_format
+    logging::info(
+        "dictation",
+        format!(
+            "capture started (rate={}Hz, channels={}, format={:?})",
+            sample_rate, channels, sample_format
+        ),
     );
     let _ = ready_tx.send(Ok(sample_rate));
     let _ = stop_rx.recv();
@@ -1285,9 +1399,15 @@ fn transcribe_audio(
     sample_rate: u32,
     context: &WhisperContext,
     preferred_language: Option<String>,
-) -> Result<String, String> {
+) -> Result<DictationResult, String> {
+    let mut result = DictationResult {
+        text: String::new(),
+        detected_language: None,
+        confidence: None,
+        duration_ms: 0,
+    };
     if samples.is_empty() {
-        return Ok(String::new());
+        return Ok(result);
     }
     let mut max = 0.0f32;
     let mut sum = 0.0f32;
@@ -1310,13 +1430,16 @@ fn transcribe_audio(
             *value = (*value * gain).clamp(-1.0, 1.0);
         }
     }
-    eprintln!(
-        "dictation: captured {} samples ({:.2}s), max={:.4}, rms={:.4}, gain={:.2}",
-        samples.len(),
-        duration,
-        max,
-        rms,
-        gain
+    logging::info(
+        "dictation",
+        format!(
+            "captured {} samples ({:.2}s), max={:.4}, rms={:.4}, gain={:.2}",
+            samples.len(),
+            duration,
+            max,
+            rms,
+            gain
+        ),
     );
     if duration < 0.2 {
         return Err("Audio too short for transcription.".to_string());
@@ -1343,6 +1466,7 @@ fn transcribe_audio(
     params.set_no_context(true);
     params.set_single_segment(false);
     let mut forced_language: Option<String> = None;
+    let mut language_probs: Option<Vec<f32>> = None;
     if let Some(preferred) = preferred_language.clone() {
         if let Some(pref_id) = get_lang_id(&preferred) {
             if state.pcm_to_mel(&audio, threads).is_ok() {
@@ -1356,6 +1480,7 @@ fn transcribe_audio(
                     if best_prob > 0.0 && (best_prob - pref_prob) <= 0.30 {
                         forced_language = Some(preferred);
                     }
+                    language_probs = Some(probs);
                 }
             }
         }
@@ -1377,7 +1502,7 @@ fn transcribe_audio(
     let segments = state
         .full_n_segments()
         .map_err(|error| format!("Failed to read segments: {error}"))?;
-    eprintln!("dictation: whisper segments={}", segments);
+    logging::info("dictation", format!("whisper segments={}", segments));
     let mut transcript = String::new();
     for index in 0..segments {
         let segment = state
@@ -1387,13 +1512,27 @@ fn transcribe_audio(
     }
     let cleaned = transcript.trim().to_string();
     if cleaned.is_empty() {
-        eprintln!(
-            "dictation: no speech detected (rms={:.4}, max={:.4}, duration={:.2}s, segments={})",
-            rms, max, duration, segments
+        logging::info(
+            "dictation",
+            format!(
+                "no speech detected (rms={:.4}, max={:.4}, duration={:.2}s, segments={})",
+                rms, max, duration, segments
+            ),
         );
-        return Ok(String::new());
+        return Ok(result);
+    }
+    let language_id = match forced_language.as_deref() {
+        Some(language) => get_lang_id(language),
+        None => state.full_lang_id_from_state().ok(),
+    };
+    if let Some(language_id) = language_id {
+        result.detected_language = get_lang_str(language_id).map(str::to_string);
+        result.confidence = language_probs
+            .as_ref()
+            .and_then(|probs| probs.get(language_id.max(0) as usize).copied());
     }
-    Ok(cleaned)
+    result.text = cleaned;
+    Ok(result)
 }
 
 fn resample_audio(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
@@ -1,24 +1,157 @@
-use tauri::{AppHandle, Emitter};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use crate::backend::events::{AppServerEvent, EventSink, TerminalOutput};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::backend::events::{
+    AppServerEvent, EventSink, GitStatusChanged, SessionExited, TerminalExit, TerminalOutput,
+    TurnProgress, WorkspaceFilesChanged,
+};
+use crate::settings::dnd_active_now;
+use crate::state::AppState;
+
+const NOTIFICATION_PREVIEW_CHARS: usize = 140;
 
 #[derive(Clone)]
 pub(crate) struct TauriEventSink {
     app: AppHandle,
+    last_agent_messages: Arc<Mutex<HashMap<String, String>>>,
 }
 
 impl TauriEventSink {
     pub(crate) fn new(app: AppHandle) -> Self {
-        Self { app }
+        Self {
+            app,
+            last_agent_messages: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Remembers the latest agent message per thread so `turn/completed`,
+    /// which carries no text of its own, can be turned into a notification.
+    fn track_completion(&self, event: &AppServerEvent) -> Option<String> {
+        let method = event.message.get("method").and_then(Value::as_str)?;
+        let params = event.message.get("params")?;
+        let thread_id = params
+            .get("threadId")
+            .or_else(|| params.get("thread_id"))
+            .or_else(|| params.get("turn").and_then(|turn| turn.get("threadId")))
+            .and_then(Value::as_str)?;
+        let key = format!("{}:{thread_id}", event.workspace_id);
+        let mut messages = self.last_agent_messages.lock().ok()?;
+        match method {
+            "item/completed" => {
+                let item = params.get("item")?;
+                if item.get("type").and_then(Value::as_str) == Some("agentMessage") {
+                    let text = item.get("text").and_then(Value::as_str).unwrap_or("");
+                    messages.insert(key, text.to_string());
+                }
+                None
+            }
+            "turn/completed" => messages
+                .remove(&key)
+                .map(|text| notification_preview(&text, NOTIFICATION_PREVIEW_CHARS))
+                .filter(|preview| !preview.is_empty()),
+            _ => None,
+        }
+    }
+
+    fn notify_completion(&self, workspace_id: String, preview: String) {
+        let app = self.app.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<AppState>();
+            {
+                let settings = state.app_settings.lock().await;
+                if !settings.desktop_notifications_enabled || dnd_active_now(&settings) {
+                    return;
+                }
+            }
+            let title = {
+                let workspaces = state.workspaces.lock().await;
+                match workspaces.get(&workspace_id) {
+                    Some(entry) if entry.settings.muted => return,
+                    Some(entry) => entry.name.clone(),
+                    None => "Agent finished".to_string(),
+                }
+            };
+            let focused = app
+                .get_webview_window("main")
+                .and_then(|window| window.is_focused().ok())
+                .unwrap_or(false);
+            if focused {
+                return;
+            }
+            // Desktop notifications activate the app when clicked; there is no
+            // per-notification click callback to hook on these platforms.
+            let _ = app
+                .notification()
+                .builder()
+                .title(title)
+                .body(preview)
+                .show();
+        });
     }
 }
 
+/// Collapses whitespace and cuts `text` to `max_chars`, ending in an
+/// ellipsis when anything was dropped.
+fn notification_preview(text: &str, max_chars: usize) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= max_chars {
+        return collapsed;
+    }
+    let mut preview: String = collapsed.chars().take(max_chars.saturating_sub(1)).collect();
+    preview.truncate(preview.trim_end().len());
+    preview.push('…');
+    preview
+}
+
 impl EventSink for TauriEventSink {
     fn emit_app_server_event(&self, event: AppServerEvent) {
+        if let Some(preview) = self.track_completion(&event) {
+            self.notify_completion(event.workspace_id.clone(), preview);
+        }
+        if let Some(state) = self.app.try_state::<AppState>() {
+            state.local_api.publish(&event);
+        }
         let _ = self.app.emit("app-server-event", event);
     }
 
     fn emit_terminal_output(&self, event: TerminalOutput) {
         let _ = self.app.emit("terminal-output", event);
     }
+
+    fn emit_terminal_exit(&self, event: TerminalExit) {
+        let _ = self.app.emit("terminal-closed", event);
+    }
+
+    fn emit_session_exited(&self, event: SessionExited) {
+        let _ = self.app.emit("session-exited", event);
+    }
+
+    fn emit_workspace_files_changed(&self, event: WorkspaceFilesChanged) {
+        let _ = self.app.emit("workspace-files-changed", event);
+    }
+
+    fn emit_git_status_changed(&self, event: GitStatusChanged) {
+        let _ = self.app.emit("git-status-changed", event);
+    }
+
+    fn emit_turn_progress(&self, event: TurnProgress) {
+        let _ = self.app.emit("turn-progress", event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::notification_preview;
+
+    #[test]
+    fn notification_preview_collapses_and_truncates() {
+        assert_eq!(notification_preview("  done\n\nall  good ", 40), "done all good");
+        assert_eq!(notification_preview("abcdef ghij", 8), "abcdef…");
+        assert_eq!(notification_preview("héllo wörld", 6), "héllo…");
+        assert_eq!(notification_preview("", 10), "");
+    }
 }
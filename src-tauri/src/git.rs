@@ -1,8 +1,14 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
 
 use git2::{BranchType, DiffOptions, Repository, Sort, Status, StatusOptions};
-use serde_json::json;
-use tauri::State;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::AsyncReadExt;
 use tokio::process::Command;
 
 use crate::git_utils::{
@@ -11,22 +17,157 @@ use crate::git_utils::{
 };
 use crate::state::AppState;
 use crate::types::{
-    BranchInfo, GitFileDiff, GitFileStatus, GitHubIssue, GitHubIssuesResponse,
-    GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff,
-    GitHubPullRequestsResponse, GitLogResponse,
+    BranchInfo, GitBlameLine, GitBlameResponse, GitConflictFile, GitConflictRange, GitDiffStats,
+    GitFileDiff, GitFileStatus, GitHubIssue, GitHubIssuesResponse, GitHubPullRequest,
+    GitHubPullRequestComment, GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse,
+    GitProgressEvent, GitPushResponse, GitSyncResponse,
 };
-use crate::utils::normalize_git_path;
+use crate::utils::{normalize_git_path, now_ms};
+use crate::workspace_files::resolve_workspace_file;
 
-async fn run_git_command(repo_root: &Path, args: &[&str]) -> Result<(), String> {
-    let output = Command::new("git")
+const GITHUB_CACHE_ISSUES: &str = "issues";
+const GITHUB_CACHE_PULL_REQUESTS: &str = "pullRequests";
+const MAX_BLAME_LINES: usize = 5_000;
+
+pub(crate) struct GitHubCacheEntry {
+    repo_name: String,
+    fetched_at_ms: i64,
+    payload: Value,
+}
+
+async fn read_github_cache<T: DeserializeOwned>(
+    state: &AppState,
+    workspace_id: &str,
+    kind: &str,
+    repo_name: &str,
+) -> Option<T> {
+    let ttl_secs = state.app_settings.lock().await.github_cache_ttl_secs;
+    if ttl_secs == 0 {
+        return None;
+    }
+    let cache = state.github_cache.lock().await;
+    let entry = cache.get(&(workspace_id.to_string(), kind.to_string()))?;
+    // A different repo means the remote changed since the entry was written.
+    if entry.repo_name != repo_name {
+        return None;
+    }
+    let age_ms = now_ms().saturating_sub(entry.fetched_at_ms);
+    if age_ms < 0 || age_ms as u64 >= ttl_secs.saturating_mul(1000) {
+        return None;
+    }
+    serde_json::from_value(entry.payload.clone()).ok()
+}
+
+async fn write_github_cache<T: Serialize>(
+    state: &AppState,
+    workspace_id: &str,
+    kind: &str,
+    repo_name: &str,
+    fetched_at_ms: i64,
+    response: &T,
+) {
+    let Ok(payload) = serde_json::to_value(response) else {
+        return;
+    };
+    state.github_cache.lock().await.insert(
+        (workspace_id.to_string(), kind.to_string()),
+        GitHubCacheEntry {
+            repo_name: repo_name.to_string(),
+            fetched_at_ms,
+            payload,
+        },
+    );
+}
+
+async fn invalidate_github_cache(state: &AppState, workspace_id: &str) {
+    state
+        .github_cache
+        .lock()
+        .await
+        .retain(|(cached_workspace_id, _), _| cached_workspace_id != workspace_id);
+}
+
+async fn run_git_command(
+    repo_root: &Path,
+    args: &[&str],
+    timeout: Duration,
+) -> Result<(), String> {
+    run_git_command_output(repo_root, args, timeout).await.map(|_| ())
+}
+
+/// Prefix on errors from git commands killed for running past their
+/// deadline, so callers can tell a hang apart from a git failure.
+const GIT_TIMEOUT_PREFIX: &str = "Timeout:";
+
+/// The user's deadline for git commands, from `gitTimeoutSecs`.
+async fn git_timeout(state: &AppState) -> Duration {
+    Duration::from_secs(state.app_settings.lock().await.git_timeout_secs)
+}
+
+/// Builds a non-interactive git command: no terminal or askpass credential
+/// prompts, no stdin, and the child is killed if its handle is dropped.
+fn git_command(repo_root: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new("git");
+    command
         .args(args)
         .current_dir(repo_root)
-        .output()
-        .await
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GIT_ASKPASS", "false")
+        .env("GCM_INTERACTIVE", "never")
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    command
+}
+
+fn git_operation<'a>(args: &[&'a str]) -> &'a str {
+    args.iter()
+        .find(|arg| !arg.starts_with('-'))
+        .copied()
+        .unwrap_or("command")
+}
+
+fn git_timeout_error(args: &[&str], timeout: Duration) -> String {
+    format!(
+        "{GIT_TIMEOUT_PREFIX} git {} did not finish within {}s.",
+        git_operation(args),
+        timeout.as_secs()
+    )
+}
+
+fn git_stall_error(args: &[&str], timeout: Duration) -> String {
+    format!(
+        "{GIT_TIMEOUT_PREFIX} git {} made no progress for {}s.",
+        git_operation(args),
+        timeout.as_secs()
+    )
+}
+
+async fn run_git_with_timeout(
+    repo_root: &Path,
+    args: &[&str],
+    timeout: Duration,
+) -> Result<std::process::Output, String> {
+    let child = git_command(repo_root, args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .map_err(|e| format!("Failed to run git: {e}"))?;
+    // Dropping the future on timeout drops the child, which kills it.
+    match tokio::time::timeout(timeout, child.wait_with_output()).await {
+        Ok(output) => output.map_err(|e| format!("Failed to run git: {e}")),
+        Err(_) => Err(git_timeout_error(args, timeout)),
+    }
+}
+
+async fn run_git_command_output(
+    repo_root: &Path,
+    args: &[&str],
+    timeout: Duration,
+) -> Result<Vec<u8>, String> {
+    let output = run_git_with_timeout(repo_root, args, timeout).await?;
 
     if output.status.success() {
-        return Ok(());
+        return Ok(output.stdout);
     }
 
     let stderr = String::from_utf8_lossy(&output.stderr);
@@ -42,6 +183,273 @@ async fn run_git_command(repo_root: &Path, args: &[&str]) -> Result<(), String>
     Err(detail.to_string())
 }
 
+fn is_git_auth_error(detail: &str) -> bool {
+    let lower = detail.to_ascii_lowercase();
+    [
+        "authentication failed",
+        "could not read username",
+        "could not read password",
+        "terminal prompts disabled",
+        "permission denied (publickey",
+        "host key verification failed",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle))
+}
+
+/// Splits git's stderr into lines. Progress meters redraw with `\r`, so both
+/// `\r` and `\n` end a line; an unterminated tail stays in `pending`.
+fn drain_progress_lines(pending: &mut Vec<u8>, chunk: &[u8]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for byte in chunk {
+        if *byte == b'\r' || *byte == b'\n' {
+            let line = String::from_utf8_lossy(pending).trim().to_string();
+            pending.clear();
+            if !line.is_empty() {
+                lines.push(line);
+            }
+        } else {
+            pending.push(*byte);
+        }
+    }
+    lines
+}
+
+/// Runs a network git command, forwarding its progress output as
+/// `git-progress` events. Credential prompts are disabled so a remote that
+/// needs auth fails fast instead of hanging on input nobody can provide.
+/// Large transfers can take any amount of time, so the command is only
+/// killed once it prints nothing for `stall_timeout`.
+async fn run_git_with_progress(
+    app: &AppHandle,
+    workspace_id: &str,
+    operation: &str,
+    repo_root: &Path,
+    args: &[&str],
+    stall_timeout: Duration,
+) -> Result<(), String> {
+    let mut child = git_command(repo_root, args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {e}"))?;
+    let stdout = child.stdout.take();
+    let stdout_task = tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        if let Some(mut stdout) = stdout {
+            let _ = stdout.read_to_end(&mut buffer).await;
+        }
+        buffer
+    });
+    let mut stderr = child.stderr.take().ok_or("Failed to capture git output.")?;
+
+    let mut lines = Vec::new();
+    let mut pending = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let finished = loop {
+        let read = match tokio::time::timeout(stall_timeout, stderr.read(&mut chunk)).await {
+            Ok(read) => read.map_err(|e| e.to_string())?,
+            Err(_) => break false,
+        };
+        let drained = if read == 0 {
+            drain_progress_lines(&mut pending, b"\n")
+        } else {
+            drain_progress_lines(&mut pending, &chunk[..read])
+        };
+        for line in drained {
+            let _ = app.emit(
+                "git-progress",
+                GitProgressEvent {
+                    workspace_id: workspace_id.to_string(),
+                    operation: operation.to_string(),
+                    line: line.clone(),
+                },
+            );
+            lines.push(line);
+        }
+        if read == 0 {
+            break true;
+        }
+    };
+    let status = if finished {
+        tokio::time::timeout(stall_timeout, child.wait()).await.ok()
+    } else {
+        None
+    };
+    let Some(status) = status else {
+        let _ = child.kill().await;
+        return Err(git_stall_error(args, stall_timeout));
+    };
+    let status = status.map_err(|e| format!("Failed to run git: {e}"))?;
+    let stdout = stdout_task.await.unwrap_or_default();
+    if status.success() {
+        return Ok(());
+    }
+    // Progress meters are noise in an error message; keep the rest.
+    let detail = lines
+        .iter()
+        .filter(|line| !line.contains('%'))
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let detail = if detail.trim().is_empty() {
+        String::from_utf8_lossy(&stdout).trim().to_string()
+    } else {
+        detail
+    };
+    if is_git_auth_error(&detail) {
+        return Err(format!(
+            "Authentication required for this remote. Configure a credential helper or SSH \
+             key and try again.\n{detail}"
+        ));
+    }
+    if detail.is_empty() {
+        return Err("Git command failed.".to_string());
+    }
+    Err(detail)
+}
+
+/// Prefix on push errors caused by the remote having commits the local branch
+/// lacks, so the UI can offer to pull instead of showing raw git output.
+const PUSH_REJECTED_PREFIX: &str = "PushRejected:";
+
+fn is_non_fast_forward_error(detail: &str) -> bool {
+    let lower = detail.to_ascii_lowercase();
+    lower.contains("non-fast-forward")
+        || (lower.contains("[rejected]") && lower.contains("fetch first"))
+        || lower.contains("stale info")
+}
+
+const UNMERGED_STATUSES: [&str; 7] = ["DD", "AU", "UD", "UA", "DU", "AA", "UU"];
+
+/// Reads unmerged entries out of `git status --porcelain=v1 -z` output.
+fn parse_unmerged_porcelain_z(output: &[u8]) -> Vec<(String, String)> {
+    let mut files = Vec::new();
+    let mut records = output.split(|byte| *byte == 0);
+    while let Some(record) = records.next() {
+        if record.len() < 4 {
+            continue;
+        }
+        let status = String::from_utf8_lossy(&record[..2]).to_string();
+        let path = String::from_utf8_lossy(&record[3..]).to_string();
+        // Renames and copies carry their source path as a separate record.
+        if status.starts_with('R') || status.starts_with('C') {
+            records.next();
+        }
+        if UNMERGED_STATUSES.contains(&status.as_str()) {
+            files.push((normalize_git_path(&path), status));
+        }
+    }
+    files
+}
+
+/// Returns the 1-based line ranges spanned by `<<<<<<<` ... `>>>>>>>` blocks.
+fn conflict_marker_ranges(text: &str) -> Vec<GitConflictRange> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (index, line) in text.lines().enumerate() {
+        if line.starts_with("<<<<<<<") {
+            start = Some(index + 1);
+        } else if line.starts_with(">>>>>>>") {
+            if let Some(start_line) = start.take() {
+                ranges.push(GitConflictRange {
+                    start_line,
+                    end_line: index + 1,
+                });
+            }
+        }
+    }
+    ranges
+}
+
+/// Parses `git blame --porcelain` output. Commit headers are only printed the
+/// first time a commit appears, so they are remembered for later lines.
+fn parse_blame_porcelain(output: &str, max_lines: usize) -> (Vec<GitBlameLine>, bool) {
+    let mut commits: HashMap<String, (String, i64, String)> = HashMap::new();
+    let mut lines = Vec::new();
+    let mut current: Option<(String, usize)> = None;
+    for raw in output.lines() {
+        if let Some(content) = raw.strip_prefix('\t') {
+            let Some((sha, line)) = current.take() else {
+                continue;
+            };
+            if lines.len() >= max_lines {
+                return (lines, true);
+            }
+            let (author, timestamp, summary) = commits.get(&sha).cloned().unwrap_or_default();
+            lines.push(GitBlameLine {
+                sha,
+                author,
+                timestamp,
+                summary,
+                line,
+                content: content.to_string(),
+            });
+            continue;
+        }
+        match &current {
+            None => {
+                let mut parts = raw.split(' ');
+                let sha = parts.next().unwrap_or_default();
+                let line = parts.nth(1).and_then(|value| value.parse().ok());
+                if let Some(line) = line.filter(|_| sha.len() >= 40) {
+                    commits.entry(sha.to_string()).or_default();
+                    current = Some((sha.to_string(), line));
+                }
+            }
+            Some((sha, _)) => {
+                let Some(commit) = commits.get_mut(sha) else {
+                    continue;
+                };
+                if let Some(author) = raw.strip_prefix("author ") {
+                    commit.0 = author.to_string();
+                } else if let Some(time) = raw.strip_prefix("author-time ") {
+                    commit.1 = time.trim().parse().unwrap_or(0);
+                } else if let Some(summary) = raw.strip_prefix("summary ") {
+                    commit.2 = summary.to_string();
+                }
+            }
+        }
+    }
+    (lines, false)
+}
+
+fn default_remote_name(repo: &Repository) -> Option<String> {
+    let remotes = repo.remotes().ok()?;
+    if remotes.iter().any(|remote| remote == Some("origin")) {
+        return Some("origin".to_string());
+    }
+    remotes.iter().flatten().next().map(|name| name.to_string())
+}
+
+fn read_upstream_status(repo_root: &Path) -> Result<GitSyncResponse, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let mut response = GitSyncResponse {
+        ahead: 0,
+        behind: 0,
+        upstream: None,
+    };
+    let Some(branch_name) = current_branch_name(&repo) else {
+        return Ok(response);
+    };
+    let Ok(branch) = repo.find_branch(&branch_name, BranchType::Local) else {
+        return Ok(response);
+    };
+    let Ok(upstream) = branch.upstream() else {
+        return Ok(response);
+    };
+    response.upstream = upstream.get().shorthand().map(|name| name.to_string());
+    if let (Some(local_oid), Some(upstream_oid)) = (branch.get().target(), upstream.get().target())
+    {
+        let (ahead, behind) = repo
+            .graph_ahead_behind(local_oid, upstream_oid)
+            .map_err(|e| e.to_string())?;
+        response.ahead = ahead;
+        response.behind = behind;
+    }
+    Ok(response)
+}
+
 fn status_for_index(status: Status) -> Option<&'static str> {
     if status.contains(Status::INDEX_NEW) {
         Some("A")
@@ -74,6 +482,113 @@ fn status_for_workdir(status: Status) -> Option<&'static str> {
     }
 }
 
+fn parse_numstat_z(output: &[u8]) -> Vec<(String, i64, i64)> {
+    let mut results = Vec::new();
+    let mut fields = output.split(|byte| *byte == 0);
+    while let Some(field) = fields.next() {
+        if field.is_empty() {
+            continue;
+        }
+        let text = String::from_utf8_lossy(field);
+        let mut parts = text.splitn(3, '\t');
+        let additions = parts.next().unwrap_or("").parse::<i64>().unwrap_or(0);
+        let deletions = parts.next().unwrap_or("").parse::<i64>().unwrap_or(0);
+        let mut path = parts.next().unwrap_or("").to_string();
+        if path.is_empty() {
+            // Renames and copies carry the old and new paths as separate fields.
+            let _old_path = fields.next();
+            path = fields
+                .next()
+                .map(|value| String::from_utf8_lossy(value).to_string())
+                .unwrap_or_default();
+        }
+        if path.is_empty() {
+            continue;
+        }
+        results.push((normalize_git_path(&path), additions, deletions));
+    }
+    results
+}
+
+fn parse_name_status_z(output: &[u8]) -> HashMap<String, String> {
+    let mut results = HashMap::new();
+    let mut fields = output.split(|byte| *byte == 0);
+    while let Some(field) = fields.next() {
+        if field.is_empty() {
+            continue;
+        }
+        let status = String::from_utf8_lossy(field).to_string();
+        let Some(code) = status.chars().next() else {
+            continue;
+        };
+        if matches!(code, 'R' | 'C') {
+            let _old_path = fields.next();
+        }
+        let Some(path) = fields.next() else {
+            break;
+        };
+        let path = normalize_git_path(&String::from_utf8_lossy(path));
+        results.insert(path, code.to_string());
+    }
+    results
+}
+
+async fn diff_stats_from_numstat(
+    repo_root: &Path,
+    staged: bool,
+    timeout: Duration,
+) -> Result<Vec<GitFileStatus>, String> {
+    let mut numstat_args = vec!["diff", "--numstat", "-z", "--no-color"];
+    let mut name_status_args = vec!["diff", "--name-status", "-z", "--no-color"];
+    if staged {
+        numstat_args.push("--cached");
+        name_status_args.push("--cached");
+    }
+    let numstat = run_git_command_output(repo_root, &numstat_args, timeout).await?;
+    let name_status = run_git_command_output(repo_root, &name_status_args, timeout).await?;
+    let statuses = parse_name_status_z(&name_status);
+    Ok(parse_numstat_z(&numstat)
+        .into_iter()
+        .map(|(path, additions, deletions)| GitFileStatus {
+            status: statuses.get(&path).cloned().unwrap_or_else(|| "M".to_string()),
+            path,
+            additions,
+            deletions,
+        })
+        .collect())
+}
+
+fn collect_local_branches(repo: &Repository) -> Result<Vec<BranchInfo>, String> {
+    let mut branches = Vec::new();
+    let refs = repo
+        .branches(Some(BranchType::Local))
+        .map_err(|e| e.to_string())?;
+    for branch_result in refs {
+        let (branch, _) = branch_result.map_err(|e| e.to_string())?;
+        let name = branch.name().ok().flatten().unwrap_or("").to_string();
+        if name.is_empty() {
+            continue;
+        }
+        let last_commit = branch
+            .get()
+            .target()
+            .and_then(|oid| repo.find_commit(oid).ok())
+            .map(|commit| commit.time().seconds())
+            .unwrap_or(0);
+        branches.push(BranchInfo { name, last_commit });
+    }
+    branches.sort_by(|a, b| b.last_commit.cmp(&a.last_commit));
+    Ok(branches)
+}
+
+fn current_branch_name(repo: &Repository) -> Option<String> {
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    head.shorthand().map(|name| name.to_string())
+}
+
 fn github_repo_from_path(path: &Path) -> Result<String, String> {
     let repo = Repository::open(path).map_err(|e| e.to_string())?;
     let remotes = repo.remotes().map_err(|e| e.to_string())?;
@@ -97,6 +612,194 @@ fn github_repo_from_path(path: &Path) -> Result<String, String> {
     parse_github_repo(remote_url).ok_or("Remote is not a GitHub repository.".to_string())
 }
 
+const REMOTE_RETRY_ATTEMPTS: u32 = 3;
+const REMOTE_RETRY_BASE_DELAY_MS: u64 = 500;
+/// Longest wait for a GitHub rate limit to reset before a read gives up and
+/// reports the reset time instead.
+const RATE_LIMIT_MAX_WAIT: Duration = Duration::from_secs(60);
+
+/// Errors worth another try: server-side failures and network timeouts.
+/// Auth failures never are, however the message is worded, and rate limits
+/// wait for their reset instead. Neither are our own deadlines: a command
+/// that ran out its time would most likely do so again.
+fn is_transient_remote_error(detail: &str) -> bool {
+    if detail.starts_with(GIT_TIMEOUT_PREFIX)
+        || is_gh_auth_error(detail)
+        || is_git_auth_error(detail)
+    {
+        return false;
+    }
+    let lower = detail.to_ascii_lowercase();
+    let server_error = (500..600).any(|code| {
+        lower.contains(&format!("http {code}")) || lower.contains(&format!("error: {code}"))
+    });
+    server_error
+        || [
+            "timed out",
+            "timeout",
+            "connection reset",
+            "bad gateway",
+            "service unavailable",
+        ]
+        .iter()
+        .any(|needle| lower.contains(needle))
+}
+
+/// Runs a remote operation up to `REMOTE_RETRY_ATTEMPTS` times, doubling the
+/// delay between attempts. Only transient errors are retried; the final error
+/// says how many attempts were made.
+async fn retry_remote<T, F, Fut>(mut operation: F) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, String>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt < REMOTE_RETRY_ATTEMPTS && is_transient_remote_error(&err) => {
+                let delay = REMOTE_RETRY_BASE_DELAY_MS << (attempt - 1);
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                attempt += 1;
+            }
+            Err(err) if attempt > 1 => {
+                return Err(format!("{err}\n(failed after {attempt} attempts)"));
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn is_gh_auth_error(detail: &str) -> bool {
+    let lower = detail.to_ascii_lowercase();
+    lower.contains("gh auth login")
+        || lower.contains("not logged into")
+        || lower.contains("authentication required")
+        || lower.contains("http 401")
+}
+
+fn gh_command_error(output: &std::process::Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let detail = if stderr.trim().is_empty() {
+        stdout.trim()
+    } else {
+        stderr.trim()
+    };
+    if detail.is_empty() {
+        return "GitHub CLI command failed.".to_string();
+    }
+    if is_gh_auth_error(detail) {
+        return "GitHub CLI is not authenticated. Run `gh auth login` and try again.".to_string();
+    }
+    detail.to_string()
+}
+
+/// Runs `gh` once and returns its stdout, or the cleaned-up error output.
+async fn run_gh_command_once(repo_root: &Path, args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = Command::new("gh")
+        .args(args)
+        .current_dir(repo_root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if !output.status.success() {
+        return Err(gh_command_error(&output));
+    }
+    Ok(output.stdout)
+}
+
+fn is_rate_limit_error(detail: &str) -> bool {
+    detail.to_ascii_lowercase().contains("rate limit")
+}
+
+/// How long until the exhausted GitHub rate limit resets. `gh` does not
+/// print the reset headers, so this asks the `rate_limit` endpoint, which is
+/// not itself rate limited.
+async fn github_rate_limit_wait(repo_root: &Path) -> Option<Duration> {
+    let output = run_gh_command_once(
+        repo_root,
+        &[
+            "api",
+            "rate_limit",
+            "--jq",
+            "[.resources[] | select(.remaining == 0) | .reset] | max",
+        ],
+    )
+    .await
+    .ok()?;
+    let reset_secs: i64 = String::from_utf8_lossy(&output).trim().parse().ok()?;
+    let wait_secs = reset_secs.saturating_sub(now_ms() / 1000).max(1);
+    Some(Duration::from_secs(wait_secs as u64))
+}
+
+fn rate_limit_error(detail: &str, wait: Option<Duration>) -> String {
+    match wait {
+        Some(wait) => format!(
+            "{detail}\nThe GitHub rate limit resets in about {} min.",
+            wait.as_secs().div_ceil(60)
+        ),
+        None => detail.to_string(),
+    }
+}
+
+/// Runs a read-only `gh` command, retrying transient failures. A rate limit
+/// that resets soon is waited out once; a later one is reported with its
+/// reset time. Commands that create anything must use `run_gh_command_once`
+/// so a retry cannot post twice.
+async fn run_gh_command(repo_root: &Path, args: &[&str]) -> Result<Vec<u8>, String> {
+    let err = match retry_remote(|| run_gh_command_once(repo_root, args)).await {
+        Err(err) if is_rate_limit_error(&err) => err,
+        result => return result,
+    };
+    match github_rate_limit_wait(repo_root).await {
+        Some(wait) if wait <= RATE_LIMIT_MAX_WAIT => {
+            tokio::time::sleep(wait).await;
+            run_gh_command_once(repo_root, args).await
+        }
+        wait => Err(rate_limit_error(&err, wait)),
+    }
+}
+
+async fn post_github_comment(
+    repo_root: &Path,
+    repo_name: &str,
+    kind: &str,
+    number: u64,
+    body: &str,
+) -> Result<String, String> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err("Comment body is required.".to_string());
+    }
+    // Posting is not idempotent: a failure after GitHub stored the comment
+    // would duplicate it on retry, so this runs once.
+    let output = match run_gh_command_once(
+        repo_root,
+        &[kind, "comment", &number.to_string(), "--repo", repo_name, "--body", body],
+    )
+    .await
+    {
+        Ok(output) => output,
+        Err(err) if is_rate_limit_error(&err) => {
+            let wait = github_rate_limit_wait(repo_root).await;
+            return Err(rate_limit_error(&err, wait));
+        }
+        Err(err) => return Err(err),
+    };
+
+    let url = String::from_utf8_lossy(&output)
+        .lines()
+        .map(|line| line.trim())
+        .rfind(|line| line.starts_with("https://"))
+        .unwrap_or("")
+        .to_string();
+    if url.is_empty() {
+        return Err("GitHub CLI did not return a comment URL.".to_string());
+    }
+    Ok(url)
+}
+
 fn parse_pr_diff(diff: &str) -> Vec<GitHubPullRequestDiff> {
     let mut entries = Vec::new();
     let mut current_lines: Vec<&str> = Vec::new();
@@ -316,6 +1019,7 @@ pub(crate) async fn stage_git_file(
     path: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let timeout = git_timeout(&state).await;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -323,7 +1027,7 @@ pub(crate) async fn stage_git_file(
         .clone();
 
     let repo_root = resolve_git_root(&entry)?;
-    run_git_command(&repo_root, &["add", "--", &path]).await
+    run_git_command(&repo_root, &["add", "--", &path], timeout).await
 }
 
 #[tauri::command]
@@ -332,6 +1036,7 @@ pub(crate) async fn unstage_git_file(
     path: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let timeout = git_timeout(&state).await;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -339,7 +1044,7 @@ pub(crate) async fn unstage_git_file(
         .clone();
 
     let repo_root = resolve_git_root(&entry)?;
-    run_git_command(&repo_root, &["restore", "--staged", "--", &path]).await
+    run_git_command(&repo_root, &["restore", "--staged", "--", &path], timeout).await
 }
 
 #[tauri::command]
@@ -348,6 +1053,7 @@ pub(crate) async fn revert_git_file(
     path: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let timeout = git_timeout(&state).await;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
@@ -355,13 +1061,13 @@ pub(crate) async fn revert_git_file(
         .clone();
 
     let repo_root = resolve_git_root(&entry)?;
-    if run_git_command(&repo_root, &["restore", "--staged", "--worktree", "--", &path])
+    if run_git_command(&repo_root, &["restore", "--staged", "--worktree", "--", &path], timeout)
         .await
         .is_ok()
     {
         return Ok(());
     }
-    run_git_command(&repo_root, &["clean", "-f", "--", &path]).await
+    run_git_command(&repo_root, &["clean", "-f", "--", &path], timeout).await
 }
 
 #[tauri::command]
@@ -369,13 +1075,15 @@ pub(crate) async fn revert_git_all(
     workspace_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let timeout = git_timeout(&state).await;
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?;
     let repo_root = resolve_git_root(entry)?;
-    run_git_command(&repo_root, &["restore", "--staged", "--worktree", "--", "."]).await?;
-    run_git_command(&repo_root, &["clean", "-f", "-d"]).await
+    let args = ["restore", "--staged", "--worktree", "--", "."];
+    run_git_command(&repo_root, &args, timeout).await?;
+    run_git_command(&repo_root, &["clean", "-f", "-d"], timeout).await
 }
 
 #[tauri::command]
@@ -398,6 +1106,8 @@ pub(crate) async fn list_git_roots(
 #[tauri::command]
 pub(crate) async fn get_git_diffs(
     workspace_id: String,
+    paths: Option<Vec<String>>,
+    context_lines: Option<u32>,
     state: State<'_, AppState>,
 ) -> Result<Vec<GitFileDiff>, String> {
     let workspaces = state.workspaces.lock().await;
@@ -418,6 +1128,23 @@ pub(crate) async fn get_git_diffs(
         .include_untracked(true)
         .recurse_untracked_dirs(true)
         .show_untracked_content(true);
+    if let Some(context_lines) = context_lines {
+        options.context_lines(context_lines);
+    }
+    if let Some(paths) = paths.as_ref() {
+        let paths: Vec<&str> = paths
+            .iter()
+            .map(|path| path.trim())
+            .filter(|path| !path.is_empty())
+            .collect();
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
+        options.disable_pathspec_match(true);
+        for path in paths {
+            options.pathspec(path);
+        }
+    }
 
     let diff = match head_tree.as_ref() {
         Some(tree) => repo
@@ -460,6 +1187,47 @@ pub(crate) async fn get_git_diffs(
     Ok(results)
 }
 
+#[tauri::command]
+pub(crate) async fn git_diff_stats(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitDiffStats, String> {
+    let timeout = git_timeout(&state).await;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let staged_files = diff_stats_from_numstat(&repo_root, true, timeout).await?;
+    let unstaged_files = diff_stats_from_numstat(&repo_root, false, timeout).await?;
+
+    let mut files: Vec<GitFileStatus> = Vec::new();
+    for file in staged_files.iter().chain(unstaged_files.iter()) {
+        match files.iter_mut().find(|existing| existing.path == file.path) {
+            Some(existing) => {
+                existing.additions += file.additions;
+                existing.deletions += file.deletions;
+                existing.status = file.status.clone();
+            }
+            None => files.push(file.clone()),
+        }
+    }
+    let total_additions = files.iter().map(|file| file.additions).sum();
+    let total_deletions = files.iter().map(|file| file.deletions).sum();
+
+    Ok(GitDiffStats {
+        files,
+        staged_files,
+        unstaged_files,
+        total_additions,
+        total_deletions,
+    })
+}
+
 #[tauri::command]
 pub(crate) async fn get_git_log(
     workspace_id: String,
@@ -581,20 +1349,9 @@ pub(crate) async fn get_git_remote(
 
     let repo_root = resolve_git_root(&entry)?;
     let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    let remotes = repo.remotes().map_err(|e| e.to_string())?;
-    let name = if remotes.iter().any(|remote| remote == Some("origin")) {
-        "origin".to_string()
-    } else {
-        remotes
-            .iter()
-            .flatten()
-            .next()
-            .unwrap_or("")
-            .to_string()
-    };
-    if name.is_empty() {
+    let Some(name) = default_remote_name(&repo) else {
         return Ok(None);
-    }
+    };
     let remote = repo.find_remote(&name).map_err(|e| e.to_string())?;
     Ok(remote.url().map(|url| url.to_string()))
 }
@@ -602,19 +1359,30 @@ pub(crate) async fn get_git_remote(
 #[tauri::command]
 pub(crate) async fn get_github_issues(
     workspace_id: String,
+    force_refresh: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<GitHubIssuesResponse, String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?
-        .clone();
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
 
     let repo_root = resolve_git_root(&entry)?;
     let repo_name = github_repo_from_path(&repo_root)?;
+    if !force_refresh.unwrap_or(false) {
+        if let Some(cached) =
+            read_github_cache(&state, &workspace_id, GITHUB_CACHE_ISSUES, &repo_name).await
+        {
+            return Ok(cached);
+        }
+    }
 
-    let output = Command::new("gh")
-        .args([
+    let output = run_gh_command(
+        &repo_root,
+        &[
             "issue",
             "list",
             "--repo",
@@ -623,28 +1391,11 @@ pub(crate) async fn get_github_issues(
             "50",
             "--json",
             "number,title,url,updatedAt",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+        ],
+    )
+    .await?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
-    }
-
-    let issues: Vec<GitHubIssue> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    let issues: Vec<GitHubIssue> = serde_json::from_slice(&output).map_err(|e| e.to_string())?;
 
     let search_query = format!("repo:{repo_name} is:issue is:open");
     let search_query = search_query.replace(' ', "+");
@@ -666,25 +1417,51 @@ pub(crate) async fn get_github_issues(
         _ => issues.len(),
     };
 
-    Ok(GitHubIssuesResponse { total, issues })
+    let response = GitHubIssuesResponse {
+        total,
+        issues,
+        fetched_at_ms: now_ms(),
+    };
+    write_github_cache(
+        &state,
+        &workspace_id,
+        GITHUB_CACHE_ISSUES,
+        &repo_name,
+        response.fetched_at_ms,
+        &response,
+    )
+    .await;
+    Ok(response)
 }
 
 #[tauri::command]
 pub(crate) async fn get_github_pull_requests(
     workspace_id: String,
+    force_refresh: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<GitHubPullRequestsResponse, String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?
-        .clone();
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
 
     let repo_root = resolve_git_root(&entry)?;
     let repo_name = github_repo_from_path(&repo_root)?;
+    if !force_refresh.unwrap_or(false) {
+        if let Some(cached) =
+            read_github_cache(&state, &workspace_id, GITHUB_CACHE_PULL_REQUESTS, &repo_name)
+                .await
+        {
+            return Ok(cached);
+        }
+    }
 
-    let output = Command::new("gh")
-        .args([
+    let output = run_gh_command(
+        &repo_root,
+        &[
             "pr",
             "list",
             "--repo",
@@ -695,28 +1472,12 @@ pub(crate) async fn get_github_pull_requests(
             "50",
             "--json",
             "number,title,url,updatedAt,createdAt,body,headRefName,baseRefName,isDraft,author",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
-    }
+        ],
+    )
+    .await?;
 
     let pull_requests: Vec<GitHubPullRequest> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+        serde_json::from_slice(&output).map_err(|e| e.to_string())?;
 
     let search_query = format!("repo:{repo_name} is:pr is:open");
     let search_query = search_query.replace(' ', "+");
@@ -738,10 +1499,21 @@ pub(crate) async fn get_github_pull_requests(
         _ => pull_requests.len(),
     };
 
-    Ok(GitHubPullRequestsResponse {
+    let response = GitHubPullRequestsResponse {
         total,
         pull_requests,
-    })
+        fetched_at_ms: now_ms(),
+    };
+    write_github_cache(
+        &state,
+        &workspace_id,
+        GITHUB_CACHE_PULL_REQUESTS,
+        &repo_name,
+        response.fetched_at_ms,
+        &response,
+    )
+    .await;
+    Ok(response)
 }
 
 #[tauri::command]
@@ -759,8 +1531,9 @@ pub(crate) async fn get_github_pull_request_diff(
     let repo_root = resolve_git_root(&entry)?;
     let repo_name = github_repo_from_path(&repo_root)?;
 
-    let output = Command::new("gh")
-        .args([
+    let output = run_gh_command(
+        &repo_root,
+        &[
             "pr",
             "diff",
             &pr_number.to_string(),
@@ -768,27 +1541,11 @@ pub(crate) async fn get_github_pull_request_diff(
             &repo_name,
             "--color",
             "never",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+        ],
+    )
+    .await?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
-    }
-
-    let diff_text = String::from_utf8_lossy(&output.stdout);
+    let diff_text = String::from_utf8_lossy(&output);
     Ok(parse_pr_diff(&diff_text))
 }
 
@@ -811,29 +1568,11 @@ pub(crate) async fn get_github_pull_request_comments(
         format!("/repos/{repo_name}/issues/{pr_number}/comments?per_page=30");
     let jq_filter = r#"[.[] | {id, body, createdAt: .created_at, url: .html_url, author: (if .user then {login: .user.login} else null end)}]"#;
 
-    let output = Command::new("gh")
-        .args(["api", &comments_endpoint, "--jq", jq_filter])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
-    }
+    let output =
+        run_gh_command(&repo_root, &["api", &comments_endpoint, "--jq", jq_filter]).await?;
 
     let comments: Vec<GitHubPullRequestComment> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+        serde_json::from_slice(&output).map_err(|e| e.to_string())?;
 
     Ok(comments)
 }
@@ -850,25 +1589,7 @@ pub(crate) async fn list_git_branches(
         .clone();
     let repo_root = resolve_git_root(&entry)?;
     let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    let mut branches = Vec::new();
-    let refs = repo
-        .branches(Some(BranchType::Local))
-        .map_err(|e| e.to_string())?;
-    for branch_result in refs {
-        let (branch, _) = branch_result.map_err(|e| e.to_string())?;
-        let name = branch.name().ok().flatten().unwrap_or("").to_string();
-        if name.is_empty() {
-            continue;
-        }
-        let last_commit = branch
-            .get()
-            .target()
-            .and_then(|oid| repo.find_commit(oid).ok())
-            .map(|commit| commit.time().seconds())
-            .unwrap_or(0);
-        branches.push(BranchInfo { name, last_commit });
-    }
-    branches.sort_by(|a, b| b.last_commit.cmp(&a.last_commit));
+    let branches = collect_local_branches(&repo)?;
     Ok(json!({ "branches": branches }))
 }
 
@@ -907,3 +1628,515 @@ pub(crate) async fn create_git_branch(
         .map_err(|e| e.to_string())?;
     checkout_branch(&repo, &name).map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub(crate) async fn post_github_pr_comment(
+    workspace_id: String,
+    pr_number: u64,
+    body: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let url = post_github_comment(&repo_root, &repo_name, "pr", pr_number, &body).await?;
+    invalidate_github_cache(&state, &workspace_id).await;
+    Ok(url)
+}
+
+#[tauri::command]
+pub(crate) async fn post_github_issue_comment(
+    workspace_id: String,
+    issue_number: u64,
+    body: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let url = post_github_comment(&repo_root, &repo_name, "issue", issue_number, &body).await?;
+    invalidate_github_cache(&state, &workspace_id).await;
+    Ok(url)
+}
+
+#[tauri::command]
+pub(crate) async fn git_fetch(
+    workspace_id: String,
+    remote: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitSyncResponse, String> {
+    let timeout = git_timeout(&state).await;
+    let remote = remote
+        .map(|remote| remote.trim().to_string())
+        .filter(|remote| !remote.is_empty());
+    if remote.as_deref().is_some_and(|remote| remote.starts_with('-')) {
+        return Err("Invalid remote name.".to_string());
+    }
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let mut args = vec!["fetch", "--progress"];
+    if let Some(remote) = remote.as_deref() {
+        args.push(remote);
+    }
+    retry_remote(|| {
+        run_git_with_progress(&app, &workspace_id, "fetch", &repo_root, &args, timeout)
+    })
+    .await?;
+    read_upstream_status(&repo_root)
+}
+
+#[tauri::command]
+pub(crate) async fn git_pull(
+    workspace_id: String,
+    rebase: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitSyncResponse, String> {
+    let timeout = git_timeout(&state).await;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let mode = if rebase { "--rebase" } else { "--no-rebase" };
+    let args = ["pull", "--progress", mode];
+    retry_remote(|| {
+        run_git_with_progress(&app, &workspace_id, "pull", &repo_root, &args, timeout)
+    })
+    .await?;
+    read_upstream_status(&repo_root)
+}
+
+#[tauri::command]
+pub(crate) async fn git_push(
+    workspace_id: String,
+    set_upstream: bool,
+    force_with_lease: bool,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitPushResponse, String> {
+    let timeout = git_timeout(&state).await;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let (branch_name, remote, has_upstream) = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let branch_name = current_branch_name(&repo)
+            .ok_or("Cannot push from a detached HEAD. Check out a branch first.")?;
+        let tracked_remote = repo
+            .branch_upstream_remote(&format!("refs/heads/{branch_name}"))
+            .ok()
+            .and_then(|remote| remote.as_str().map(|name| name.to_string()));
+        match tracked_remote {
+            Some(remote) => (branch_name, remote, true),
+            None if set_upstream => {
+                let remote = default_remote_name(&repo)
+                    .ok_or("This repository has no remotes to push to.")?;
+                (branch_name, remote, false)
+            }
+            None => {
+                return Err(format!(
+                    "Branch `{branch_name}` has no upstream. Push with set upstream to publish it."
+                ))
+            }
+        }
+    };
+
+    // A bare --force is never offered: --force-with-lease refuses to clobber
+    // commits that were pushed since the last fetch.
+    let mut args = vec!["push", "--progress"];
+    if force_with_lease {
+        args.push("--force-with-lease");
+    }
+    if !has_upstream {
+        args.push("--set-upstream");
+        args.push(&remote);
+        args.push(&branch_name);
+    }
+    run_git_with_progress(&app, &workspace_id, "push", &repo_root, &args, timeout)
+        .await
+        .map_err(|error| {
+            if is_non_fast_forward_error(&error) {
+                format!(
+                    "{PUSH_REJECTED_PREFIX} The remote has commits that `{branch_name}` does not. \
+                     Pull first, then push again."
+                )
+            } else {
+                error
+            }
+        })?;
+
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let remote_url = repo
+        .find_remote(&remote)
+        .ok()
+        .and_then(|found| found.url().map(|url| url.to_string()));
+    let upstream = read_upstream_status(&repo_root)?.upstream;
+    Ok(GitPushResponse {
+        remote,
+        remote_url,
+        upstream,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn git_conflicts(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitConflictFile>, String> {
+    let timeout = git_timeout(&state).await;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let output = run_git_command_output(
+        &repo_root,
+        &["status", "--porcelain=v1", "-z", "--untracked-files=no"],
+        timeout,
+    )
+    .await?;
+    let mut conflicts = Vec::new();
+    for (path, status) in parse_unmerged_porcelain_z(&output) {
+        // Deleted sides and binary files have no markers to report.
+        let markers = tokio::fs::read(repo_root.join(&path))
+            .await
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .map(|text| conflict_marker_ranges(&text))
+            .unwrap_or_default();
+        conflicts.push(GitConflictFile {
+            path,
+            status,
+            markers,
+        });
+    }
+    Ok(conflicts)
+}
+
+#[tauri::command]
+pub(crate) async fn git_blame(
+    workspace_id: String,
+    relative_path: String,
+    start_line: Option<u32>,
+    end_line: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<GitBlameResponse, String> {
+    let timeout = git_timeout(&state).await;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    resolve_workspace_file(&repo_root, &relative_path)?;
+    let range = match (start_line, end_line) {
+        (None, None) => None,
+        (start, end) => {
+            let start = start.unwrap_or(1).max(1);
+            let end = end.map(|end| end.to_string()).unwrap_or_default();
+            Some(format!("{start},{end}"))
+        }
+    };
+    let mut args = vec!["blame", "--porcelain"];
+    if let Some(range) = range.as_deref() {
+        args.push("-L");
+        args.push(range);
+    }
+    args.push("--");
+    args.push(relative_path.trim());
+    let output = run_git_command_output(&repo_root, &args, timeout).await?;
+    let (lines, truncated) =
+        parse_blame_porcelain(&String::from_utf8_lossy(&output), MAX_BLAME_LINES);
+    Ok(GitBlameResponse { lines, truncated })
+}
+
+#[tauri::command]
+pub(crate) async fn git_abort_merge(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let timeout = git_timeout(&state).await;
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    run_git_command(&repo_root, &["merge", "--abort"], timeout).await
+}
+
+#[tauri::command]
+pub(crate) async fn delete_git_branch(
+    workspace_id: String,
+    name: String,
+    force: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<BranchInfo>, String> {
+    let timeout = git_timeout(&state).await;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Branch name is required.".to_string());
+    }
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        if current_branch_name(&repo).as_deref() == Some(name.as_str()) {
+            return Err(format!(
+                "Cannot delete `{name}` because it is the currently checked-out branch."
+            ));
+        }
+        let branch = repo
+            .find_branch(&name, BranchType::Local)
+            .map_err(|_| format!("Branch `{name}` not found."))?;
+        if !force {
+            if let Ok(upstream) = branch.upstream() {
+                if let (Some(local_oid), Some(upstream_oid)) =
+                    (branch.get().target(), upstream.get().target())
+                {
+                    let (ahead, _) = repo
+                        .graph_ahead_behind(local_oid, upstream_oid)
+                        .map_err(|e| e.to_string())?;
+                    if ahead > 0 {
+                        return Err(format!(
+                            "Branch `{name}` has {ahead} commit(s) not pushed to its upstream. Use force to delete it anyway."
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    let flag = if force { "-D" } else { "-d" };
+    run_git_command(&repo_root, &["branch", flag, "--", &name], timeout).await?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    collect_local_branches(&repo)
+}
+
+#[tauri::command]
+pub(crate) async fn rename_git_branch(
+    workspace_id: String,
+    old: String,
+    new: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<BranchInfo>, String> {
+    let timeout = git_timeout(&state).await;
+    let old = old.trim().to_string();
+    let new = new.trim().to_string();
+    if old.is_empty() || new.is_empty() {
+        return Err("Branch name is required.".to_string());
+    }
+    if old == new {
+        return Err("New branch name must differ from the current name.".to_string());
+    }
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?
+            .clone()
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    run_git_command(&repo_root, &["branch", "-m", "--", &old, &new], timeout).await?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    collect_local_branches(&repo)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        conflict_marker_ranges, drain_progress_lines, git_stall_error, is_git_auth_error,
+        is_non_fast_forward_error, is_rate_limit_error, is_transient_remote_error,
+        parse_blame_porcelain, parse_name_status_z, parse_numstat_z, parse_unmerged_porcelain_z,
+        rate_limit_error,
+    };
+    use crate::types::GitConflictRange;
+
+    #[test]
+    fn parse_blame_porcelain_reuses_commit_headers() {
+        let sha_a = "a".repeat(40);
+        let sha_b = "b".repeat(40);
+        let output = format!(
+            "{sha_a} 1 1 2\nauthor Ada\nauthor-time 100\nsummary First\nfilename f.rs\n\tone\n\
+             {sha_a} 2 2\n\ttwo\n\
+             {sha_b} 3 3 1\nauthor Codex\nauthor-time 200\nsummary Agent edit\n\
+             filename f.rs\n\tthree\n"
+        );
+        let (lines, truncated) = parse_blame_porcelain(&output, 10);
+        assert!(!truncated);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].sha, sha_a);
+        assert_eq!(lines[1].author, "Ada");
+        assert_eq!(lines[1].line, 2);
+        assert_eq!(lines[1].content, "two");
+        assert_eq!(lines[2].summary, "Agent edit");
+        assert_eq!(lines[2].timestamp, 200);
+
+        let (lines, truncated) = parse_blame_porcelain(&output, 2);
+        assert!(truncated);
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn parse_unmerged_porcelain_z_keeps_only_conflicts() {
+        let output = b"UU src/main.rs\0M  staged.rs\0R  new.rs\0old.rs\0AA both.txt\0DU gone.rs\0";
+        let parsed = parse_unmerged_porcelain_z(output);
+        assert_eq!(
+            parsed,
+            vec![
+                ("src/main.rs".to_string(), "UU".to_string()),
+                ("both.txt".to_string(), "AA".to_string()),
+                ("gone.rs".to_string(), "DU".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn conflict_marker_ranges_reports_each_block() {
+        let text = concat!(
+            "a\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> branch\n",
+            "b\n<<<<<<< HEAD\nx\n=======\ny\n>>>>>>> other\n",
+        );
+        assert_eq!(
+            conflict_marker_ranges(text),
+            vec![
+                GitConflictRange {
+                    start_line: 2,
+                    end_line: 6,
+                },
+                GitConflictRange {
+                    start_line: 8,
+                    end_line: 12,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn is_non_fast_forward_error_matches_rejected_pushes() {
+        assert!(is_non_fast_forward_error(
+            " ! [rejected]        main -> main (non-fast-forward)"
+        ));
+        assert!(is_non_fast_forward_error(" ! [rejected]        main -> main (fetch first)"));
+        assert!(is_non_fast_forward_error(" ! [rejected]        main -> main (stale info)"));
+        assert!(!is_non_fast_forward_error("remote: Permission to repo denied."));
+    }
+
+    #[test]
+    fn drain_progress_lines_splits_on_carriage_returns() {
+        let mut pending = Vec::new();
+        let lines = drain_progress_lines(&mut pending, b"Receiving objects:  50%\rReceiving obj");
+        assert_eq!(lines, vec!["Receiving objects:  50%".to_string()]);
+        let lines = drain_progress_lines(&mut pending, b"ects: 100%\r\nFrom origin\n");
+        assert_eq!(
+            lines,
+            vec!["Receiving objects: 100%".to_string(), "From origin".to_string()]
+        );
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn is_git_auth_error_matches_prompt_failures() {
+        assert!(is_git_auth_error(
+            "fatal: could not read Username for 'https://github.com': terminal prompts disabled"
+        ));
+        assert!(is_git_auth_error("git@github.com: Permission denied (publickey)."));
+        assert!(!is_git_auth_error("fatal: couldn't find remote ref main"));
+    }
+
+    #[test]
+    fn rate_limit_errors_name_the_reset_time() {
+        let detail = "API rate limit exceeded for user ID 1.";
+        assert_eq!(rate_limit_error(detail, None), detail);
+        assert_eq!(
+            rate_limit_error(detail, Some(Duration::from_secs(61))),
+            format!("{detail}\nThe GitHub rate limit resets in about 2 min.")
+        );
+    }
+
+    #[test]
+    fn is_transient_remote_error_skips_auth_failures() {
+        assert!(is_transient_remote_error("HTTP 502: Bad Gateway (https://api.github.com)"));
+        assert!(!is_transient_remote_error("API rate limit exceeded for user ID 1."));
+        assert!(is_rate_limit_error("API rate limit exceeded for user ID 1."));
+        assert!(is_transient_remote_error(
+            "fatal: unable to access 'https://github.com/a/b.git/': Operation timed out"
+        ));
+        assert!(!is_transient_remote_error("HTTP 401: Bad credentials"));
+        assert!(!is_transient_remote_error("git@github.com: Permission denied (publickey)."));
+        assert!(!is_transient_remote_error("HTTP 404: Not Found"));
+        assert!(!is_transient_remote_error(&git_stall_error(
+            &["fetch", "--progress"],
+            Duration::from_secs(60)
+        )));
+    }
+
+    #[test]
+    fn parse_numstat_z_reads_counts_and_renames() {
+        let output = b"3\t1\tsrc/main.rs\0-\t-\tassets/logo.png\x002\t0\t\0old.txt\0new.txt\0";
+        let parsed = parse_numstat_z(output);
+        assert_eq!(
+            parsed,
+            vec![
+                ("src/main.rs".to_string(), 3, 1),
+                ("assets/logo.png".to_string(), 0, 0),
+                ("new.txt".to_string(), 2, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_name_status_z_maps_paths_to_status() {
+        let output = b"M\0src/main.rs\0A\0added.rs\0R100\0old.txt\0new.txt\0D\0gone.rs\0";
+        let parsed = parse_name_status_z(output);
+        assert_eq!(parsed.get("src/main.rs").map(String::as_str), Some("M"));
+        assert_eq!(parsed.get("added.rs").map(String::as_str), Some("A"));
+        assert_eq!(parsed.get("new.txt").map(String::as_str), Some("R"));
+        assert_eq!(parsed.get("gone.rs").map(String::as_str), Some("D"));
+        assert!(!parsed.contains_key("old.txt"));
+    }
+}
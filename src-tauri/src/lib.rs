@@ -11,10 +11,14 @@ mod dictation;
 #[cfg(target_os = "windows")]
 #[path = "dictation_stub.rs"]
 mod dictation;
+mod diagnostics;
 mod event_sink;
 mod git;
 mod git_utils;
+mod local_api;
+mod local_http;
 mod local_usage;
+mod logging;
 mod prompts;
 mod rules;
 mod settings;
@@ -24,6 +28,7 @@ mod window;
 mod storage;
 mod types;
 mod utils;
+mod workspace_files;
 mod workspaces;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -216,6 +221,14 @@ pub fn run() {
         .setup(|app| {
             let state = state::AppState::load(&app.handle());
             app.manage(state);
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let state = handle.state::<state::AppState>();
+                let enabled = state.app_settings.lock().await.local_api_enabled;
+                if let Err(err) = state.local_api.apply_settings(enabled).await {
+                    logging::warn("local-api", err);
+                }
+            });
             #[cfg(desktop)]
             app.handle()
                 .plugin(tauri_plugin_updater::Builder::new().build())?;
@@ -223,12 +236,19 @@ pub fn run() {
         })
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_process::init())
         .invoke_handler(tauri::generate_handler![
             settings::get_app_settings,
             settings::update_app_settings,
+            settings::validate_app_settings,
+            settings::is_dnd_active,
+            local_api::local_api_info,
+            settings::export_config,
+            settings::import_config,
             codex::codex_doctor,
             workspaces::list_workspaces,
+            workspaces::runner_info,
             workspaces::add_workspace,
             workspaces::add_clone,
             workspaces::add_worktree,
@@ -236,21 +256,39 @@ pub fn run() {
             workspaces::remove_worktree,
             workspaces::apply_worktree_changes,
             workspaces::update_workspace_settings,
+            workspaces::reorder_workspaces,
             workspaces::update_workspace_codex_bin,
+            workspaces::rename_workspace,
+            workspaces::set_thread_pinned,
+            codex::workspace_health,
+            codex::workspace_stderr,
+            codex::thread_usage,
             codex::start_thread,
             codex::send_user_message,
+            codex::send_user_message_blocking,
+            codex::set_thread_defaults,
             codex::turn_interrupt,
             codex::start_review,
             codex::respond_to_server_request,
+            codex::abort_all_turns,
+            codex::list_pending_server_requests,
+            codex::cancel_request,
+            codex::session_metrics,
             codex::remember_approval_rule,
             codex::resume_thread,
             codex::list_threads,
+            codex::list_workspace_threads,
+            codex::search_threads,
             codex::archive_thread,
+            codex::delete_thread,
             codex::collaboration_mode_list,
             workspaces::connect_workspace,
+            workspaces::connect_all_workspaces,
+            workspaces::disconnect_all_workspaces,
             git::get_git_status,
             git::list_git_roots,
             git::get_git_diffs,
+            git::git_diff_stats,
             git::get_git_log,
             git::get_git_remote,
             git::stage_git_file,
@@ -261,15 +299,35 @@ pub fn run() {
             git::get_github_pull_requests,
             git::get_github_pull_request_diff,
             git::get_github_pull_request_comments,
+            git::post_github_pr_comment,
+            git::post_github_issue_comment,
             workspaces::list_workspace_files,
+            workspaces::read_workspace_file,
+            workspaces::search_workspace,
             workspaces::open_workspace_in,
+            workspaces::open_in_editor,
+            diagnostics::get_logs,
+            diagnostics::collect_diagnostics,
+            diagnostics::save_diagnostics,
             git::list_git_branches,
             git::checkout_git_branch,
             git::create_git_branch,
+            git::delete_git_branch,
+            git::rename_git_branch,
+            git::git_fetch,
+            git::git_pull,
+            git::git_push,
+            git::git_conflicts,
+            git::git_abort_merge,
+            git::git_blame,
             codex::model_list,
+            codex::models_list,
             codex::account_rate_limits,
             codex::skills_list,
             prompts::prompts_list,
+            prompts::prompts_search,
+            prompts::prompts_get,
+            prompts::prompts_render,
             prompts::prompts_create,
             prompts::prompts_update,
             prompts::prompts_delete,
@@ -280,6 +338,8 @@ pub fn run() {
             terminal::terminal_write,
             terminal::terminal_resize,
             terminal::terminal_close,
+            terminal::terminal_list,
+            terminal::terminal_snapshot,
             dictation::dictation_model_status,
             dictation::dictation_download_model,
             dictation::dictation_cancel_download,
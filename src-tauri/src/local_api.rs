@@ -0,0 +1,203 @@
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tauri::State;
+use tokio::io::{AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, Mutex};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::backend::events::AppServerEvent;
+use crate::local_http::{read_request_head, sse_frame, write_event_stream_head, write_response};
+use crate::logging;
+use crate::state::AppState;
+
+const EVENT_BUFFER: usize = 1024;
+/// Comment frames keep idle streams alive and surface closed clients.
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Loopback HTTP endpoint that streams app-server events as server-sent
+/// events, for scripts and dashboards outside the app.
+pub(crate) struct LocalApi {
+    events: StdMutex<Option<broadcast::Sender<String>>>,
+    server: Mutex<Option<LocalServer>>,
+}
+
+struct LocalServer {
+    access: Arc<StreamAccess>,
+    task: JoinHandle<()>,
+}
+
+/// What a client must present: the listener's own `Host` and the token
+/// generated when the server started.
+struct StreamAccess {
+    port: u16,
+    token: String,
+}
+
+impl LocalApi {
+    pub(crate) fn new() -> Self {
+        Self {
+            events: StdMutex::new(None),
+            server: Mutex::new(None),
+        }
+    }
+
+    /// Forwards an event to connected stream clients; a no-op when the API is
+    /// off or nobody is listening.
+    pub(crate) fn publish(&self, event: &AppServerEvent) {
+        let Ok(events) = self.events.lock() else {
+            return;
+        };
+        let Some(sender) = events.as_ref().filter(|sender| sender.receiver_count() > 0) else {
+            return;
+        };
+        if let Ok(payload) = serde_json::to_string(event) {
+            let _ = sender.send(payload);
+        }
+    }
+
+    /// Port and token of the running server.
+    pub(crate) async fn access(&self) -> Option<(u16, String)> {
+        self.server
+            .lock()
+            .await
+            .as_ref()
+            .map(|server| (server.access.port, server.access.token.clone()))
+    }
+
+    /// Binds 127.0.0.1 on a random port with a fresh token. Returns the
+    /// running port if the server is already up.
+    async fn start(&self) -> Result<u16, String> {
+        let mut server = self.server.lock().await;
+        if let Some(server) = server.as_ref() {
+            return Ok(server.access.port);
+        }
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .map_err(|err| format!("Failed to start local API: {err}"))?;
+        let port = listener.local_addr().map_err(|err| err.to_string())?.port();
+        let (sender, _) = broadcast::channel(EVENT_BUFFER);
+        if let Ok(mut events) = self.events.lock() {
+            *events = Some(sender.clone());
+        }
+        let access = Arc::new(StreamAccess {
+            port,
+            token: Uuid::new_v4().simple().to_string(),
+        });
+        let task = tokio::spawn(accept_loop(listener, sender, Arc::clone(&access)));
+        *server = Some(LocalServer { access, task });
+        logging::info("local-api", format!("streaming events on http://127.0.0.1:{port}/events"));
+        Ok(port)
+    }
+
+    /// Stops accepting and ends every open stream: once the accept loop and
+    /// the stored sender are gone, subscribers see the channel close.
+    async fn stop(&self) {
+        if let Some(server) = self.server.lock().await.take() {
+            server.task.abort();
+            logging::info("local-api", "stopped");
+        }
+        if let Ok(mut events) = self.events.lock() {
+            events.take();
+        }
+    }
+
+    pub(crate) async fn apply_settings(&self, enabled: bool) -> Result<(), String> {
+        if enabled {
+            self.start().await.map(|_| ())
+        } else {
+            self.stop().await;
+            Ok(())
+        }
+    }
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    events: broadcast::Sender<String>,
+    access: Arc<StreamAccess>,
+) {
+    loop {
+        match listener.accept().await {
+            Ok((socket, _addr)) => {
+                tokio::spawn(serve_connection(
+                    socket,
+                    events.subscribe(),
+                    Arc::clone(&access),
+                ));
+            }
+            Err(err) => {
+                logging::warn("local-api", format!("failed to accept connection: {err}"));
+            }
+        }
+    }
+}
+
+async fn serve_connection(
+    socket: TcpStream,
+    mut events: broadcast::Receiver<String>,
+    access: Arc<StreamAccess>,
+) {
+    let (reader, mut writer) = socket.into_split();
+    let request = match read_request_head(&mut BufReader::new(reader)).await {
+        Ok(request) => request,
+        Err(err) => {
+            let _ = write_response(&mut writer, 400, "text/plain", &err).await;
+            return;
+        }
+    };
+    // Browsers attach an Origin to cross-site requests; scripts and curl do
+    // not. Refusing them keeps web pages from reading the local stream.
+    if request.header("origin").is_some() || !request.has_loopback_host(access.port) {
+        let _ = write_response(&mut writer, 403, "text/plain", "forbidden").await;
+        return;
+    }
+    let token = request
+        .bearer_token()
+        .or_else(|| request.query_param("token"));
+    if token != Some(access.token.as_str()) {
+        let _ = write_response(&mut writer, 401, "text/plain", "unauthorized").await;
+        return;
+    }
+    if request.path != "/events" {
+        let _ = write_response(&mut writer, 404, "text/plain", "not found").await;
+        return;
+    }
+    if request.method != "GET" {
+        let _ = write_response(&mut writer, 405, "text/plain", "use GET").await;
+        return;
+    }
+    if write_event_stream_head(&mut writer).await.is_err() {
+        return;
+    }
+    loop {
+        let frame = match tokio::time::timeout(KEEPALIVE_INTERVAL, events.recv()).await {
+            Ok(Ok(payload)) => sse_frame("app-server-event", &payload),
+            Ok(Err(broadcast::error::RecvError::Lagged(skipped))) => {
+                format!(": skipped {skipped} events\n\n")
+            }
+            Ok(Err(broadcast::error::RecvError::Closed)) => break,
+            Err(_) => ": keepalive\n\n".to_string(),
+        };
+        if writer.write_all(frame.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn local_api_info(state: State<'_, AppState>) -> Result<Value, String> {
+    let enabled = state.app_settings.lock().await.local_api_enabled;
+    let access = state.local_api.access().await;
+    Ok(json!({
+        "enabled": enabled,
+        "port": access.as_ref().map(|(port, _)| port),
+        "token": access.as_ref().map(|(_, token)| token),
+        "eventsUrl": access
+            .as_ref()
+            .map(|(port, token)| format!("http://127.0.0.1:{port}/events?token={token}")),
+    }))
+}
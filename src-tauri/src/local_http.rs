@@ -0,0 +1,232 @@
+//! Just enough HTTP/1.1 for the loopback-only local API: one request per
+//! connection, no keep-alive, no chunked bodies.
+
+use std::time::Duration;
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, Take};
+
+/// Caps the request line plus headers so a stuck client cannot grow memory.
+const MAX_HEAD_BYTES: u64 = 16 * 1024;
+/// A client that has not finished its headers by then is dropped, so idle
+/// connections cannot hold tasks open.
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct HttpRequest {
+    pub(crate) method: String,
+    /// Request path without the query string.
+    pub(crate) path: String,
+    /// Raw query string after `?`, if any.
+    pub(crate) query: Option<String>,
+    /// Header names are lowercased; values are trimmed.
+    pub(crate) headers: Vec<(String, String)>,
+}
+
+impl HttpRequest {
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn bearer_token(&self) -> Option<&str> {
+        self.header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+    }
+
+    /// Whether `Host` names the loopback listener itself. A DNS-rebound page
+    /// reaches the same socket but sends its own host name here.
+    pub(crate) fn has_loopback_host(&self, port: u16) -> bool {
+        self.header("host").is_some_and(|host| {
+            host == format!("127.0.0.1:{port}") || host == format!("localhost:{port}")
+        })
+    }
+
+    /// Looks up a query parameter. Values are not percent-decoded, which is
+    /// fine for the tokens and flags this API accepts.
+    pub(crate) fn query_param(&self, name: &str) -> Option<&str> {
+        self.query.as_deref()?.split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key == name).then_some(value)
+        })
+    }
+}
+
+fn parse_request_line(line: &str) -> Result<HttpRequest, String> {
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err("malformed request line".to_string());
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(format!("unsupported protocol {version}"));
+    }
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, Some(query.to_string())),
+        None => (target, None),
+    };
+    Ok(HttpRequest {
+        method: method.to_ascii_uppercase(),
+        path: path.to_string(),
+        query,
+        headers: Vec::new(),
+    })
+}
+
+/// Reads one head line. `read_line` buffers until it sees a newline, so the
+/// shared `take` limit is what stops an endless line.
+async fn read_head_line<R: AsyncBufRead + Unpin>(head: &mut Take<R>) -> Result<String, String> {
+    let mut line = String::new();
+    head.read_line(&mut line)
+        .await
+        .map_err(|err| err.to_string())?;
+    if !line.ends_with('\n') && head.limit() == 0 {
+        return Err("request headers too large".to_string());
+    }
+    Ok(line.trim_end().to_string())
+}
+
+/// Reads the request line and headers, leaving the reader at the start of
+/// the body.
+pub(crate) async fn read_request_head<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> Result<HttpRequest, String> {
+    let mut head = (&mut *reader).take(MAX_HEAD_BYTES);
+    let read_head = async {
+        let mut request = parse_request_line(&read_head_line(&mut head).await?)?;
+        loop {
+            let line = read_head_line(&mut head).await?;
+            if line.is_empty() {
+                return Ok(request);
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                request
+                    .headers
+                    .push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+    };
+    tokio::time::timeout(HEAD_TIMEOUT, read_head)
+        .await
+        .map_err(|_| "timed out reading request headers".to_string())?
+}
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+pub(crate) async fn write_response<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    status: u16,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {status} {}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n",
+        status_text(status),
+        body.len()
+    );
+    writer.write_all(head.as_bytes()).await?;
+    writer.write_all(body.as_bytes()).await?;
+    writer.flush().await
+}
+
+/// Starts a `text/event-stream` response; frames follow via [`sse_frame`].
+pub(crate) async fn write_event_stream_head<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+) -> std::io::Result<()> {
+    writer
+        .write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\
+              Connection: keep-alive\r\n\r\n",
+        )
+        .await?;
+    writer.flush().await
+}
+
+/// Formats one server-sent event. Multi-line data becomes several `data:`
+/// lines, which clients join back with newlines.
+pub(crate) fn sse_frame(event: &str, data: &str) -> String {
+    let mut frame = format!("event: {event}\n");
+    for line in data.split('\n') {
+        frame.push_str("data: ");
+        frame.push_str(line);
+        frame.push('\n');
+    }
+    frame.push('\n');
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_request_line, read_request_head, sse_frame, HttpRequest};
+
+    #[test]
+    fn request_line_splits_off_the_query_string() {
+        let request = parse_request_line("get /events?since=1&token=abc HTTP/1.1");
+        assert_eq!(
+            request,
+            Ok(HttpRequest {
+                method: "GET".to_string(),
+                path: "/events".to_string(),
+                query: Some("since=1&token=abc".to_string()),
+                headers: Vec::new(),
+            })
+        );
+        let mut request = request.expect("request");
+        assert_eq!(request.query_param("token"), Some("abc"));
+        request
+            .headers
+            .push(("host".to_string(), "localhost:4733".to_string()));
+        assert!(request.has_loopback_host(4733));
+        assert!(!request.has_loopback_host(80));
+        request.headers[0].1 = "attacker.example:4733".to_string();
+        assert!(!request.has_loopback_host(4733));
+        assert_eq!(request.query_param("tok"), None);
+        assert!(parse_request_line("GET /events").is_err());
+        assert!(parse_request_line("GET /events SPDY/3").is_err());
+    }
+
+    #[test]
+    fn request_head_collects_headers_and_stops_at_the_body() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("runtime");
+        let raw: &[u8] =
+            b"POST /rpc HTTP/1.1\r\nAuthorization: Bearer abc\r\nContent-Length: 2\r\n\r\n{}";
+        let mut reader = raw;
+        let request = runtime
+            .block_on(read_request_head(&mut reader))
+            .expect("head");
+        assert_eq!(request.header("authorization"), Some("Bearer abc"));
+        assert_eq!(request.header("Content-Length"), Some("2"));
+        assert_eq!(request.header("origin"), None);
+        assert_eq!(request.bearer_token(), Some("abc"));
+        assert!(!request.has_loopback_host(4733));
+        assert_eq!(reader, b"{}");
+
+        let endless = vec![b'a'; 64 * 1024];
+        let mut reader = endless.as_slice();
+        let result = runtime.block_on(read_request_head(&mut reader));
+        assert_eq!(result, Err("request headers too large".to_string()));
+    }
+
+    #[test]
+    fn sse_frames_split_multiline_data() {
+        assert_eq!(sse_frame("ping", "{}"), "event: ping\ndata: {}\n\n");
+        assert_eq!(sse_frame("log", "a\nb"), "event: log\ndata: a\ndata: b\n\n");
+    }
+}
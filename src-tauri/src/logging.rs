@@ -0,0 +1,171 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use serde::Serialize;
+
+use crate::utils::now_ms;
+
+const DEFAULT_LOG_CAPACITY: usize = 2_000;
+
+static LOG_BUFFER: OnceLock<Arc<LogBuffer>> = OnceLock::new();
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "debug" => Some(Self::Debug),
+            "info" => Some(Self::Info),
+            "warn" | "warning" => Some(Self::Warn),
+            "error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct LogEntry {
+    #[serde(rename = "timestampMs")]
+    pub(crate) timestamp_ms: i64,
+    pub(crate) level: LogLevel,
+    pub(crate) tag: String,
+    pub(crate) message: String,
+}
+
+/// Keeps the most recent log lines in memory so they can be shown in the UI
+/// without attaching a console.
+pub(crate) struct LogBuffer {
+    capacity: usize,
+    entries: Mutex<VecDeque<LogEntry>>,
+}
+
+impl LogBuffer {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Returns up to `limit` of the newest entries at or above `min_level`,
+    /// oldest first.
+    pub(crate) fn recent(&self, min_level: Option<LogLevel>, limit: usize) -> Vec<LogEntry> {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        let mut recent: Vec<LogEntry> = entries
+            .iter()
+            .rev()
+            .filter(|entry| match min_level {
+                Some(level) => entry.level >= level,
+                None => true,
+            })
+            .take(limit)
+            .cloned()
+            .collect();
+        recent.reverse();
+        recent
+    }
+}
+
+/// The process-wide buffer every `log` call writes to.
+pub(crate) fn log_buffer() -> Arc<LogBuffer> {
+    LOG_BUFFER
+        .get_or_init(|| Arc::new(LogBuffer::new(DEFAULT_LOG_CAPACITY)))
+        .clone()
+}
+
+pub(crate) fn log(level: LogLevel, tag: &str, message: impl Into<String>) {
+    let message = message.into();
+    eprintln!("[{}] {tag}: {message}", level.as_str());
+    log_buffer().push(LogEntry {
+        timestamp_ms: now_ms(),
+        level,
+        tag: tag.to_string(),
+        message,
+    });
+}
+
+pub(crate) fn debug(tag: &str, message: impl Into<String>) {
+    log(LogLevel::Debug, tag, message);
+}
+
+pub(crate) fn info(tag: &str, message: impl Into<String>) {
+    log(LogLevel::Info, tag, message);
+}
+
+pub(crate) fn warn(tag: &str, message: impl Into<String>) {
+    log(LogLevel::Warn, tag, message);
+}
+
+pub(crate) fn error(tag: &str, message: impl Into<String>) {
+    log(LogLevel::Error, tag, message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogBuffer, LogEntry, LogLevel};
+
+    fn entry(level: LogLevel, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp_ms: 0,
+            level,
+            tag: "test".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn log_buffer_drops_oldest_and_filters_by_level() {
+        let buffer = LogBuffer::new(3);
+        buffer.push(entry(LogLevel::Info, "one"));
+        buffer.push(entry(LogLevel::Error, "two"));
+        buffer.push(entry(LogLevel::Debug, "three"));
+        buffer.push(entry(LogLevel::Warn, "four"));
+
+        let all: Vec<String> = buffer
+            .recent(None, 10)
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect();
+        assert_eq!(all, vec!["two", "three", "four"]);
+
+        let warnings: Vec<String> = buffer
+            .recent(Some(LogLevel::Warn), 10)
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect();
+        assert_eq!(warnings, vec!["two", "four"]);
+
+        let newest: Vec<String> = buffer
+            .recent(None, 1)
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect();
+        assert_eq!(newest, vec!["four"]);
+        assert_eq!(LogLevel::parse("WARNING"), Some(LogLevel::Warn));
+        assert_eq!(LogLevel::parse("verbose"), None);
+    }
+}
@@ -19,6 +19,8 @@ pub(crate) struct CustomPromptEntry {
     pub(crate) content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) scope: Option<String>,
+    /// `{{name}}` placeholders found in the body, in first-use order.
+    pub(crate) arguments: Vec<String>,
 }
 
 fn resolve_home_dir() -> Option<PathBuf> {
@@ -223,6 +225,63 @@ fn build_prompt_contents(
     output
 }
 
+/// Splits `content` into literal text and `{{name}}` placeholders. Braces that
+/// do not wrap a plain identifier are kept as literal text.
+fn split_placeholders(content: &str) -> Vec<(bool, &str)> {
+    let mut parts = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let name = after[..end].trim();
+        let is_placeholder = !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+        if is_placeholder {
+            parts.push((false, &rest[..start]));
+            parts.push((true, name));
+            rest = &after[end + 2..];
+        } else {
+            parts.push((false, &rest[..start + 2]));
+            rest = after;
+        }
+    }
+    parts.push((false, rest));
+    parts
+}
+
+fn prompt_arguments(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (is_placeholder, value) in split_placeholders(content) {
+        if is_placeholder && !names.iter().any(|name| name == value) {
+            names.push(value.to_string());
+        }
+    }
+    names
+}
+
+fn render_prompt(content: &str, args: &HashMap<String, String>) -> Result<String, String> {
+    let missing: Vec<String> = prompt_arguments(content)
+        .into_iter()
+        .filter(|name| !args.contains_key(name))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Missing prompt arguments: {}", missing.join(", ")));
+    }
+    let mut output = String::with_capacity(content.len());
+    for (is_placeholder, value) in split_placeholders(content) {
+        if is_placeholder {
+            output.push_str(&args[value]);
+        } else {
+            output.push_str(value);
+        }
+    }
+    Ok(output)
+}
+
 fn sanitize_prompt_name(name: &str) -> Result<String, String> {
     let trimmed = name.trim();
     if trimmed.is_empty() {
@@ -275,6 +334,7 @@ fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry
             path: path.to_string_lossy().to_string(),
             description,
             argument_hint,
+            arguments: prompt_arguments(&body),
             content: body,
             scope: scope.map(|value| value.to_string()),
         });
@@ -284,17 +344,44 @@ fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry
     out
 }
 
-#[tauri::command]
-pub(crate) async fn prompts_list(
-    state: State<'_, AppState>,
-    workspace_id: String,
+fn subsequence_match(haystack: &str, needle: &str) -> bool {
+    let mut chars = haystack.chars();
+    needle.chars().all(|ch| chars.any(|candidate| candidate == ch))
+}
+
+/// Ranks a prompt against a lowercase query: name matches beat description
+/// matches, and exact/prefix/substring beat scattered subsequence hits.
+fn prompt_match_score(entry: &CustomPromptEntry, query: &str) -> Option<u32> {
+    let name = entry.name.to_lowercase();
+    if name == query {
+        return Some(1000);
+    }
+    if name.starts_with(query) {
+        return Some(800);
+    }
+    if let Some(index) = name.find(query) {
+        return Some(600u32.saturating_sub(index as u32));
+    }
+    if subsequence_match(&name, query) {
+        return Some(300);
+    }
+    let description = entry.description.as_deref().unwrap_or("").to_lowercase();
+    if description.contains(query) {
+        return Some(200);
+    }
+    None
+}
+
+async fn load_prompts(
+    state: &State<'_, AppState>,
+    workspace_id: &str,
 ) -> Result<Vec<CustomPromptEntry>, String> {
     let (workspace_dir, global_dir) = {
         let workspaces = state.workspaces.lock().await;
-        let entry = workspaces.get(&workspace_id).cloned();
+        let entry = workspaces.get(workspace_id).cloned();
         let workspace_dir = entry
             .as_ref()
-            .and_then(|entry| workspace_prompts_dir(&state, entry).ok());
+            .and_then(|entry| workspace_prompts_dir(state, entry).ok());
         (workspace_dir, default_prompts_dir())
     };
 
@@ -314,6 +401,59 @@ pub(crate) async fn prompts_list(
     .map_err(|_| "prompt discovery failed".to_string())
 }
 
+#[tauri::command]
+pub(crate) async fn prompts_list(
+    state: State<'_, AppState>,
+    workspace_id: String,
+) -> Result<Vec<CustomPromptEntry>, String> {
+    load_prompts(&state, &workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_search(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    query: String,
+) -> Result<Vec<CustomPromptEntry>, String> {
+    let prompts = load_prompts(&state, &workspace_id).await?;
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Ok(prompts);
+    }
+    let mut ranked: Vec<(u32, CustomPromptEntry)> = prompts
+        .into_iter()
+        .filter_map(|entry| prompt_match_score(&entry, &query).map(|score| (score, entry)))
+        .collect();
+    ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    Ok(ranked.into_iter().map(|(_, entry)| entry).collect())
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_get(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    name: String,
+) -> Result<CustomPromptEntry, String> {
+    let name = name.trim();
+    // Workspace prompts are listed first, so they shadow global ones.
+    load_prompts(&state, &workspace_id)
+        .await?
+        .into_iter()
+        .find(|entry| entry.name == name)
+        .ok_or_else(|| "Prompt not found.".to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_render(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    name: String,
+    args: HashMap<String, String>,
+) -> Result<String, String> {
+    let prompt = prompts_get(state, workspace_id, name).await?;
+    render_prompt(&prompt.content, &args)
+}
+
 #[tauri::command]
 pub(crate) async fn prompts_workspace_dir(
     state: State<'_, AppState>,
@@ -375,6 +515,7 @@ pub(crate) async fn prompts_create(
         path: path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        arguments: prompt_arguments(&content),
         content,
         scope: Some(resolved_scope.to_string()),
     })
@@ -428,6 +569,7 @@ pub(crate) async fn prompts_update(
         path: next_path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        arguments: prompt_arguments(&content),
         content,
         scope,
     })
@@ -505,7 +647,57 @@ pub(crate) async fn prompts_move(
         path: next_path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        arguments: prompt_arguments(&body),
         content: body,
         scope: Some(scope),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{prompt_arguments, prompt_match_score, render_prompt, CustomPromptEntry};
+    use std::collections::HashMap;
+
+    fn prompt(name: &str, description: Option<&str>) -> CustomPromptEntry {
+        CustomPromptEntry {
+            name: name.to_string(),
+            path: format!("/tmp/{name}.md"),
+            description: description.map(|value| value.to_string()),
+            argument_hint: None,
+            arguments: Vec::new(),
+            content: String::new(),
+            scope: Some("global".to_string()),
+        }
+    }
+
+    #[test]
+    fn prompt_scores_prefer_name_matches() {
+        let exact = prompt_match_score(&prompt("review", None), "review");
+        let prefix = prompt_match_score(&prompt("review-pr", None), "review");
+        let fuzzy = prompt_match_score(&prompt("revise-new", None), "review");
+        let described = prompt_match_score(&prompt("audit", Some("Review the diff")), "review");
+        assert!(exact > prefix);
+        assert!(prefix > fuzzy);
+        assert!(fuzzy > described);
+        assert!(described.is_some());
+        assert!(prompt_match_score(&prompt("deploy", None), "review").is_none());
+    }
+
+    #[test]
+    fn prompt_arguments_are_detected_and_rendered() {
+        let content =
+            "Review {{ file }} for {{focus}}; keep {{file}} style. Literal {{not valid}}.";
+        assert_eq!(prompt_arguments(content), vec!["file", "focus"]);
+
+        let mut args = HashMap::new();
+        args.insert("file".to_string(), "main.rs".to_string());
+        let error = render_prompt(content, &args).expect_err("missing focus");
+        assert_eq!(error, "Missing prompt arguments: focus");
+
+        args.insert("focus".to_string(), "bugs".to_string());
+        assert_eq!(
+            render_prompt(content, &args).expect("render"),
+            "Review main.rs for bugs; keep main.rs style. Literal {{not valid}}."
+        );
+    }
+}
@@ -1,9 +1,14 @@
-use tauri::{State, Window};
+use chrono::{Local, Timelike};
+use tauri::{Emitter, State, Window};
 
 use crate::codex_config;
+use crate::logging;
 use crate::state::AppState;
-use crate::storage::write_settings;
-use crate::types::AppSettings;
+use crate::storage::{
+    apply_config_bundle, build_config_bundle, replaced_workspace_ids, write_config, write_settings,
+};
+use crate::types::{format_settings_errors, AppSettings, ConfigBundle, SettingsFieldError};
+use crate::utils::now_ms;
 use crate::window;
 
 #[tauri::command]
@@ -27,16 +32,92 @@ pub(crate) async fn get_app_settings(
 
 #[tauri::command]
 pub(crate) async fn update_app_settings(
-    settings: AppSettings,
+    mut settings: AppSettings,
     state: State<'_, AppState>,
     window: Window,
 ) -> Result<AppSettings, String> {
+    let errors = settings.validate();
+    if !errors.is_empty() {
+        return Err(format_settings_errors(&errors));
+    }
     let _ = codex_config::write_collab_enabled(settings.experimental_collab_enabled);
     let _ = codex_config::write_steer_enabled(settings.experimental_steer_enabled);
     let _ = codex_config::write_unified_exec_enabled(settings.experimental_unified_exec_enabled);
     write_settings(&state.settings_path, &settings)?;
+    if let Err(err) = state.local_api.apply_settings(settings.local_api_enabled).await {
+        logging::warn("local-api", err);
+    }
     let mut current = state.app_settings.lock().await;
     *current = settings.clone();
     let _ = window::apply_window_appearance(&window, settings.theme.as_str());
+    let _ = window.emit("settings-changed", &settings);
     Ok(settings)
 }
+
+/// Evaluates the do-not-disturb schedule against the current local time.
+pub(crate) fn dnd_active_now(settings: &AppSettings) -> bool {
+    let now = Local::now();
+    settings.dnd_active_at(now.hour() * 60 + now.minute())
+}
+
+#[tauri::command]
+pub(crate) async fn is_dnd_active(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(dnd_active_now(&*state.app_settings.lock().await))
+}
+
+#[tauri::command]
+pub(crate) async fn validate_app_settings(
+    mut settings: AppSettings,
+) -> Result<Vec<SettingsFieldError>, String> {
+    Ok(settings.validate())
+}
+
+#[tauri::command]
+pub(crate) async fn export_config(
+    include_secrets: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<ConfigBundle, String> {
+    let settings = state.app_settings.lock().await.clone();
+    let workspaces = state.workspaces.lock().await.clone();
+    Ok(build_config_bundle(
+        &settings,
+        &workspaces,
+        include_secrets.unwrap_or(false),
+        now_ms(),
+    ))
+}
+
+#[tauri::command]
+pub(crate) async fn import_config(
+    bundle: ConfigBundle,
+    merge: bool,
+    state: State<'_, AppState>,
+    window: Window,
+) -> Result<ConfigBundle, String> {
+    let current_settings = state.app_settings.lock().await.clone();
+    let (settings, replaced_ids) = {
+        let mut workspaces = state.workspaces.lock().await;
+        let (settings, next) = apply_config_bundle(&current_settings, &workspaces, bundle, merge)?;
+        let list: Vec<_> = next.values().cloned().collect();
+        write_config(&state.settings_path, &settings, &state.storage_path, &list)?;
+        let replaced_ids = replaced_workspace_ids(&workspaces, &next);
+        *workspaces = next;
+        (settings, replaced_ids)
+    };
+    *state.app_settings.lock().await = settings.clone();
+    for id in replaced_ids {
+        if let Some(session) = state.sessions.lock().await.remove(&id) {
+            let mut child = session.child.lock().await;
+            let _ = child.kill().await;
+        }
+    }
+
+    let _ = codex_config::write_collab_enabled(settings.experimental_collab_enabled);
+    let _ = codex_config::write_steer_enabled(settings.experimental_steer_enabled);
+    let _ = codex_config::write_unified_exec_enabled(settings.experimental_unified_exec_enabled);
+    let _ = window::apply_window_appearance(&window, settings.theme.as_str());
+    let _ = window.emit("settings-changed", &settings);
+
+    let workspaces = state.workspaces.lock().await.clone();
+    Ok(build_config_bundle(&settings, &workspaces, false, now_ms()))
+}
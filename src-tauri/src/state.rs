@@ -2,12 +2,16 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use serde_json::Value;
 use tauri::{AppHandle, Manager};
 use tokio::sync::Mutex;
 
+use crate::backend::app_server::CachedRateLimits;
 use crate::dictation::DictationState;
-use crate::storage::{read_settings, read_workspaces};
-use crate::types::{AppSettings, WorkspaceEntry};
+use crate::local_api::LocalApi;
+use crate::logging::{log_buffer, LogBuffer};
+use crate::storage::{read_or_create_runner_id, read_settings, read_workspaces};
+use crate::types::{AppSettings, ThreadDefaults, WorkspaceEntry};
 
 pub(crate) struct AppState {
     pub(crate) workspaces: Mutex<HashMap<String, WorkspaceEntry>>,
@@ -18,6 +22,14 @@ pub(crate) struct AppState {
     pub(crate) settings_path: PathBuf,
     pub(crate) app_settings: Mutex<AppSettings>,
     pub(crate) dictation: Mutex<DictationState>,
+    pub(crate) github_cache: Mutex<HashMap<(String, String), crate::git::GitHubCacheEntry>>,
+    pub(crate) thread_defaults: Mutex<HashMap<String, ThreadDefaults>>,
+    pub(crate) rate_limits_cache: Mutex<HashMap<String, CachedRateLimits>>,
+    pub(crate) models_cache: Mutex<HashMap<String, Value>>,
+    pub(crate) logs: Arc<LogBuffer>,
+    pub(crate) local_api: LocalApi,
+    pub(crate) runner_id: String,
+    pub(crate) started_at_ms: i64,
 }
 
 impl AppState {
@@ -30,6 +42,7 @@ impl AppState {
         let settings_path = data_dir.join("settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
+        let runner_id = read_or_create_runner_id(&data_dir.join("runner-id"));
         Self {
             workspaces: Mutex::new(workspaces),
             sessions: Mutex::new(HashMap::new()),
@@ -38,6 +51,14 @@ impl AppState {
             settings_path,
             app_settings: Mutex::new(app_settings),
             dictation: Mutex::new(DictationState::default()),
+            github_cache: Mutex::new(HashMap::new()),
+            thread_defaults: Mutex::new(HashMap::new()),
+            rate_limits_cache: Mutex::new(HashMap::new()),
+            models_cache: Mutex::new(HashMap::new()),
+            logs: log_buffer(),
+            local_api: LocalApi::new(),
+            runner_id,
+            started_at_ms: crate::utils::now_ms(),
         }
     }
 }
@@ -1,48 +1,307 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use crate::types::{AppSettings, WorkspaceEntry};
+use serde::de::DeserializeOwned;
 
-pub(crate) fn read_workspaces(path: &PathBuf) -> Result<HashMap<String, WorkspaceEntry>, String> {
-    if !path.exists() {
-        return Ok(HashMap::new());
+use crate::types::{
+    format_settings_errors, validate_access_mode, validate_codex_args, AppSettings, ConfigBundle,
+    WorkspaceEntry,
+};
+
+pub(crate) const CONFIG_BUNDLE_VERSION: u32 = 1;
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Writes `data` to a temp file next to `path`, ready to be renamed over it.
+fn stage_file(path: &Path, data: &str) -> Result<PathBuf, String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    let written = std::fs::File::create(&temp_path).and_then(|mut file| {
+        file.write_all(data.as_bytes())?;
+        file.sync_all()
+    });
+    written.map(|_| temp_path.clone()).map_err(|e| {
+        let _ = std::fs::remove_file(&temp_path);
+        e.to_string()
+    })
+}
+
+fn commit_file(temp_path: &Path, path: &Path) -> Result<(), String> {
+    std::fs::rename(temp_path, path).map_err(|e| {
+        let _ = std::fs::remove_file(temp_path);
+        e.to_string()
+    })
+}
+
+/// Keeps the current file as `.bak` when it still parses, so a corrupt file
+/// never replaces the last good backup. The backup goes through a temp file
+/// too, so it is never left half-written.
+fn back_up(path: &Path) {
+    let Ok(existing) = std::fs::read_to_string(path) else {
+        return;
+    };
+    if serde_json::from_str::<serde_json::Value>(&existing).is_err() {
+        return;
+    }
+    let backup = backup_path(path);
+    if let Ok(temp_path) = stage_file(&backup, &existing) {
+        let _ = commit_file(&temp_path, &backup);
+    }
+}
+
+/// Writes through a temp file and renames it over `path`, so a crash leaves
+/// either the old or the new file. The previous file is kept as `.bak`.
+fn write_atomic(path: &Path, data: &str) -> Result<(), String> {
+    let temp_path = stage_file(path, data)?;
+    back_up(path);
+    commit_file(&temp_path, path)
+}
+
+/// Reads JSON from `path`, falling back to its `.bak` when the file is
+/// missing or unreadable. Returns `None` when neither exists.
+fn read_json_with_backup<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, String> {
+    let backup = backup_path(path);
+    if !path.exists() && !backup.exists() {
+        return Ok(None);
+    }
+    let primary = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|data| serde_json::from_str(&data).map_err(|e| e.to_string()));
+    match primary {
+        Ok(value) => Ok(Some(value)),
+        Err(err) => std::fs::read_to_string(&backup)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .map(Some)
+            .ok_or(err),
     }
-    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
-    let list: Vec<WorkspaceEntry> = serde_json::from_str(&data).map_err(|e| e.to_string())?;
+}
+
+pub(crate) fn read_workspaces(path: &Path) -> Result<HashMap<String, WorkspaceEntry>, String> {
+    let list: Vec<WorkspaceEntry> = read_json_with_backup(path)?.unwrap_or_default();
     Ok(list
         .into_iter()
         .map(|entry| (entry.id.clone(), entry))
         .collect())
 }
 
-pub(crate) fn write_workspaces(path: &PathBuf, entries: &[WorkspaceEntry]) -> Result<(), String> {
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-    }
+pub(crate) fn write_workspaces(path: &Path, entries: &[WorkspaceEntry]) -> Result<(), String> {
     let data = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
-    std::fs::write(path, data).map_err(|e| e.to_string())
+    write_atomic(path, &data)
+}
+
+pub(crate) fn read_settings(path: &Path) -> Result<AppSettings, String> {
+    Ok(read_json_with_backup(path)?.unwrap_or_default())
+}
+
+pub(crate) fn write_settings(path: &Path, settings: &AppSettings) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    write_atomic(path, &data)
+}
+
+/// Writes settings and workspaces together for a config import. Both files
+/// are staged before either is replaced, so a failed write leaves the old
+/// pair in place rather than new settings next to old workspaces.
+pub(crate) fn write_config(
+    settings_path: &Path,
+    settings: &AppSettings,
+    workspaces_path: &Path,
+    entries: &[WorkspaceEntry],
+) -> Result<(), String> {
+    let settings_data = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    let workspaces_data = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    let settings_temp = stage_file(settings_path, &settings_data)?;
+    let discard_settings = |_: &String| {
+        let _ = std::fs::remove_file(&settings_temp);
+    };
+    let workspaces_temp =
+        stage_file(workspaces_path, &workspaces_data).inspect_err(discard_settings)?;
+    back_up(workspaces_path);
+    back_up(settings_path);
+    commit_file(&workspaces_temp, workspaces_path).inspect_err(discard_settings)?;
+    commit_file(&settings_temp, settings_path)
 }
 
-pub(crate) fn read_settings(path: &PathBuf) -> Result<AppSettings, String> {
-    if !path.exists() {
-        return Ok(AppSettings::default());
+/// Ids of workspaces an import removes or whose entry it changes. Their
+/// sessions still run with the old entry and have to be restarted.
+pub(crate) fn replaced_workspace_ids(
+    current: &HashMap<String, WorkspaceEntry>,
+    next: &HashMap<String, WorkspaceEntry>,
+) -> Vec<String> {
+    let as_json = |entry: &WorkspaceEntry| serde_json::to_value(entry).ok();
+    current
+        .iter()
+        .filter(|(id, entry)| match next.get(*id) {
+            Some(next_entry) => as_json(entry) != as_json(next_entry),
+            None => true,
+        })
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+/// Assigns sequential sort orders to main workspaces, listed ids first.
+/// Worktrees keep theirs.
+pub(crate) fn apply_workspace_reorder(
+    workspaces: &mut HashMap<String, WorkspaceEntry>,
+    ordered_ids: &[String],
+) {
+    let mut ordered: Vec<String> = Vec::new();
+    for id in ordered_ids {
+        let is_main = workspaces
+            .get(id)
+            .is_some_and(|entry| !entry.kind.is_worktree());
+        if is_main && !ordered.contains(id) {
+            ordered.push(id.clone());
+        }
+    }
+    // Workspaces missing from the request keep their relative order after the listed ones.
+    let mut remaining: Vec<&WorkspaceEntry> = workspaces
+        .values()
+        .filter(|entry| !entry.kind.is_worktree() && !ordered.contains(&entry.id))
+        .collect();
+    remaining.sort_by(|a, b| {
+        let a_order = a.settings.sort_order.unwrap_or(u32::MAX);
+        let b_order = b.settings.sort_order.unwrap_or(u32::MAX);
+        a_order
+            .cmp(&b_order)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    let remaining: Vec<String> = remaining.into_iter().map(|entry| entry.id.clone()).collect();
+    ordered.extend(remaining);
+    for (index, id) in ordered.iter().enumerate() {
+        if let Some(entry) = workspaces.get_mut(id) {
+            entry.settings.sort_order = Some(index as u32);
+        }
+    }
+}
+
+pub(crate) fn build_config_bundle(
+    settings: &AppSettings,
+    workspaces: &HashMap<String, WorkspaceEntry>,
+    include_secrets: bool,
+    exported_at_ms: i64,
+) -> ConfigBundle {
+    let mut settings = settings.clone();
+    if !include_secrets {
+        settings.remote_backend_token = None;
+    }
+    let mut workspaces: Vec<WorkspaceEntry> = workspaces.values().cloned().collect();
+    workspaces.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+    ConfigBundle {
+        version: CONFIG_BUNDLE_VERSION,
+        exported_at_ms,
+        settings,
+        workspaces,
     }
-    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
-    serde_json::from_str(&data).map_err(|e| e.to_string())
 }
 
-pub(crate) fn write_settings(path: &PathBuf, settings: &AppSettings) -> Result<(), String> {
+/// Computes the settings and workspaces that result from importing `bundle`.
+/// Replace mode adopts the bundle wholesale; merge mode keeps local settings
+/// and only adds workspaces and groups that are not already present. A
+/// redacted secret never clears the local one.
+pub(crate) fn apply_config_bundle(
+    current_settings: &AppSettings,
+    current_workspaces: &HashMap<String, WorkspaceEntry>,
+    bundle: ConfigBundle,
+    merge: bool,
+) -> Result<(AppSettings, HashMap<String, WorkspaceEntry>), String> {
+    if bundle.version > CONFIG_BUNDLE_VERSION {
+        return Err(format!(
+            "Unsupported config bundle version {} (expected {CONFIG_BUNDLE_VERSION} or older)",
+            bundle.version
+        ));
+    }
+    let mut imported_settings = bundle.settings;
+    let errors = imported_settings.validate();
+    if !errors.is_empty() {
+        return Err(format_settings_errors(&errors));
+    }
+    for entry in &bundle.workspaces {
+        let invalid = |err: String| format!("workspace \"{}\": {err}", entry.name);
+        if let Some(mode) = entry.settings.max_access_mode.as_deref() {
+            validate_access_mode(mode).map_err(invalid)?;
+        }
+        if let Some(args) = entry.settings.codex_args.as_deref() {
+            validate_codex_args(args).map_err(invalid)?;
+        }
+    }
+
+    let (settings, mut workspaces) = if merge {
+        let mut settings = current_settings.clone();
+        for group in imported_settings.workspace_groups {
+            if !settings
+                .workspace_groups
+                .iter()
+                .any(|existing| existing.id == group.id)
+            {
+                settings.workspace_groups.push(group);
+            }
+        }
+        let mut workspaces = current_workspaces.clone();
+        for entry in bundle.workspaces {
+            let duplicate = workspaces
+                .values()
+                .any(|existing| existing.id == entry.id || existing.path == entry.path);
+            if !duplicate {
+                workspaces.insert(entry.id.clone(), entry);
+            }
+        }
+        (settings, workspaces)
+    } else {
+        if imported_settings.remote_backend_token.is_none() {
+            imported_settings.remote_backend_token =
+                current_settings.remote_backend_token.clone();
+        }
+        let workspaces = bundle
+            .workspaces
+            .into_iter()
+            .map(|entry| (entry.id.clone(), entry))
+            .collect();
+        (imported_settings, workspaces)
+    };
+
+    // Worktrees are meaningless without their parent workspace.
+    let ids: Vec<String> = workspaces.keys().cloned().collect();
+    workspaces.retain(|_, entry| match &entry.parent_id {
+        Some(parent_id) => ids.contains(parent_id),
+        None => true,
+    });
+    Ok((settings, workspaces))
+}
+
+pub(crate) fn read_or_create_runner_id(path: &PathBuf) -> String {
+    if let Ok(existing) = std::fs::read_to_string(path) {
+        let trimmed = existing.trim();
+        if !trimmed.is_empty() {
+            return trimmed.to_string();
+        }
+    }
+    let id = uuid::Uuid::new_v4().to_string();
     if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        let _ = std::fs::create_dir_all(parent);
     }
-    let data = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
-    std::fs::write(path, data).map_err(|e| e.to_string())
+    let _ = std::fs::write(path, &id);
+    id
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{read_workspaces, write_workspaces};
-    use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+    use super::{
+        apply_config_bundle, apply_workspace_reorder, build_config_bundle, read_settings,
+        read_workspaces, replaced_workspace_ids, write_settings, write_workspaces,
+    };
+    use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+    use std::collections::HashMap;
     use uuid::Uuid;
 
     #[test]
@@ -77,4 +336,120 @@ mod tests {
         assert!(stored.settings.sidebar_collapsed);
         assert_eq!(stored.settings.git_root.as_deref(), Some("/tmp"));
     }
+
+    #[test]
+    fn truncated_settings_recover_from_backup() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        let path = temp_dir.join("settings.json");
+
+        let first = AppSettings {
+            ui_scale: 1.5,
+            ..AppSettings::default()
+        };
+        write_settings(&path, &first).expect("write first settings");
+        let second = AppSettings {
+            ui_scale: 2.0,
+            ..AppSettings::default()
+        };
+        write_settings(&path, &second).expect("write second settings");
+        assert!(!temp_dir.join("settings.json.tmp").exists());
+
+        let data = std::fs::read_to_string(&path).expect("read settings");
+        std::fs::write(&path, &data[..data.len() / 2]).expect("truncate settings");
+
+        let recovered = read_settings(&path).expect("recover settings");
+        assert!((recovered.ui_scale - 1.5).abs() < f64::EPSILON);
+
+        // A corrupt file must not overwrite the last good backup.
+        write_settings(&path, &second).expect("write after corruption");
+        std::fs::write(&path, "{").expect("corrupt settings");
+        let recovered = read_settings(&path).expect("recover settings again");
+        assert!((recovered.ui_scale - 1.5).abs() < f64::EPSILON);
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    fn workspace(id: &str, path: &str) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }
+    }
+
+    #[test]
+    fn reorder_workspaces_assigns_sequential_order_and_skips_worktrees() {
+        let entry = |id: &str, kind: WorkspaceKind, sort_order: Option<u32>| {
+            let mut entry = workspace(id, "/tmp");
+            entry.kind = kind;
+            entry.settings.sort_order = sort_order;
+            (id.to_string(), entry)
+        };
+        let mut workspaces = HashMap::from([
+            entry("a", WorkspaceKind::Main, Some(0)),
+            entry("b", WorkspaceKind::Main, Some(1)),
+            entry("c", WorkspaceKind::Main, Some(2)),
+            entry("d", WorkspaceKind::Main, Some(1)),
+            entry("wt", WorkspaceKind::Worktree, Some(7)),
+        ]);
+        workspaces.get_mut("d").expect("d").name = "b".to_string();
+
+        apply_workspace_reorder(
+            &mut workspaces,
+            &["c".to_string(), "missing".to_string(), "wt".to_string(), "a".to_string()],
+        );
+
+        let order = |id: &str| workspaces.get(id).and_then(|entry| entry.settings.sort_order);
+        assert_eq!(order("c"), Some(0));
+        assert_eq!(order("a"), Some(1));
+        assert_eq!(order("b"), Some(2));
+        assert_eq!(order("d"), Some(3));
+        assert_eq!(order("wt"), Some(7));
+    }
+
+    #[test]
+    fn config_bundle_round_trip_redacts_and_merges() {
+        let local_settings = AppSettings {
+            remote_backend_token: Some("secret".to_string()),
+            ..AppSettings::default()
+        };
+        let mut local_workspaces = HashMap::new();
+        local_workspaces.insert("w1".to_string(), workspace("w1", "/repo/one"));
+
+        let bundle = build_config_bundle(&local_settings, &local_workspaces, false, 0);
+        assert!(bundle.settings.remote_backend_token.is_none());
+
+        let (settings, _) =
+            apply_config_bundle(&local_settings, &HashMap::new(), bundle, false).expect("replace");
+        assert_eq!(settings.remote_backend_token.as_deref(), Some("secret"));
+
+        let mut remote_workspaces = HashMap::new();
+        remote_workspaces.insert("w2".to_string(), workspace("w2", "/repo/one"));
+        remote_workspaces.insert("w3".to_string(), workspace("w3", "/repo/three"));
+        let mut orphan = workspace("wt", "/repo/wt");
+        orphan.parent_id = Some("missing".to_string());
+        remote_workspaces.insert("wt".to_string(), orphan);
+        let bundle = build_config_bundle(&AppSettings::default(), &remote_workspaces, true, 0);
+        let (_, workspaces) =
+            apply_config_bundle(&local_settings, &local_workspaces, bundle, true).expect("merge");
+        let mut ids: Vec<&str> = workspaces.keys().map(String::as_str).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["w1", "w3"]);
+
+        let mut invalid = build_config_bundle(&AppSettings::default(), &remote_workspaces, true, 0);
+        invalid.workspaces[0].settings.codex_args = Some(vec!["app-server".to_string()]);
+        assert!(apply_config_bundle(&local_settings, &local_workspaces, invalid, false).is_err());
+
+        assert!(replaced_workspace_ids(&local_workspaces, &local_workspaces).is_empty());
+        let mut moved = local_workspaces.clone();
+        moved.get_mut("w1").expect("w1").path = "/repo/moved".to_string();
+        moved.insert("w4".to_string(), workspace("w4", "/repo/four"));
+        assert_eq!(replaced_workspace_ids(&local_workspaces, &moved), vec!["w1"]);
+        assert_eq!(replaced_workspace_ids(&local_workspaces, &HashMap::new()), vec!["w1"]);
+    }
 }
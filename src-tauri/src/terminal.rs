@@ -1,13 +1,14 @@
+use std::collections::VecDeque;
 use std::io::{Read, Write};
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, State};
 use tokio::sync::Mutex;
 
-use crate::backend::events::{EventSink, TerminalOutput};
+use crate::backend::events::{EventSink, TerminalExit, TerminalOutput};
 use crate::event_sink::TauriEventSink;
 use crate::state::AppState;
 
@@ -16,11 +17,85 @@ pub(crate) struct TerminalSession {
     pub(crate) master: Mutex<Box<dyn portable_pty::MasterPty + Send>>,
     pub(crate) writer: Mutex<Box<dyn Write + Send>>,
     pub(crate) child: Mutex<Box<dyn portable_pty::Child + Send>>,
+    pub(crate) size: Mutex<(u16, u16)>,
+    pub(crate) scrollback: Arc<std::sync::Mutex<Scrollback>>,
+}
+
+/// Bounded buffer of the most recent PTY output, replayed to reconnecting UIs.
+pub(crate) struct Scrollback {
+    data: VecDeque<u8>,
+    capacity: usize,
+}
+
+impl Scrollback {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            data: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        if self.capacity == 0 {
+            return;
+        }
+        let bytes = if bytes.len() > self.capacity {
+            &bytes[bytes.len() - self.capacity..]
+        } else {
+            bytes
+        };
+        let overflow = (self.data.len() + bytes.len()).saturating_sub(self.capacity);
+        self.data.drain(..overflow);
+        self.data.extend(bytes);
+        // A cut can land inside a multi-byte character; drop its tail so the
+        // snapshot starts on a character boundary.
+        while self.data.front().is_some_and(|byte| byte & 0xC0 == 0x80) {
+            self.data.pop_front();
+        }
+    }
+
+    fn snapshot(&self) -> String {
+        let (front, back) = self.data.as_slices();
+        let mut bytes = Vec::with_capacity(self.data.len());
+        bytes.extend_from_slice(front);
+        bytes.extend_from_slice(back);
+        String::from_utf8_lossy(&bytes).to_string()
+    }
+}
+
+/// Optional overrides for a new terminal session.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct TerminalOpenOptions {
+    #[serde(default)]
+    shell: Option<String>,
+    /// Working directory, relative to the workspace root when not absolute.
+    #[serde(default)]
+    cwd: Option<String>,
+    /// Typed into the shell right after it starts.
+    #[serde(default, rename = "initialCommand")]
+    initial_command: Option<String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
 pub(crate) struct TerminalSessionInfo {
     id: String,
+    cols: u16,
+    rows: u16,
+    alive: bool,
+}
+
+async fn session_info(session: &TerminalSession) -> TerminalSessionInfo {
+    let (cols, rows) = *session.size.lock().await;
+    let alive = {
+        let mut child = session.child.lock().await;
+        matches!(child.try_wait(), Ok(None))
+    };
+    TerminalSessionInfo {
+        id: session.id.clone(),
+        cols,
+        rows,
+        alive,
+    }
 }
 
 fn terminal_key(workspace_id: &str, terminal_id: &str) -> String {
@@ -31,11 +106,57 @@ fn shell_path() -> String {
     std::env::var("SHELL").unwrap_or_else(|_| "/bin/zsh".to_string())
 }
 
+fn resolve_shell(shell: Option<String>) -> Result<PathBuf, String> {
+    let shell = shell
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(shell_path);
+    let candidate = PathBuf::from(&shell);
+    if candidate.components().count() > 1 {
+        return if candidate.is_file() {
+            Ok(candidate)
+        } else {
+            Err(format!("Shell not found: {shell}"))
+        };
+    }
+    std::env::var_os("PATH")
+        .and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join(&shell))
+                .find(|path| path.is_file())
+        })
+        .ok_or_else(|| format!("Shell not found on PATH: {shell}"))
+}
+
+fn resolve_cwd(workspace_path: PathBuf, cwd: Option<String>) -> Result<PathBuf, String> {
+    let Some(cwd) = cwd
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+    else {
+        return Ok(workspace_path);
+    };
+    let candidate = workspace_path.join(&cwd);
+    if candidate.is_dir() {
+        Ok(candidate)
+    } else {
+        Err(format!("Working directory not found: {cwd}"))
+    }
+}
+
+async fn write_initial_command(session: &TerminalSession, command: &str) -> Result<(), String> {
+    let mut writer = session.writer.lock().await;
+    writer
+        .write_all(format!("{command}\n").as_bytes())
+        .and_then(|_| writer.flush())
+        .map_err(|e| format!("Failed to run initial command: {e}"))
+}
+
 fn spawn_terminal_reader(
     event_sink: impl EventSink,
     workspace_id: String,
     terminal_id: String,
     mut reader: Box<dyn Read + Send>,
+    scrollback: Arc<std::sync::Mutex<Scrollback>>,
 ) {
     std::thread::spawn(move || {
         let mut buffer = [0u8; 8192];
@@ -43,6 +164,9 @@ fn spawn_terminal_reader(
             match reader.read(&mut buffer) {
                 Ok(0) => break,
                 Ok(count) => {
+                    if let Ok(mut scrollback) = scrollback.lock() {
+                        scrollback.push(&buffer[..count]);
+                    }
                     let data = String::from_utf8_lossy(&buffer[..count]).to_string();
                     let payload = TerminalOutput {
                         workspace_id: workspace_id.clone(),
@@ -54,6 +178,10 @@ fn spawn_terminal_reader(
                 Err(_) => break,
             }
         }
+        event_sink.emit_terminal_exit(TerminalExit {
+            workspace_id,
+            terminal_id,
+        });
     });
 }
 
@@ -74,6 +202,7 @@ pub(crate) async fn terminal_open(
     terminal_id: String,
     cols: u16,
     rows: u16,
+    options: Option<TerminalOpenOptions>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<TerminalSessionInfo, String> {
@@ -84,13 +213,14 @@ pub(crate) async fn terminal_open(
     {
         let sessions = state.terminal_sessions.lock().await;
         if let Some(existing) = sessions.get(&key) {
-            return Ok(TerminalSessionInfo {
-                id: existing.id.clone(),
-            });
+            return Ok(session_info(existing).await);
         }
     }
 
-    let cwd = get_workspace_path(&workspace_id, &state).await?;
+    let options = options.unwrap_or_default();
+    let workspace_path = get_workspace_path(&workspace_id, &state).await?;
+    let cwd = resolve_cwd(workspace_path, options.cwd)?;
+    let shell = resolve_shell(options.shell)?;
     let pty_system = native_pty_system();
     let size = PtySize {
         rows: rows.max(2),
@@ -102,10 +232,11 @@ pub(crate) async fn terminal_open(
         .openpty(size)
         .map_err(|e| format!("Failed to open pty: {e}"))?;
 
-    let mut cmd = CommandBuilder::new(shell_path());
-    cmd.cwd(cwd);
+    let mut cmd = CommandBuilder::new(shell);
+    cmd.cwd(&cwd);
     cmd.arg("-i");
     cmd.env("TERM", "xterm-256color");
+    cmd.env("PWD", &cwd);
 
     let child = pair
         .slave
@@ -120,31 +251,44 @@ pub(crate) async fn terminal_open(
         .take_writer()
         .map_err(|e| format!("Failed to open pty writer: {e}"))?;
 
+    let scrollback_capacity = state.app_settings.lock().await.terminal_scrollback_bytes;
+    let scrollback = Arc::new(std::sync::Mutex::new(Scrollback::new(scrollback_capacity)));
     let session = Arc::new(TerminalSession {
         id: terminal_id.clone(),
         master: Mutex::new(pair.master),
         writer: Mutex::new(writer),
         child: Mutex::new(child),
+        size: Mutex::new((size.cols, size.rows)),
+        scrollback: Arc::clone(&scrollback),
     });
-    let session_id = session.id.clone();
+    let info = session_info(&session).await;
 
     {
         let mut sessions = state.terminal_sessions.lock().await;
         if let Some(existing) = sessions.get(&key) {
             let mut child = session.child.lock().await;
             let _ = child.kill();
-            return Ok(TerminalSessionInfo {
-                id: existing.id.clone(),
-            });
+            return Ok(session_info(existing).await);
         }
-        sessions.insert(key, session);
+        sessions.insert(key.clone(), Arc::clone(&session));
     }
     let event_sink = TauriEventSink::new(app);
-    spawn_terminal_reader(event_sink, workspace_id, terminal_id, reader);
+    spawn_terminal_reader(event_sink, workspace_id, terminal_id, reader, scrollback);
+
+    if let Some(command) = options
+        .initial_command
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+    {
+        if let Err(error) = write_initial_command(&session, &command).await {
+            // Don't leave a running PTY behind a failed open.
+            state.terminal_sessions.lock().await.remove(&key);
+            let _ = session.child.lock().await.kill();
+            return Err(error);
+        }
+    }
 
-    Ok(TerminalSessionInfo {
-        id: session_id,
-    })
+    Ok(info)
 }
 
 #[tauri::command]
@@ -192,9 +336,50 @@ pub(crate) async fn terminal_resize(
     master
         .resize(size)
         .map_err(|e| format!("Failed to resize pty: {e}"))?;
+    *session.size.lock().await = (size.cols, size.rows);
     Ok(())
 }
 
+#[tauri::command]
+pub(crate) async fn terminal_snapshot(
+    workspace_id: String,
+    terminal_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let key = terminal_key(&workspace_id, &terminal_id);
+    let sessions = state.terminal_sessions.lock().await;
+    let session = sessions
+        .get(&key)
+        .ok_or_else(|| "Terminal session not found".to_string())?;
+    let scrollback = session
+        .scrollback
+        .lock()
+        .map_err(|_| "Terminal scrollback unavailable".to_string())?;
+    Ok(scrollback.snapshot())
+}
+
+#[tauri::command]
+pub(crate) async fn terminal_list(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<TerminalSessionInfo>, String> {
+    let prefix = terminal_key(&workspace_id, "");
+    let sessions: Vec<Arc<TerminalSession>> = {
+        let sessions = state.terminal_sessions.lock().await;
+        sessions
+            .iter()
+            .filter(|(key, _)| key.starts_with(&prefix))
+            .map(|(_, session)| Arc::clone(session))
+            .collect()
+    };
+    let mut infos = Vec::with_capacity(sessions.len());
+    for session in sessions {
+        infos.push(session_info(&session).await);
+    }
+    infos.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(infos)
+}
+
 #[tauri::command]
 pub(crate) async fn terminal_close(
     workspace_id: String,
@@ -210,3 +395,37 @@ pub(crate) async fn terminal_close(
     let _ = child.kill();
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Scrollback;
+
+    #[test]
+    fn scrollback_keeps_only_most_recent_bytes() {
+        let mut scrollback = Scrollback::new(8);
+        scrollback.push(b"hello ");
+        scrollback.push(b"world");
+        assert_eq!(scrollback.snapshot(), "lo world");
+
+        scrollback.push(b"0123456789");
+        assert_eq!(scrollback.snapshot(), "23456789");
+    }
+
+    #[test]
+    fn scrollback_trims_to_a_character_boundary() {
+        let mut scrollback = Scrollback::new(4);
+        scrollback.push("aé".as_bytes());
+        scrollback.push("€".as_bytes());
+        assert_eq!(scrollback.snapshot(), "€");
+
+        scrollback.push("xé".as_bytes());
+        assert_eq!(scrollback.snapshot(), "xé");
+    }
+
+    #[test]
+    fn scrollback_with_zero_capacity_stores_nothing() {
+        let mut scrollback = Scrollback::new(0);
+        scrollback.push(b"ignored");
+        assert_eq!(scrollback.snapshot(), "");
+    }
+}
@@ -1,3 +1,6 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -8,6 +11,19 @@ pub(crate) struct GitFileStatus {
     pub(crate) deletions: i64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitDiffStats {
+    pub(crate) files: Vec<GitFileStatus>,
+    #[serde(rename = "stagedFiles")]
+    pub(crate) staged_files: Vec<GitFileStatus>,
+    #[serde(rename = "unstagedFiles")]
+    pub(crate) unstaged_files: Vec<GitFileStatus>,
+    #[serde(rename = "totalAdditions")]
+    pub(crate) total_additions: i64,
+    #[serde(rename = "totalDeletions")]
+    pub(crate) total_deletions: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitFileDiff {
     pub(crate) path: String,
@@ -38,6 +54,60 @@ pub(crate) struct GitLogResponse {
     pub(crate) upstream: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitSyncResponse {
+    pub(crate) ahead: usize,
+    pub(crate) behind: usize,
+    pub(crate) upstream: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitPushResponse {
+    pub(crate) remote: String,
+    #[serde(rename = "remoteUrl")]
+    pub(crate) remote_url: Option<String>,
+    pub(crate) upstream: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct GitConflictRange {
+    #[serde(rename = "startLine")]
+    pub(crate) start_line: usize,
+    #[serde(rename = "endLine")]
+    pub(crate) end_line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitConflictFile {
+    pub(crate) path: String,
+    pub(crate) status: String,
+    pub(crate) markers: Vec<GitConflictRange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct GitBlameLine {
+    pub(crate) sha: String,
+    pub(crate) author: String,
+    pub(crate) timestamp: i64,
+    pub(crate) summary: String,
+    pub(crate) line: usize,
+    pub(crate) content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitBlameResponse {
+    pub(crate) lines: Vec<GitBlameLine>,
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct GitProgressEvent {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    pub(crate) operation: String,
+    pub(crate) line: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitHubIssue {
     pub(crate) number: u64,
@@ -51,6 +121,8 @@ pub(crate) struct GitHubIssue {
 pub(crate) struct GitHubIssuesResponse {
     pub(crate) total: usize,
     pub(crate) issues: Vec<GitHubIssue>,
+    #[serde(default, rename = "fetchedAtMs")]
+    pub(crate) fetched_at_ms: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -83,6 +155,8 @@ pub(crate) struct GitHubPullRequestsResponse {
     pub(crate) total: usize,
     #[serde(rename = "pullRequests")]
     pub(crate) pull_requests: Vec<GitHubPullRequest>,
+    #[serde(default, rename = "fetchedAtMs")]
+    pub(crate) fetched_at_ms: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -144,9 +218,309 @@ pub(crate) struct LocalUsageSnapshot {
     pub(crate) top_models: Vec<LocalUsageModel>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorkspaceFileListing {
+    pub(crate) files: Vec<String>,
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorkspaceFileContent {
+    pub(crate) content: String,
+    pub(crate) truncated: bool,
+    #[serde(rename = "isBinary")]
+    pub(crate) is_binary: bool,
+    pub(crate) size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorkspaceSearchMatch {
+    pub(crate) path: String,
+    pub(crate) line: u64,
+    pub(crate) column: u64,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorkspaceSearchResult {
+    pub(crate) matches: Vec<WorkspaceSearchMatch>,
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorkspaceConnectResult {
+    pub(crate) ok: bool,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+impl From<Result<(), String>> for WorkspaceConnectResult {
+    fn from(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => Self {
+                ok: true,
+                error: None,
+            },
+            Err(error) => Self {
+                ok: false,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct WorkspaceHealth {
+    pub(crate) connected: bool,
+    #[serde(rename = "codexPid")]
+    pub(crate) codex_pid: Option<u32>,
+    #[serde(rename = "uptimeSecs")]
+    pub(crate) uptime_secs: u64,
+    #[serde(rename = "lastEventMs")]
+    pub(crate) last_event_ms: Option<i64>,
+    #[serde(rename = "pendingRequests")]
+    pub(crate) pending_requests: usize,
+}
+
+/// Request counters for one codex session since it started.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub(crate) struct SessionMetrics {
+    #[serde(rename = "requestsSent")]
+    pub(crate) requests_sent: u64,
+    #[serde(rename = "responsesReceived")]
+    pub(crate) responses_received: u64,
+    /// Responses carrying an `error`, plus requests that could not be written.
+    pub(crate) errors: u64,
+    pub(crate) timeouts: u64,
+    /// Moving average over recent responses; `None` before the first one.
+    #[serde(rename = "avgLatencyMs")]
+    pub(crate) avg_latency_ms: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct ModelInfo {
+    pub(crate) id: String,
+    pub(crate) model: String,
+    #[serde(rename = "displayName")]
+    pub(crate) display_name: String,
+    pub(crate) description: String,
+    #[serde(rename = "supportedEfforts")]
+    pub(crate) supported_efforts: Vec<String>,
+    #[serde(rename = "defaultEffort")]
+    pub(crate) default_effort: Option<String>,
+    #[serde(rename = "isDefault")]
+    pub(crate) is_default: bool,
+    #[serde(rename = "supportsEffort")]
+    pub(crate) supports_effort: bool,
+    #[serde(rename = "contextWindow")]
+    pub(crate) context_window: Option<u64>,
+    #[serde(rename = "supportsImages")]
+    pub(crate) supports_images: Option<bool>,
+}
+
+impl ModelInfo {
+    /// Parses one `model/list` entry, accepting camelCase or snake_case keys.
+    pub(crate) fn from_value(value: &serde_json::Value) -> Option<Self> {
+        let field = |camel: &str, snake: &str| value.get(camel).or_else(|| value.get(snake));
+        let text = |camel: &str, snake: &str| {
+            field(camel, snake)
+                .and_then(serde_json::Value::as_str)
+                .map(|value| value.to_string())
+        };
+        let model = text("model", "model").or_else(|| text("id", "id"))?;
+        let supported_efforts: Vec<String> =
+            field("supportedReasoningEfforts", "supported_reasoning_efforts")
+                .and_then(serde_json::Value::as_array)
+                .map(|efforts| {
+                    efforts
+                        .iter()
+                        .filter_map(|effort| {
+                            effort
+                                .get("reasoningEffort")
+                                .or_else(|| effort.get("reasoning_effort"))
+                                .or(Some(effort))
+                                .and_then(serde_json::Value::as_str)
+                                .map(|value| value.to_string())
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+        let supports_images = field("supportsImages", "supports_images")
+            .and_then(serde_json::Value::as_bool)
+            .or_else(|| {
+                field("inputModalities", "input_modalities")
+                    .and_then(serde_json::Value::as_array)
+                    .map(|modalities| {
+                        modalities
+                            .iter()
+                            .any(|modality| modality.as_str() == Some("image"))
+                    })
+            });
+        Some(Self {
+            id: text("id", "id").unwrap_or_else(|| model.clone()),
+            display_name: text("displayName", "display_name").unwrap_or_else(|| model.clone()),
+            description: text("description", "description").unwrap_or_default(),
+            supports_effort: !supported_efforts.is_empty(),
+            supported_efforts,
+            default_effort: text("defaultReasoningEffort", "default_reasoning_effort")
+                .filter(|value| !value.is_empty()),
+            is_default: field("isDefault", "is_default")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+            context_window: field("contextWindow", "context_window")
+                .and_then(serde_json::Value::as_u64),
+            supports_images,
+            model,
+        })
+    }
+
+    /// Parses the `data` array of a `model/list` response.
+    pub(crate) fn list_from_response(response: &serde_json::Value) -> Vec<Self> {
+        let result = response.get("result").unwrap_or(response);
+        result
+            .get("data")
+            .and_then(serde_json::Value::as_array)
+            .map(|entries| entries.iter().filter_map(Self::from_value).collect())
+            .unwrap_or_default()
+    }
+
+    /// Rejects an effort the named model does not advertise. Unknown models
+    /// pass, since the list may be stale or incomplete.
+    pub(crate) fn check_effort(models: &[Self], model: &str, effort: &str) -> Result<(), String> {
+        let Some(info) = models
+            .iter()
+            .find(|info| info.model == model || info.id == model)
+        else {
+            return Ok(());
+        };
+        if info.supported_efforts.iter().any(|value| value == effort) {
+            return Ok(());
+        }
+        if info.supports_effort {
+            Err(format!(
+                "Model {model} does not support effort `{effort}` (supported: {})",
+                info.supported_efforts.join(", ")
+            ))
+        } else {
+            Err(format!("Model {model} does not support reasoning effort"))
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TurnUsage {
+    #[serde(rename = "inputTokens")]
+    pub(crate) input_tokens: u64,
+    #[serde(rename = "outputTokens")]
+    pub(crate) output_tokens: u64,
+    pub(crate) total: u64,
+}
+
+impl TurnUsage {
+    /// Parses a codex token breakdown, accepting camelCase or snake_case keys.
+    pub(crate) fn from_value(value: &serde_json::Value) -> Option<Self> {
+        let read = |camel: &str, snake: &str| {
+            value
+                .get(camel)
+                .or_else(|| value.get(snake))
+                .and_then(serde_json::Value::as_u64)
+        };
+        let input_tokens = read("inputTokens", "input_tokens");
+        let output_tokens = read("outputTokens", "output_tokens");
+        let total = read("totalTokens", "total_tokens");
+        if input_tokens.is_none() && output_tokens.is_none() && total.is_none() {
+            return None;
+        }
+        let input_tokens = input_tokens.unwrap_or(0);
+        let output_tokens = output_tokens.unwrap_or(0);
+        Some(Self {
+            input_tokens,
+            output_tokens,
+            total: total.unwrap_or(input_tokens + output_tokens),
+        })
+    }
+
+    pub(crate) fn saturating_sub(self, other: Self) -> Self {
+        Self {
+            input_tokens: self.input_tokens.saturating_sub(other.input_tokens),
+            output_tokens: self.output_tokens.saturating_sub(other.output_tokens),
+            total: self.total.saturating_sub(other.total),
+        }
+    }
+
+    pub(crate) fn accumulate(&mut self, other: Self) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.total += other.total;
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct TurnCompletion {
+    #[serde(rename = "threadId")]
+    pub(crate) thread_id: String,
+    #[serde(rename = "turnId")]
+    pub(crate) turn_id: Option<String>,
+    pub(crate) text: String,
+    #[serde(rename = "tokenUsage")]
+    pub(crate) token_usage: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct ThreadSummary {
+    pub(crate) id: String,
+    pub(crate) preview: String,
+    pub(crate) cwd: String,
+    #[serde(rename = "updatedAt")]
+    pub(crate) updated_at: i64,
+    #[serde(default)]
+    pub(crate) pinned: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct ThreadSummaryPage {
+    pub(crate) threads: Vec<ThreadSummary>,
+    #[serde(rename = "nextCursor")]
+    pub(crate) next_cursor: Option<String>,
+}
+
+/// A request codex sent to the client, such as an approval prompt, that has
+/// not been answered yet.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct PendingServerRequest {
+    #[serde(rename = "requestId")]
+    pub(crate) request_id: u64,
+    pub(crate) method: String,
+    #[serde(rename = "threadId")]
+    pub(crate) thread_id: Option<String>,
+    pub(crate) description: String,
+    pub(crate) params: serde_json::Value,
+    #[serde(rename = "receivedAtMs")]
+    pub(crate) received_at_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct RunnerInfo {
+    #[serde(rename = "runnerId")]
+    pub(crate) runner_id: String,
+    pub(crate) version: String,
+    pub(crate) platform: String,
+    pub(crate) os: String,
+    pub(crate) hostname: Option<String>,
+    #[serde(rename = "startedAtMs")]
+    pub(crate) started_at_ms: i64,
+    #[serde(rename = "workspaceCount")]
+    pub(crate) workspace_count: usize,
+    #[serde(rename = "connectedCount")]
+    pub(crate) connected_count: usize,
+    #[serde(rename = "cloudProvider")]
+    pub(crate) cloud_provider: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct BranchInfo {
     pub(crate) name: String,
+    #[serde(rename = "lastCommit")]
     pub(crate) last_commit: i64,
 }
 
@@ -166,12 +540,26 @@ pub(crate) struct WorkspaceEntry {
     pub(crate) settings: WorkspaceSettings,
 }
 
+/// Portable snapshot of app settings and the workspace list.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct ConfigBundle {
+    pub(crate) version: u32,
+    #[serde(default, rename = "exportedAtMs")]
+    pub(crate) exported_at_ms: i64,
+    pub(crate) settings: AppSettings,
+    #[serde(default)]
+    pub(crate) workspaces: Vec<WorkspaceEntry>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct WorkspaceInfo {
     pub(crate) id: String,
     pub(crate) name: String,
     pub(crate) path: String,
     pub(crate) connected: bool,
+    /// Whether the file watcher is running. The UI polls while it is not.
+    #[serde(default)]
+    pub(crate) watching: bool,
     pub(crate) codex_bin: Option<String>,
     #[serde(default)]
     pub(crate) kind: WorkspaceKind,
@@ -202,6 +590,64 @@ impl WorkspaceKind {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WorkspaceKindFilter {
+    #[default]
+    All,
+    Main,
+    Worktree,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WorkspaceSort {
+    /// The sidebar order: `sortOrder`, then name.
+    #[default]
+    SortOrder,
+    Name,
+    /// Most recent codex event first; workspaces without a session go last.
+    LastActivity,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct WorkspaceListOptions {
+    #[serde(default, rename = "kindFilter")]
+    pub(crate) kind_filter: WorkspaceKindFilter,
+    #[serde(default, rename = "connectedOnly")]
+    pub(crate) connected_only: bool,
+    #[serde(default)]
+    pub(crate) sort: WorkspaceSort,
+}
+
+impl WorkspaceListOptions {
+    /// Filters and reorders a list that is already in sidebar order.
+    /// `last_activity` maps workspace ids to their latest codex event time.
+    pub(crate) fn apply(
+        &self,
+        list: &mut Vec<WorkspaceInfo>,
+        last_activity: &HashMap<String, i64>,
+    ) {
+        list.retain(|workspace| {
+            let kind_matches = match self.kind_filter {
+                WorkspaceKindFilter::All => true,
+                WorkspaceKindFilter::Main => !workspace.kind.is_worktree(),
+                WorkspaceKindFilter::Worktree => workspace.kind.is_worktree(),
+            };
+            kind_matches && (workspace.connected || !self.connected_only)
+        });
+        match self.sort {
+            WorkspaceSort::SortOrder => {}
+            WorkspaceSort::Name => {
+                list.sort_by_cached_key(|workspace| workspace.name.to_lowercase());
+            }
+            WorkspaceSort::LastActivity => {
+                list.sort_by_key(|workspace| Reverse(last_activity.get(&workspace.id).copied()));
+            }
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct WorktreeInfo {
     pub(crate) branch: String,
@@ -227,12 +673,115 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) group_id: Option<String>,
     #[serde(default, rename = "gitRoot")]
     pub(crate) git_root: Option<String>,
+    #[serde(default, rename = "pinnedThreads")]
+    pub(crate) pinned_threads: Vec<String>,
+    /// Silences completion notifications for this workspace only.
+    #[serde(default)]
+    pub(crate) muted: bool,
+    #[serde(default, rename = "autoApprove")]
+    pub(crate) auto_approve: AutoApprovePolicy,
+    /// Most permissive access mode any turn in this workspace may use,
+    /// whatever the client asks for.
+    #[serde(default, rename = "maxAccessMode")]
+    pub(crate) max_access_mode: Option<String>,
+    /// Watch the workspace folder while connected and emit
+    /// `workspace-files-changed` events.
+    #[serde(default, rename = "watchFiles")]
+    pub(crate) watch_files: bool,
+    /// Replaces the global `codexArgs` for this workspace when set.
+    #[serde(default, rename = "codexArgs")]
+    pub(crate) codex_args: Option<Vec<String>>,
+}
+
+/// Which codex approval requests a workspace answers on its own. Anything
+/// the policy does not cover is still shown to the user.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(tag = "mode", content = "patterns", rename_all = "camelCase")]
+pub(crate) enum AutoApprovePolicy {
+    #[default]
+    Never,
+    ReadOnlyCommands,
+    MatchingPatterns(Vec<String>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub(crate) struct ThreadDefaults {
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+    #[serde(default, rename = "accessMode")]
+    pub(crate) access_mode: Option<String>,
+}
+
+impl ThreadDefaults {
+    /// Fills omitted fields from the stored defaults and records any
+    /// explicitly provided values for the next turn.
+    pub(crate) fn resolve(
+        &mut self,
+        model: Option<String>,
+        effort: Option<String>,
+        access_mode: Option<String>,
+    ) -> ThreadDefaults {
+        if model.is_some() {
+            self.model = model;
+        }
+        if effort.is_some() {
+            self.effort = effort;
+        }
+        if access_mode.is_some() {
+            self.access_mode = access_mode;
+        }
+        self.clone()
+    }
+
+    /// Resolves the settings for a turn, but only stores them once the
+    /// model/effort pair passes `ModelInfo::check_effort`, so a rejected
+    /// override cannot break every later send on the thread.
+    pub(crate) fn resolve_for_turn(
+        &mut self,
+        model: Option<String>,
+        effort: Option<String>,
+        access_mode: Option<String>,
+        models: Option<&[ModelInfo]>,
+    ) -> Result<ThreadDefaults, String> {
+        let resolved = self.clone().resolve(model, effort, access_mode);
+        if let (Some(models), Some(model), Some(effort)) =
+            (models, resolved.model.as_deref(), resolved.effort.as_deref())
+        {
+            ModelInfo::check_effort(models, model, effort)?;
+        }
+        *self = resolved.clone();
+        Ok(resolved)
+    }
+}
+
+/// Optional per-turn settings for `send_user_message`. The daemon reads them
+/// from the top level of the request params.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub(crate) struct TurnOptions {
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+    #[serde(default, rename = "accessMode")]
+    pub(crate) access_mode: Option<String>,
+    #[serde(default)]
+    pub(crate) images: Option<Vec<String>>,
+    #[serde(default, rename = "collaborationMode")]
+    pub(crate) collaboration_mode: Option<serde_json::Value>,
+    /// Client-chosen id that lets the turn be cancelled before it starts.
+    #[serde(default, rename = "requestId")]
+    pub(crate) request_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct AppSettings {
     #[serde(default, rename = "codexBin")]
     pub(crate) codex_bin: Option<String>,
+    /// Extra arguments appended after `codex app-server`.
+    #[serde(default, rename = "codexArgs")]
+    pub(crate) codex_args: Vec<String>,
     #[serde(default, rename = "backendMode")]
     pub(crate) backend_mode: BackendMode,
     #[serde(default = "default_remote_backend_host", rename = "remoteBackendHost")]
@@ -300,6 +849,32 @@ pub(crate) struct AppSettings {
     pub(crate) dictation_hold_key: String,
     #[serde(default = "default_workspace_groups", rename = "workspaceGroups")]
     pub(crate) workspace_groups: Vec<WorkspaceGroup>,
+    #[serde(
+        default = "default_github_cache_ttl_secs",
+        rename = "githubCacheTtlSecs"
+    )]
+    pub(crate) github_cache_ttl_secs: u64,
+    /// Deadline for local git commands, and how long fetch, pull and push
+    /// may go without printing progress before they are killed.
+    #[serde(default = "default_git_timeout_secs", rename = "gitTimeoutSecs")]
+    pub(crate) git_timeout_secs: u64,
+    /// Bytes of output kept per terminal for replay, at most
+    /// `TERMINAL_SCROLLBACK_MAX_BYTES` (16 MiB).
+    #[serde(default = "default_terminal_scrollback_bytes", rename = "terminalScrollbackBytes")]
+    pub(crate) terminal_scrollback_bytes: usize,
+    #[serde(default, rename = "preferredEditor")]
+    pub(crate) preferred_editor: Option<String>,
+    #[serde(
+        default = "default_desktop_notifications_enabled",
+        rename = "desktopNotificationsEnabled"
+    )]
+    pub(crate) desktop_notifications_enabled: bool,
+    #[serde(default, rename = "dndStart")]
+    pub(crate) dnd_start: Option<String>,
+    #[serde(default, rename = "dndEnd")]
+    pub(crate) dnd_end: Option<String>,
+    #[serde(default = "default_local_api_enabled", rename = "localApiEnabled")]
+    pub(crate) local_api_enabled: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -375,10 +950,31 @@ fn default_workspace_groups() -> Vec<WorkspaceGroup> {
     Vec::new()
 }
 
+fn default_github_cache_ttl_secs() -> u64 {
+    60
+}
+
+fn default_git_timeout_secs() -> u64 {
+    60
+}
+
+fn default_terminal_scrollback_bytes() -> usize {
+    256 * 1024
+}
+
+fn default_desktop_notifications_enabled() -> bool {
+    false
+}
+
+fn default_local_api_enabled() -> bool {
+    false
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
             codex_bin: None,
+            codex_args: Vec::new(),
             backend_mode: BackendMode::Local,
             remote_backend_host: default_remote_backend_host(),
             remote_backend_token: None,
@@ -399,14 +995,182 @@ impl Default for AppSettings {
             dictation_preferred_language: None,
             dictation_hold_key: default_dictation_hold_key(),
             workspace_groups: default_workspace_groups(),
+            github_cache_ttl_secs: default_github_cache_ttl_secs(),
+            git_timeout_secs: default_git_timeout_secs(),
+            terminal_scrollback_bytes: default_terminal_scrollback_bytes(),
+            preferred_editor: None,
+            desktop_notifications_enabled: default_desktop_notifications_enabled(),
+            dnd_start: None,
+            dnd_end: None,
+            local_api_enabled: default_local_api_enabled(),
         }
     }
 }
 
+const UI_SCALE_MIN: f64 = 0.1;
+const UI_SCALE_MAX: f64 = 3.0;
+const GIT_TIMEOUT_MIN_SECS: u64 = 5;
+const GIT_TIMEOUT_MAX_SECS: u64 = 3600;
+/// Every open terminal keeps up to this much output in memory.
+const TERMINAL_SCROLLBACK_MAX_BYTES: usize = 16 * 1024 * 1024;
+const ACCESS_MODES: [&str; 3] = ["read-only", "current", "full-access"];
+const THEMES: [&str; 3] = ["system", "light", "dark"];
+
+pub(crate) fn validate_access_mode(mode: &str) -> Result<(), String> {
+    if ACCESS_MODES.contains(&mode) {
+        Ok(())
+    } else {
+        Err(format!("access mode must be one of {}", ACCESS_MODES.join(", ")))
+    }
+}
+
+/// Arguments are passed to codex directly, never through a shell, so only
+/// values that cannot be sent as a single argument are rejected.
+pub(crate) fn validate_codex_args(args: &[String]) -> Result<(), String> {
+    for arg in args {
+        if arg.trim().is_empty() {
+            return Err("codex arguments must not be empty".to_string());
+        }
+        if arg.contains(['\0', '\n', '\r']) {
+            return Err(format!("codex argument {arg:?} contains a control character"));
+        }
+        if arg == "app-server" {
+            return Err("codex arguments must not repeat the app-server command".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Clamps a requested access mode to a workspace's `maxAccessMode`. Modes rank
+/// read-only < current < full-access, and an unrecognized cap counts as
+/// read-only so a typo never widens access. Returns the mode to use and
+/// whether it was lowered.
+pub(crate) fn cap_access_mode(requested: &str, max: Option<&str>) -> (String, bool) {
+    let Some(max) = max else {
+        return (requested.to_string(), false);
+    };
+    let rank = |mode: &str| ACCESS_MODES.iter().position(|candidate| *candidate == mode);
+    let max_rank = rank(max).unwrap_or(0);
+    // Unknown modes run as workspace-write, the same as "current".
+    let requested_rank = rank(requested).unwrap_or(1);
+    if requested_rank <= max_rank {
+        (requested.to_string(), false)
+    } else {
+        (ACCESS_MODES[max_rank].to_string(), true)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct SettingsFieldError {
+    pub(crate) field: String,
+    pub(crate) message: String,
+}
+
+impl SettingsFieldError {
+    fn new(field: &str, message: String) -> Self {
+        Self {
+            field: field.to_string(),
+            message,
+        }
+    }
+}
+
+pub(crate) fn format_settings_errors(errors: &[SettingsFieldError]) -> String {
+    let details: Vec<String> = errors
+        .iter()
+        .map(|error| format!("{}: {}", error.field, error.message))
+        .collect();
+    format!("Invalid settings: {}", details.join("; "))
+}
+
+impl AppSettings {
+    /// Clamps values that have an obvious safe replacement and reports the
+    /// fields that cannot be repaired, keyed by their JSON name.
+    pub(crate) fn validate(&mut self) -> Vec<SettingsFieldError> {
+        let mut errors = Vec::new();
+        self.ui_scale = if self.ui_scale.is_finite() {
+            self.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX)
+        } else {
+            default_ui_scale()
+        };
+        self.git_timeout_secs = self
+            .git_timeout_secs
+            .clamp(GIT_TIMEOUT_MIN_SECS, GIT_TIMEOUT_MAX_SECS);
+        self.terminal_scrollback_bytes = self
+            .terminal_scrollback_bytes
+            .min(TERMINAL_SCROLLBACK_MAX_BYTES);
+        if !ACCESS_MODES.contains(&self.default_access_mode.as_str()) {
+            errors.push(SettingsFieldError::new(
+                "defaultAccessMode",
+                format!("must be one of {}", ACCESS_MODES.join(", ")),
+            ));
+        }
+        if !THEMES.contains(&self.theme.as_str()) {
+            errors.push(SettingsFieldError::new(
+                "theme",
+                format!("must be one of {}", THEMES.join(", ")),
+            ));
+        }
+        if matches!(self.backend_mode, BackendMode::Remote) {
+            let host = self.remote_backend_host.trim();
+            let has_port = host
+                .rsplit_once(':')
+                .is_some_and(|(name, port)| !name.is_empty() && port.parse::<u16>().is_ok());
+            if !has_port {
+                errors.push(SettingsFieldError::new(
+                    "remoteBackendHost",
+                    "must be host:port".to_string(),
+                ));
+            }
+        }
+        if let Err(message) = validate_codex_args(&self.codex_args) {
+            errors.push(SettingsFieldError::new("codexArgs", message));
+        }
+        for (field, value) in [("dndStart", &self.dnd_start), ("dndEnd", &self.dnd_end)] {
+            if value.as_deref().is_some_and(|time| parse_local_time(time).is_none()) {
+                errors.push(SettingsFieldError::new(field, "must be HH:MM".to_string()));
+            }
+        }
+        errors
+    }
+
+    /// Whether `minutes` (since local midnight) falls inside the do-not-disturb
+    /// window. Windows where the end is before the start span midnight; a
+    /// schedule missing either end is off.
+    pub(crate) fn dnd_active_at(&self, minutes: u32) -> bool {
+        let (Some(start), Some(end)) = (
+            self.dnd_start.as_deref().and_then(parse_local_time),
+            self.dnd_end.as_deref().and_then(parse_local_time),
+        ) else {
+            return false;
+        };
+        if start <= end {
+            start <= minutes && minutes < end
+        } else {
+            minutes >= start || minutes < end
+        }
+    }
+}
+
+/// Parses a 24-hour `HH:MM` local time into minutes since midnight.
+pub(crate) fn parse_local_time(value: &str) -> Option<u32> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::{
-        AppSettings, BackendMode, WorkspaceEntry, WorkspaceGroup, WorkspaceKind, WorkspaceSettings,
+        cap_access_mode, validate_codex_args, AppSettings, BackendMode, ModelInfo, ThreadDefaults,
+        WorkspaceEntry, WorkspaceGroup, WorkspaceInfo, WorkspaceKind, WorkspaceKindFilter,
+        WorkspaceListOptions, WorkspaceSettings, WorkspaceSort,
     };
 
     #[test]
@@ -440,6 +1204,12 @@ mod tests {
         assert!(settings.dictation_preferred_language.is_none());
         assert_eq!(settings.dictation_hold_key, "alt");
         assert!(settings.workspace_groups.is_empty());
+        assert_eq!(settings.github_cache_ttl_secs, 60);
+        assert_eq!(settings.git_timeout_secs, 60);
+        assert_eq!(settings.terminal_scrollback_bytes, 256 * 1024);
+        assert!(settings.preferred_editor.is_none());
+        assert!(!settings.desktop_notifications_enabled);
+        assert!(!settings.local_api_enabled);
     }
 
     #[test]
@@ -490,4 +1260,182 @@ mod tests {
         assert!(settings.group_id.is_none());
         assert!(settings.git_root.is_none());
     }
+
+    #[test]
+    fn thread_defaults_fill_omitted_fields() {
+        let mut defaults = ThreadDefaults::default();
+        let first = defaults.resolve(Some("gpt-5".to_string()), Some("high".to_string()), None);
+        assert_eq!(first.model.as_deref(), Some("gpt-5"));
+        assert!(first.access_mode.is_none());
+
+        let second = defaults.resolve(None, Some("low".to_string()), Some("read-only".to_string()));
+        assert_eq!(second.model.as_deref(), Some("gpt-5"));
+        assert_eq!(second.effort.as_deref(), Some("low"));
+        assert_eq!(second.access_mode.as_deref(), Some("read-only"));
+    }
+
+    #[test]
+    fn model_info_parses_capabilities_and_checks_effort() {
+        let response = serde_json::json!({
+            "result": {
+                "data": [{
+                    "id": "gpt-5.2-codex",
+                    "model": "gpt-5.2-codex",
+                    "displayName": "GPT-5.2 Codex",
+                    "supportedReasoningEfforts": [
+                        { "reasoningEffort": "low", "description": "" },
+                        { "reasoningEffort": "high", "description": "" }
+                    ],
+                    "defaultReasoningEffort": "high",
+                    "inputModalities": ["text", "image"],
+                    "contextWindow": 272000
+                }]
+            }
+        });
+        let models = ModelInfo::list_from_response(&response);
+        assert_eq!(models.len(), 1);
+        assert!(models[0].supports_effort);
+        assert_eq!(models[0].supports_images, Some(true));
+        assert_eq!(models[0].context_window, Some(272000));
+        assert_eq!(models[0].default_effort.as_deref(), Some("high"));
+
+        assert!(ModelInfo::check_effort(&models, "gpt-5.2-codex", "high").is_ok());
+        assert!(ModelInfo::check_effort(&models, "gpt-5.2-codex", "xhigh").is_err());
+        assert!(ModelInfo::check_effort(&models, "unknown", "xhigh").is_ok());
+
+        let mut defaults = ThreadDefaults::default();
+        let rejected = defaults.resolve_for_turn(
+            Some("gpt-5.2-codex".to_string()),
+            Some("xhigh".to_string()),
+            None,
+            Some(&models),
+        );
+        assert!(rejected.is_err());
+        assert_eq!(defaults, ThreadDefaults::default());
+        let plain = defaults
+            .resolve_for_turn(None, None, None, Some(&models))
+            .expect("plain send");
+        assert_eq!(plain, ThreadDefaults::default());
+        let accepted = defaults
+            .resolve_for_turn(None, Some("low".to_string()), None, Some(&models))
+            .expect("supported effort");
+        assert_eq!(defaults, accepted);
+    }
+
+    #[test]
+    fn settings_validation_clamps_scale_and_reports_bad_fields() {
+        let mut settings = AppSettings {
+            ui_scale: 0.0,
+            git_timeout_secs: 0,
+            terminal_scrollback_bytes: usize::MAX,
+            default_access_mode: "everything".to_string(),
+            backend_mode: BackendMode::Remote,
+            remote_backend_host: "localhost".to_string(),
+            codex_args: vec!["--config".to_string(), " ".to_string()],
+            ..AppSettings::default()
+        };
+        let errors = settings.validate();
+        assert!((settings.ui_scale - 0.1).abs() < f64::EPSILON);
+        assert_eq!(settings.git_timeout_secs, 5);
+        assert_eq!(settings.terminal_scrollback_bytes, 16 * 1024 * 1024);
+        let fields: Vec<&str> = errors.iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, vec!["defaultAccessMode", "remoteBackendHost", "codexArgs"]);
+        assert!(validate_codex_args(&["-c".to_string(), "model=\"o3\"".to_string()]).is_ok());
+        assert!(validate_codex_args(&["app-server".to_string()]).is_err());
+        assert!(validate_codex_args(&["a\nb".to_string()]).is_err());
+
+        let mut settings = AppSettings::default();
+        assert!(settings.validate().is_empty());
+    }
+
+    #[test]
+    fn dnd_window_handles_midnight() {
+        let mut settings = AppSettings {
+            dnd_start: Some("22:00".to_string()),
+            dnd_end: Some("07:30".to_string()),
+            ..AppSettings::default()
+        };
+        assert!(settings.dnd_active_at(23 * 60));
+        assert!(settings.dnd_active_at(0));
+        assert!(settings.dnd_active_at(7 * 60 + 29));
+        assert!(!settings.dnd_active_at(7 * 60 + 30));
+        assert!(!settings.dnd_active_at(12 * 60));
+
+        settings.dnd_start = Some("09:00".to_string());
+        settings.dnd_end = Some("17:00".to_string());
+        assert!(settings.dnd_active_at(9 * 60));
+        assert!(!settings.dnd_active_at(17 * 60));
+        assert!(!settings.dnd_active_at(8 * 60));
+
+        settings.dnd_end = Some("25:00".to_string());
+        assert!(!settings.dnd_active_at(10 * 60));
+        assert_eq!(settings.validate().len(), 1);
+        assert!(!AppSettings::default().dnd_active_at(0));
+    }
+
+    #[test]
+    fn workspace_list_options_filter_then_sort() {
+        let workspace = |id: &str, name: &str, kind: WorkspaceKind, connected: bool| WorkspaceInfo {
+            id: id.to_string(),
+            name: name.to_string(),
+            path: format!("/tmp/{id}"),
+            connected,
+            watching: false,
+            codex_bin: None,
+            kind,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        };
+        let list = vec![
+            workspace("a", "zeta", WorkspaceKind::Main, true),
+            workspace("b", "Alpha", WorkspaceKind::Main, false),
+            workspace("c", "mid", WorkspaceKind::Worktree, true),
+            workspace("d", "beta", WorkspaceKind::Main, true),
+        ];
+        let activity = HashMap::from([("a".to_string(), 10), ("d".to_string(), 30)]);
+        let ids = |options: WorkspaceListOptions| {
+            let mut list = list.clone();
+            options.apply(&mut list, &activity);
+            list.into_iter().map(|workspace| workspace.id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(WorkspaceListOptions::default()), vec!["a", "b", "c", "d"]);
+        let by_name = WorkspaceListOptions {
+            kind_filter: WorkspaceKindFilter::Main,
+            sort: WorkspaceSort::Name,
+            ..WorkspaceListOptions::default()
+        };
+        assert_eq!(ids(by_name), vec!["b", "d", "a"]);
+        let recent = WorkspaceListOptions {
+            connected_only: true,
+            sort: WorkspaceSort::LastActivity,
+            ..WorkspaceListOptions::default()
+        };
+        assert_eq!(ids(recent), vec!["d", "a", "c"]);
+
+        let parsed: WorkspaceListOptions = serde_json::from_value(serde_json::json!({
+            "kindFilter": "worktree",
+            "sort": "lastActivity",
+        }))
+        .expect("options");
+        assert_eq!(parsed.kind_filter, WorkspaceKindFilter::Worktree);
+        assert_eq!(parsed.sort, WorkspaceSort::LastActivity);
+        assert!(!parsed.connected_only);
+    }
+
+    #[test]
+    fn access_mode_cap_only_ever_lowers_the_request() {
+        assert_eq!(cap_access_mode("full-access", None), ("full-access".to_string(), false));
+        assert_eq!(
+            cap_access_mode("full-access", Some("read-only")),
+            ("read-only".to_string(), true)
+        );
+        assert_eq!(
+            cap_access_mode("current", Some("full-access")),
+            ("current".to_string(), false)
+        );
+        assert_eq!(cap_access_mode("current", Some("bogus")), ("read-only".to_string(), true));
+        assert_eq!(cap_access_mode("read-only", Some("current")), ("read-only".to_string(), false));
+    }
 }
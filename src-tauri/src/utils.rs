@@ -1,13 +1,119 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
 pub(crate) fn normalize_git_path(path: &str) -> String {
     path.replace('\\', "/")
 }
 
+/// Resolves symlinks, `..` and trailing separators so the same folder added
+/// twice compares equal. Unresolvable paths fall back to their trimmed form.
+pub(crate) fn canonical_workspace_path(path: &str) -> String {
+    let trimmed = path.trim();
+    let resolved = std::fs::canonicalize(trimmed)
+        .map(|resolved| resolved.to_string_lossy().to_string())
+        .unwrap_or_else(|_| trimmed.to_string());
+    let normalized = normalize_git_path(&resolved);
+    let normalized = normalized.strip_prefix("//?/").unwrap_or(&normalized);
+    if normalized.len() > 1 {
+        normalized.trim_end_matches('/').to_string()
+    } else {
+        normalized.to_string()
+    }
+}
+
+/// Returns the main worktree root when `git_dir` belongs to a linked worktree,
+/// i.e. when it differs from the repository's common dir. Relative dirs, as
+/// printed by `git rev-parse`, are resolved against `workspace_path`.
+pub(crate) fn linked_worktree_root(
+    workspace_path: &str,
+    git_dir: &str,
+    common_dir: &str,
+) -> Option<String> {
+    let resolve = |dir: &str| -> PathBuf {
+        let dir = Path::new(dir.trim());
+        if dir.is_absolute() {
+            dir.to_path_buf()
+        } else {
+            Path::new(workspace_path).join(dir)
+        }
+    };
+    let git_dir = canonical_workspace_path(&resolve(git_dir).to_string_lossy());
+    let common_dir = resolve(common_dir);
+    if git_dir == canonical_workspace_path(&common_dir.to_string_lossy()) {
+        return None;
+    }
+    // Bare repositories have no main worktree to link against.
+    if common_dir.file_name().and_then(|name| name.to_str()) != Some(".git") {
+        return None;
+    }
+    common_dir
+        .parent()
+        .map(|root| canonical_workspace_path(&root.to_string_lossy()))
+}
+
+pub(crate) fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+pub(crate) fn hostname() -> Option<String> {
+    #[cfg(unix)]
+    {
+        let mut buffer = [0u8; 256];
+        let result =
+            unsafe { libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len()) };
+        if result == 0 {
+            let end = buffer.iter().position(|byte| *byte == 0).unwrap_or(buffer.len());
+            let name = String::from_utf8_lossy(&buffer[..end]).trim().to_string();
+            if !name.is_empty() {
+                return Some(name);
+            }
+        }
+    }
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+pub(crate) fn runner_platform() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::normalize_git_path;
+    use super::{canonical_workspace_path, linked_worktree_root, normalize_git_path};
 
     #[test]
     fn normalize_git_path_replaces_backslashes() {
         assert_eq!(normalize_git_path("foo\\bar\\baz"), "foo/bar/baz");
     }
+
+    #[test]
+    fn canonical_workspace_path_resolves_dot_segments_and_trailing_slashes() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(temp_dir.join("repo")).expect("create repo dir");
+        let direct = canonical_workspace_path(&temp_dir.join("repo").to_string_lossy());
+        let dotted = temp_dir.join("repo").join("..").join("repo");
+        let dotted = format!("{}/", dotted.to_string_lossy());
+        assert_eq!(canonical_workspace_path(&dotted), direct);
+        assert_eq!(canonical_workspace_path("/missing/path/"), "/missing/path");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn linked_worktree_root_detects_worktree_git_dirs() {
+        assert_eq!(
+            linked_worktree_root("/work/feature", "/repo/.git/worktrees/feature", "/repo/.git"),
+            Some("/repo".to_string())
+        );
+        assert_eq!(linked_worktree_root("/repo", ".git", ".git"), None);
+        assert_eq!(
+            linked_worktree_root("/work/feature", "/srv/bare.git/worktrees/x", "/srv/bare.git"),
+            None
+        );
+    }
 }
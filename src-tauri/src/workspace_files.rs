@@ -0,0 +1,393 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use ignore::WalkBuilder;
+use regex::RegexBuilder;
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
+
+use crate::types::{WorkspaceFileContent, WorkspaceSearchMatch, WorkspaceSearchResult};
+
+pub(crate) const DEFAULT_MAX_FILE_BYTES: usize = 512 * 1024;
+
+const BINARY_SNIFF_BYTES: usize = 8000;
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(10);
+const SEARCH_MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
+/// Resolves `relative_path` to an existing file under `root`, rejecting
+/// absolute paths, `..` segments and symlinks that escape the root.
+pub(crate) fn resolve_workspace_file(root: &Path, relative_path: &str) -> Result<PathBuf, String> {
+    let relative_path = relative_path.trim();
+    if relative_path.is_empty() {
+        return Err("File path is required.".to_string());
+    }
+    let relative = Path::new(relative_path);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err("Path must stay inside the workspace.".to_string());
+    }
+    let root = root
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve workspace root: {e}"))?;
+    let full_path = root
+        .join(relative)
+        .canonicalize()
+        .map_err(|_| format!("File not found: {relative_path}"))?;
+    // Symlinks can still point outside the root after canonicalization.
+    if !full_path.starts_with(&root) {
+        return Err("Path must stay inside the workspace.".to_string());
+    }
+    if !full_path.is_file() {
+        return Err(format!("Not a file: {relative_path}"));
+    }
+    Ok(full_path)
+}
+
+pub(crate) fn read_workspace_file_inner(
+    root: &Path,
+    relative_path: &str,
+    max_bytes: usize,
+) -> Result<WorkspaceFileContent, String> {
+    let full_path = resolve_workspace_file(root, relative_path)?;
+    let metadata = std::fs::metadata(&full_path).map_err(|e| e.to_string())?;
+    let size = metadata.len();
+    let mut bytes = Vec::new();
+    File::open(&full_path)
+        .and_then(|file| file.take(max_bytes as u64).read_to_end(&mut bytes))
+        .map_err(|e| format!("Failed to read file: {e}"))?;
+    let is_binary = looks_binary(&bytes);
+    let content = if is_binary {
+        String::new()
+    } else {
+        String::from_utf8_lossy(&bytes).to_string()
+    };
+    Ok(WorkspaceFileContent {
+        content,
+        truncated: size > max_bytes as u64,
+        is_binary,
+        size,
+    })
+}
+
+fn byte_offset_to_column(text: &str, offset: usize) -> u64 {
+    let offset = offset.min(text.len());
+    let prefix = text.get(..offset).unwrap_or(text);
+    prefix.chars().count() as u64 + 1
+}
+
+fn parse_rg_json_line(line: &str) -> Option<WorkspaceSearchMatch> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    if value.get("type").and_then(Value::as_str) != Some("match") {
+        return None;
+    }
+    let data = value.get("data")?;
+    let path = data.get("path")?.get("text")?.as_str()?;
+    let line_number = data.get("line_number")?.as_u64()?;
+    let text = data.get("lines")?.get("text")?.as_str()?;
+    let start = data
+        .get("submatches")
+        .and_then(Value::as_array)
+        .and_then(|submatches| submatches.first())
+        .and_then(|submatch| submatch.get("start"))
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as usize;
+    let path = path.strip_prefix("./").unwrap_or(path);
+    Some(WorkspaceSearchMatch {
+        path: path.replace('\\', "/"),
+        line: line_number,
+        column: byte_offset_to_column(text, start),
+        text: text.trim_end_matches(['\n', '\r']).to_string(),
+    })
+}
+
+/// Runs ripgrep and returns `None` when it isn't installed so callers can fall back.
+async fn search_with_ripgrep(
+    root: &Path,
+    query: &str,
+    is_regex: bool,
+    max_results: usize,
+) -> Option<Result<WorkspaceSearchResult, String>> {
+    let mut command = Command::new("rg");
+    // Same file set as `search_with_walker`: hidden files and ignore rules
+    // outside a repo are in, `.git` itself is out.
+    command
+        .arg("--json")
+        .arg("--hidden")
+        .arg("--no-require-git")
+        .arg("--glob")
+        .arg("!.git")
+        .arg("--max-filesize")
+        .arg(SEARCH_MAX_FILE_BYTES.to_string());
+    if !is_regex {
+        command.arg("--fixed-strings");
+    }
+    command
+        .arg("--")
+        .arg(query)
+        .arg(".")
+        .current_dir(root)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+        Err(err) => return Some(Err(format!("Failed to run rg: {err}"))),
+    };
+    let stdout = child.stdout.take()?;
+    // Read alongside stdout: enough warnings would fill the pipe and stall rg.
+    let stderr = child.stderr.take();
+    let stderr_task = tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_end(&mut buffer).await;
+        }
+        buffer
+    });
+    let mut lines = BufReader::new(stdout).lines();
+    let mut matches = Vec::new();
+    let mut truncated = false;
+    let deadline = tokio::time::Instant::now() + SEARCH_TIMEOUT;
+    loop {
+        match tokio::time::timeout_at(deadline, lines.next_line()).await {
+            Ok(Ok(Some(line))) => {
+                let Some(found) = parse_rg_json_line(&line) else {
+                    continue;
+                };
+                if matches.len() >= max_results {
+                    truncated = true;
+                    break;
+                }
+                matches.push(found);
+            }
+            Ok(Ok(None)) => break,
+            Ok(Err(err)) => return Some(Err(format!("Failed to read rg output: {err}"))),
+            Err(_) => {
+                truncated = true;
+                break;
+            }
+        }
+    }
+    if truncated {
+        let _ = child.start_kill();
+        return Some(Ok(WorkspaceSearchResult { matches, truncated }));
+    }
+    // Exit code 2 means rg itself failed (e.g. an invalid pattern) rather than "no matches".
+    if let Ok(status) = child.wait().await {
+        if status.code() == Some(2) && matches.is_empty() {
+            let stderr = stderr_task.await.unwrap_or_default();
+            let stderr = String::from_utf8_lossy(&stderr);
+            return Some(Err(format!("Search failed: {}", stderr.trim())));
+        }
+    }
+    Some(Ok(WorkspaceSearchResult { matches, truncated }))
+}
+
+fn search_with_walker(
+    root: &Path,
+    query: &str,
+    is_regex: bool,
+    max_results: usize,
+    timeout: Duration,
+) -> Result<WorkspaceSearchResult, String> {
+    let pattern = if is_regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    let matcher = RegexBuilder::new(&pattern)
+        .build()
+        .map_err(|e| format!("Invalid search pattern: {e}"))?;
+    let deadline = Instant::now() + timeout;
+    let walker = WalkBuilder::new(root)
+        .hidden(false)
+        .follow_links(false)
+        .require_git(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build();
+    let mut matches = Vec::new();
+    for entry in walker {
+        if Instant::now() >= deadline {
+            return Ok(WorkspaceSearchResult {
+                matches,
+                truncated: true,
+            });
+        }
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        if entry
+            .metadata()
+            .map(|metadata| metadata.len() > SEARCH_MAX_FILE_BYTES)
+            .unwrap_or(true)
+        {
+            continue;
+        }
+        let Ok(bytes) = std::fs::read(entry.path()) else {
+            continue;
+        };
+        if looks_binary(&bytes) {
+            continue;
+        }
+        let Ok(rel_path) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let path = rel_path.to_string_lossy().replace('\\', "/");
+        let contents = String::from_utf8_lossy(&bytes);
+        for (index, line) in contents.lines().enumerate() {
+            let Some(found) = matcher.find(line) else {
+                continue;
+            };
+            if matches.len() >= max_results {
+                return Ok(WorkspaceSearchResult {
+                    matches,
+                    truncated: true,
+                });
+            }
+            matches.push(WorkspaceSearchMatch {
+                path: path.clone(),
+                line: index as u64 + 1,
+                column: byte_offset_to_column(line, found.start()),
+                text: line.to_string(),
+            });
+        }
+    }
+    Ok(WorkspaceSearchResult {
+        matches,
+        truncated: false,
+    })
+}
+
+pub(crate) async fn search_workspace_inner(
+    root: PathBuf,
+    query: String,
+    is_regex: bool,
+    max_results: usize,
+) -> Result<WorkspaceSearchResult, String> {
+    if query.is_empty() {
+        return Err("Search query is required.".to_string());
+    }
+    if max_results == 0 {
+        return Ok(WorkspaceSearchResult {
+            matches: Vec::new(),
+            truncated: false,
+        });
+    }
+    if let Some(result) = search_with_ripgrep(&root, &query, is_regex, max_results).await {
+        return result;
+    }
+    tokio::task::spawn_blocking(move || {
+        search_with_walker(&root, &query, is_regex, max_results, SEARCH_TIMEOUT)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_rg_json_line, read_workspace_file_inner, search_with_walker};
+    use std::path::PathBuf;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn temp_workspace() -> PathBuf {
+        let root = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("src")).expect("create workspace");
+        root
+    }
+
+    #[test]
+    fn read_workspace_file_truncates_text() {
+        let root = temp_workspace();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").expect("write file");
+
+        let full = read_workspace_file_inner(&root, "src/main.rs", 1024).expect("read file");
+        assert_eq!(full.content, "fn main() {}\n");
+        assert!(!full.truncated);
+        assert!(!full.is_binary);
+        assert_eq!(full.size, 13);
+
+        let partial = read_workspace_file_inner(&root, "src/main.rs", 2).expect("read file");
+        assert_eq!(partial.content, "fn");
+        assert!(partial.truncated);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn read_workspace_file_detects_binary() {
+        let root = temp_workspace();
+        std::fs::write(root.join("blob.bin"), [0u8, 159, 146, 150]).expect("write file");
+
+        let result = read_workspace_file_inner(&root, "blob.bin", 1024).expect("read file");
+        assert!(result.is_binary);
+        assert!(result.content.is_empty());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn read_workspace_file_rejects_escaping_paths() {
+        let root = temp_workspace();
+
+        assert!(read_workspace_file_inner(&root, "../etc/passwd", 1024).is_err());
+        assert!(read_workspace_file_inner(&root, "src/../../outside", 1024).is_err());
+        assert!(read_workspace_file_inner(&root, "/etc/passwd", 1024).is_err());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn parse_rg_json_line_reads_match_events() {
+        let line = r#"{"type":"match","data":{"path":{"text":"./src/lib.rs"},"lines":{"text":"let café = todo();\n"},"line_number":7,"absolute_offset":10,"submatches":[{"match":{"text":"todo"},"start":12,"end":16}]}}"#;
+        let found = parse_rg_json_line(line).expect("match");
+        assert_eq!(found.path, "src/lib.rs");
+        assert_eq!(found.line, 7);
+        assert_eq!(found.column, 12);
+        assert_eq!(found.text, "let café = todo();");
+
+        assert!(parse_rg_json_line(r#"{"type":"begin","data":{}}"#).is_none());
+    }
+
+    #[test]
+    fn search_with_walker_finds_literal_and_regex_matches() {
+        let root = temp_workspace();
+        std::fs::write(root.join(".gitignore"), "skipped.txt\n").expect("write gitignore");
+        std::fs::write(root.join("skipped.txt"), "needle\n").expect("write skipped");
+        std::fs::write(root.join("src/a.rs"), "one\nfind the needle\nneedle.*\n").expect("write a");
+        std::fs::write(root.join("blob.bin"), b"needle\0").expect("write blob");
+
+        let literal =
+            search_with_walker(&root, "needle.*", false, 10, Duration::from_secs(5)).expect("search");
+        assert_eq!(literal.matches.len(), 1);
+        assert_eq!(literal.matches[0].path, "src/a.rs");
+        assert_eq!(literal.matches[0].line, 3);
+
+        let regex =
+            search_with_walker(&root, "ne+dle", true, 10, Duration::from_secs(5)).expect("search");
+        assert_eq!(regex.matches.len(), 2);
+        assert_eq!(regex.matches[0].column, 10);
+        assert!(!regex.truncated);
+
+        let capped =
+            search_with_walker(&root, "needle", false, 1, Duration::from_secs(5)).expect("search");
+        assert_eq!(capped.matches.len(), 1);
+        assert!(capped.truncated);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
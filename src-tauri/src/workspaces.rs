@@ -1,22 +1,42 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::sync::Arc;
 
 use ignore::WalkBuilder;
 use tauri::{AppHandle, Manager, State};
+use tauri_plugin_opener::OpenerExt;
 use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use uuid::Uuid;
 
+use crate::backend::app_server::{
+    insert_session, is_watching, last_activity_by_workspace, validate_workspace_codex_bin,
+};
 use crate::codex::spawn_workspace_session;
 use crate::codex_home::resolve_workspace_codex_home;
+use crate::event_sink::TauriEventSink;
 use crate::state::AppState;
 use crate::git_utils::resolve_git_root;
-use crate::storage::write_workspaces;
+use crate::logging;
+use crate::storage::{apply_workspace_reorder, write_workspaces};
 use crate::types::{
-    WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
+    validate_access_mode, validate_codex_args, RunnerInfo, WorkspaceConnectResult, WorkspaceEntry,
+    WorkspaceFileContent, WorkspaceFileListing, WorkspaceInfo, WorkspaceKind, WorkspaceKindFilter,
+    WorkspaceListOptions, WorkspaceSearchResult, WorkspaceSettings, WorkspaceSort, WorktreeInfo,
+};
+use crate::utils::{
+    canonical_workspace_path, hostname, linked_worktree_root, normalize_git_path, runner_platform,
 };
-use crate::utils::normalize_git_path;
+use crate::workspace_files::{
+    read_workspace_file_inner, resolve_workspace_file, search_workspace_inner,
+    DEFAULT_MAX_FILE_BYTES,
+};
+
+const DEFAULT_MAX_WORKSPACE_FILES: usize = 20_000;
+const CONNECT_ALL_CONCURRENCY: usize = 4;
 
 fn should_skip_dir(name: &str) -> bool {
     matches!(
@@ -59,8 +79,13 @@ fn sanitize_clone_dir_name(name: &str) -> String {
     }
 }
 
-fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
-    let mut results = Vec::new();
+fn list_workspace_files_inner(
+    root: &PathBuf,
+    max_depth: Option<usize>,
+    max_entries: usize,
+) -> WorkspaceFileListing {
+    let mut files = Vec::new();
+    let mut truncated = false;
     let walker = WalkBuilder::new(root)
         // Allow hidden entries.
         .hidden(false)
@@ -68,6 +93,7 @@ fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
         .follow_links(false)
         // Don't require git to be present to apply to apply git-related ignore rules.
         .require_git(false)
+        .max_depth(max_depth)
         .filter_entry(|entry| {
             if entry.depth() == 0 {
                 return true;
@@ -88,19 +114,20 @@ fn list_workspace_files_inner(root: &PathBuf, max_files: usize) -> Vec<String> {
         if !entry.file_type().is_some_and(|ft| ft.is_file()) {
             continue;
         }
+        if files.len() >= max_entries {
+            truncated = true;
+            break;
+        }
         if let Ok(rel_path) = entry.path().strip_prefix(root) {
             let normalized = normalize_git_path(&rel_path.to_string_lossy());
             if !normalized.is_empty() {
-                results.push(normalized);
+                files.push(normalized);
             }
         }
-        if results.len() >= max_files {
-            break;
-        }
     }
 
-    results.sort();
-    results
+    files.sort();
+    WorkspaceFileListing { files, truncated }
 }
 
 fn sort_workspaces(list: &mut Vec<WorkspaceInfo>) {
@@ -119,6 +146,12 @@ fn apply_workspace_settings_update(
     id: &str,
     settings: WorkspaceSettings,
 ) -> Result<WorkspaceEntry, String> {
+    if let Some(mode) = settings.max_access_mode.as_deref() {
+        validate_access_mode(mode)?;
+    }
+    if let Some(args) = settings.codex_args.as_deref() {
+        validate_codex_args(args)?;
+    }
     match workspaces.get_mut(id) {
         Some(entry) => {
             entry.settings = settings.clone();
@@ -128,6 +161,39 @@ fn apply_workspace_settings_update(
     }
 }
 
+fn apply_thread_pin(
+    workspaces: &mut HashMap<String, WorkspaceEntry>,
+    id: &str,
+    thread_id: &str,
+    pinned: bool,
+) -> Result<WorkspaceEntry, String> {
+    let entry = workspaces.get_mut(id).ok_or("workspace not found")?;
+    let pinned_threads = &mut entry.settings.pinned_threads;
+    pinned_threads.retain(|existing| existing != thread_id);
+    if pinned {
+        pinned_threads.push(thread_id.to_string());
+    }
+    Ok(entry.clone())
+}
+
+fn apply_workspace_rename(
+    workspaces: &mut HashMap<String, WorkspaceEntry>,
+    id: &str,
+    name: &str,
+) -> Result<WorkspaceEntry, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Workspace name cannot be empty.".to_string());
+    }
+    match workspaces.get_mut(id) {
+        Some(entry) => {
+            entry.name = name.to_string();
+            Ok(entry.clone())
+        }
+        None => Err("workspace not found".to_string()),
+    }
+}
+
 async fn run_git_command(repo_path: &PathBuf, args: &[&str]) -> Result<String, String> {
     let output = Command::new("git")
         .args(args)
@@ -220,6 +286,29 @@ async fn git_get_origin_url(repo_path: &PathBuf) -> Option<String> {
     }
 }
 
+/// Finds the main workspace that `path` is a linked git worktree of, along
+/// with the branch checked out there.
+async fn find_worktree_parent(
+    path: &str,
+    candidates: &[WorkspaceEntry],
+) -> Option<(WorkspaceEntry, String)> {
+    let output = run_git_command(
+        &PathBuf::from(path),
+        &["rev-parse", "--git-dir", "--git-common-dir", "--abbrev-ref", "HEAD"],
+    )
+    .await
+    .ok()?;
+    let mut lines = output.lines();
+    let git_dir = lines.next()?;
+    let common_dir = lines.next()?;
+    let branch = lines.next()?.trim().to_string();
+    let root = linked_worktree_root(path, git_dir, common_dir)?;
+    let parent = candidates
+        .iter()
+        .find(|entry| !entry.kind.is_worktree() && canonical_workspace_path(&entry.path) == root)?;
+    Some((parent.clone(), branch))
+}
+
 fn unique_worktree_path(base_dir: &PathBuf, name: &str) -> PathBuf {
     let mut candidate = base_dir.join(name);
     if !candidate.exists() {
@@ -240,6 +329,50 @@ fn build_clone_destination_path(copies_folder: &PathBuf, copy_name: &str) -> Pat
     unique_worktree_path(copies_folder, &safe_name)
 }
 
+/// Builds the command that opens `path` at `line` in `editor`, which may carry
+/// its own arguments (e.g. `code -n`). Terminal editors need a TTY the app does
+/// not have, so they return `None` and the caller falls back to the OS opener.
+fn editor_command(editor: &str, path: &str, line: Option<u32>) -> Option<(String, Vec<String>)> {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next()?.to_string();
+    let mut args: Vec<String> = parts.map(|part| part.to_string()).collect();
+    let name = Path::new(&program)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    let target = match line {
+        Some(line) => format!("{path}:{line}"),
+        None => path.to_string(),
+    };
+    match name.as_str() {
+        "vi" | "vim" | "nvim" | "nano" | "emacs" | "micro" | "hx" | "helix" | "kak" | "ed" => {
+            return None;
+        }
+        "code" | "code-insiders" | "codium" | "cursor" | "windsurf" => {
+            args.push("--goto".to_string());
+            args.push(target);
+        }
+        "subl" | "sublime_text" | "zed" => args.push(target),
+        "idea" | "webstorm" | "pycharm" | "goland" | "clion" | "rustrover" | "phpstorm"
+        | "rider" => {
+            if let Some(line) = line {
+                args.push("--line".to_string());
+                args.push(line.to_string());
+            }
+            args.push(path.to_string());
+        }
+        "gvim" | "mvim" | "emacsclient" => {
+            if let Some(line) = line {
+                args.push(format!("+{line}"));
+            }
+            args.push(path.to_string());
+        }
+        _ => args.push(path.to_string()),
+    }
+    Some((program, args))
+}
+
 fn null_device_path() -> &'static str {
     if cfg!(windows) {
         "NUL"
@@ -250,18 +383,28 @@ fn null_device_path() -> &'static str {
 
 #[tauri::command]
 pub(crate) async fn list_workspaces(
+    kind_filter: Option<WorkspaceKindFilter>,
+    connected_only: Option<bool>,
+    sort: Option<WorkspaceSort>,
     state: State<'_, AppState>,
 ) -> Result<Vec<WorkspaceInfo>, String> {
+    let options = WorkspaceListOptions {
+        kind_filter: kind_filter.unwrap_or_default(),
+        connected_only: connected_only.unwrap_or(false),
+        sort: sort.unwrap_or_default(),
+    };
     let workspaces = state.workspaces.lock().await;
     let sessions = state.sessions.lock().await;
     let mut result = Vec::new();
     for entry in workspaces.values() {
+        let watching = is_watching(sessions.get(&entry.id)).await;
         result.push(WorkspaceInfo {
             id: entry.id.clone(),
             name: entry.name.clone(),
             path: entry.path.clone(),
             codex_bin: entry.codex_bin.clone(),
             connected: sessions.contains_key(&entry.id),
+            watching,
             kind: entry.kind.clone(),
             parent_id: entry.parent_id.clone(),
             worktree: entry.worktree.clone(),
@@ -269,38 +412,110 @@ pub(crate) async fn list_workspaces(
         });
     }
     sort_workspaces(&mut result);
+    options.apply(&mut result, &last_activity_by_workspace(&sessions));
     Ok(result)
 }
 
+#[tauri::command]
+pub(crate) async fn runner_info(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<RunnerInfo, String> {
+    let workspace_count = state.workspaces.lock().await.len();
+    let connected_count = state.sessions.lock().await.len();
+    Ok(RunnerInfo {
+        runner_id: state.runner_id.clone(),
+        version: app.package_info().version.to_string(),
+        platform: runner_platform(),
+        os: std::env::consts::OS.to_string(),
+        hostname: hostname(),
+        started_at_ms: state.started_at_ms,
+        workspace_count,
+        connected_count,
+        cloud_provider: None,
+    })
+}
+
 #[tauri::command]
 pub(crate) async fn add_workspace(
     path: String,
     codex_bin: Option<String>,
+    reject_duplicate: Option<bool>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
-    let name = PathBuf::from(&path)
-        .file_name()
-        .and_then(|s| s.to_str())
-        .unwrap_or("Workspace")
-        .to_string();
-    let entry = WorkspaceEntry {
-        id: Uuid::new_v4().to_string(),
-        name: name.clone(),
-        path: path.clone(),
-        codex_bin,
-        kind: WorkspaceKind::Main,
-        parent_id: None,
-        worktree: None,
-        settings: WorkspaceSettings::default(),
+    let candidates: Vec<WorkspaceEntry> = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces.values().cloned().collect()
+    };
+    let canonical_path = canonical_workspace_path(&path);
+    if let Some(existing) = candidates
+        .iter()
+        .find(|entry| canonical_workspace_path(&entry.path) == canonical_path)
+    {
+        if reject_duplicate.unwrap_or(false) {
+            return Err(format!("This folder is already added as \"{}\".", existing.name));
+        }
+        let session = state.sessions.lock().await.get(&existing.id).cloned();
+        let connected = session.is_some();
+        let watching = is_watching(session.as_ref()).await;
+        return Ok(WorkspaceInfo {
+            id: existing.id.clone(),
+            name: existing.name.clone(),
+            path: existing.path.clone(),
+            codex_bin: existing.codex_bin.clone(),
+            connected,
+            watching,
+            kind: existing.kind.clone(),
+            parent_id: existing.parent_id.clone(),
+            worktree: existing.worktree.clone(),
+            settings: existing.settings.clone(),
+        });
+    }
+
+    // A linked worktree of a known repo is attached under it instead of
+    // showing up as an unrelated top-level workspace.
+    let worktree_parent = find_worktree_parent(&path, &candidates).await;
+    let entry = match &worktree_parent {
+        Some((parent, branch)) => WorkspaceEntry {
+            id: Uuid::new_v4().to_string(),
+            name: branch.clone(),
+            path: path.clone(),
+            codex_bin: codex_bin.or_else(|| parent.codex_bin.clone()),
+            kind: WorkspaceKind::Worktree,
+            parent_id: Some(parent.id.clone()),
+            worktree: Some(WorktreeInfo {
+                branch: branch.clone(),
+            }),
+            settings: WorkspaceSettings::default(),
+        },
+        None => {
+            let name = PathBuf::from(&path)
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Workspace")
+                .to_string();
+            WorkspaceEntry {
+                id: Uuid::new_v4().to_string(),
+                name,
+                path: path.clone(),
+                codex_bin,
+                kind: WorkspaceKind::Main,
+                parent_id: None,
+                worktree: None,
+                settings: WorkspaceSettings::default(),
+            }
+        }
     };
 
-    let default_bin = {
+    let (default_bin, default_args) = {
         let settings = state.app_settings.lock().await;
-        settings.codex_bin.clone()
+        (settings.codex_bin.clone(), settings.codex_args.clone())
     };
-    let codex_home = resolve_workspace_codex_home(&entry, None);
-    let session = spawn_workspace_session(entry.clone(), default_bin, app, codex_home).await?;
+    let parent_path = worktree_parent.as_ref().map(|(parent, _)| parent.path.as_str());
+    let codex_home = resolve_workspace_codex_home(&entry, parent_path);
+    let session =
+        spawn_workspace_session(entry.clone(), default_bin, default_args, app, codex_home).await?;
 
     if let Err(error) = {
         let mut workspaces = state.workspaces.lock().await;
@@ -317,6 +532,7 @@ pub(crate) async fn add_workspace(
         return Err(error);
     }
 
+    let watching = is_watching(Some(&session)).await;
     state
         .sessions
         .lock()
@@ -329,6 +545,7 @@ pub(crate) async fn add_workspace(
         path: entry.path,
         codex_bin: entry.codex_bin,
         connected: true,
+        watching,
         kind: entry.kind,
         parent_id: entry.parent_id,
         worktree: entry.worktree,
@@ -413,12 +630,20 @@ pub(crate) async fn add_clone(
         },
     };
 
-    let default_bin = {
+    let (default_bin, default_args) = {
         let settings = state.app_settings.lock().await;
-        settings.codex_bin.clone()
+        (settings.codex_bin.clone(), settings.codex_args.clone())
     };
     let codex_home = resolve_workspace_codex_home(&entry, None);
-    let session = match spawn_workspace_session(entry.clone(), default_bin, app, codex_home).await {
+    let session = match spawn_workspace_session(
+        entry.clone(),
+        default_bin,
+        default_args,
+        app,
+        codex_home,
+    )
+    .await
+    {
         Ok(session) => session,
         Err(error) => {
             let _ = tokio::fs::remove_dir_all(&destination_path).await;
@@ -442,6 +667,7 @@ pub(crate) async fn add_clone(
         return Err(error);
     }
 
+    let watching = is_watching(Some(&session)).await;
     state
         .sessions
         .lock()
@@ -454,6 +680,7 @@ pub(crate) async fn add_clone(
         path: entry.path,
         codex_bin: entry.codex_bin,
         connected: true,
+        watching,
         kind: entry.kind,
         parent_id: entry.parent_id,
         worktree: entry.worktree,
@@ -526,18 +753,20 @@ pub(crate) async fn add_worktree(
         settings: WorkspaceSettings::default(),
     };
 
-    let default_bin = {
+    let (default_bin, default_args) = {
         let settings = state.app_settings.lock().await;
-        settings.codex_bin.clone()
+        (settings.codex_bin.clone(), settings.codex_args.clone())
     };
     let codex_home = resolve_workspace_codex_home(&entry, Some(&parent_entry.path));
-    let session = spawn_workspace_session(entry.clone(), default_bin, app, codex_home).await?;
+    let session =
+        spawn_workspace_session(entry.clone(), default_bin, default_args, app, codex_home).await?;
     {
         let mut workspaces = state.workspaces.lock().await;
         workspaces.insert(entry.id.clone(), entry.clone());
         let list: Vec<_> = workspaces.values().cloned().collect();
         write_workspaces(&state.storage_path, &list)?;
     }
+    let watching = is_watching(Some(&session)).await;
     state
         .sessions
         .lock()
@@ -550,6 +779,7 @@ pub(crate) async fn add_worktree(
         path: entry.path,
         codex_bin: entry.codex_bin,
         connected: true,
+        watching,
         kind: entry.kind,
         parent_id: entry.parent_id,
         worktree: entry.worktree,
@@ -797,6 +1027,7 @@ pub(crate) async fn update_workspace_settings(
     id: String,
     settings: WorkspaceSettings,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<WorkspaceInfo, String> {
     let (entry_snapshot, list) = {
         let mut workspaces = state.workspaces.lock().await;
@@ -806,13 +1037,87 @@ pub(crate) async fn update_workspace_settings(
     };
     write_workspaces(&state.storage_path, &list)?;
 
-    let connected = state.sessions.lock().await.contains_key(&id);
+    let session = state.sessions.lock().await.get(&id).cloned();
+    let connected = session.is_some();
+    if let Some(session) = &session {
+        session
+            .set_auto_approve(entry_snapshot.settings.auto_approve.clone())
+            .await;
+        session
+            .set_file_watch(entry_snapshot.settings.watch_files, TauriEventSink::new(app))
+            .await;
+    }
+    let watching = is_watching(session.as_ref()).await;
+    Ok(WorkspaceInfo {
+        id: entry_snapshot.id,
+        name: entry_snapshot.name,
+        path: entry_snapshot.path,
+        codex_bin: entry_snapshot.codex_bin,
+        connected,
+        watching,
+        kind: entry_snapshot.kind,
+        parent_id: entry_snapshot.parent_id,
+        worktree: entry_snapshot.worktree,
+        settings: entry_snapshot.settings,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn rename_workspace(
+    id: String,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceInfo, String> {
+    let (entry_snapshot, list) = {
+        let mut workspaces = state.workspaces.lock().await;
+        let entry_snapshot = apply_workspace_rename(&mut workspaces, &id, &name)?;
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (entry_snapshot, list)
+    };
+    write_workspaces(&state.storage_path, &list)?;
+
+    let session = state.sessions.lock().await.get(&id).cloned();
+    let connected = session.is_some();
+    let watching = is_watching(session.as_ref()).await;
+    Ok(WorkspaceInfo {
+        id: entry_snapshot.id,
+        name: entry_snapshot.name,
+        path: entry_snapshot.path,
+        codex_bin: entry_snapshot.codex_bin,
+        connected,
+        watching,
+        kind: entry_snapshot.kind,
+        parent_id: entry_snapshot.parent_id,
+        worktree: entry_snapshot.worktree,
+        settings: entry_snapshot.settings,
+    })
+}
+
+#[tauri::command]
+pub(crate) async fn set_thread_pinned(
+    workspace_id: String,
+    thread_id: String,
+    pinned: bool,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceInfo, String> {
+    let (entry_snapshot, list) = {
+        let mut workspaces = state.workspaces.lock().await;
+        let entry_snapshot = apply_thread_pin(&mut workspaces, &workspace_id, &thread_id, pinned)?;
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (entry_snapshot, list)
+    };
+    write_workspaces(&state.storage_path, &list)?;
+
+    let session = state.sessions.lock().await.get(&workspace_id).cloned();
+    let connected = session.is_some();
+    let watching = is_watching(session.as_ref()).await;
     Ok(WorkspaceInfo {
         id: entry_snapshot.id,
         name: entry_snapshot.name,
         path: entry_snapshot.path,
         codex_bin: entry_snapshot.codex_bin,
         connected,
+        watching,
         kind: entry_snapshot.kind,
         parent_id: entry_snapshot.parent_id,
         worktree: entry_snapshot.worktree,
@@ -820,12 +1125,28 @@ pub(crate) async fn update_workspace_settings(
     })
 }
 
+#[tauri::command]
+pub(crate) async fn reorder_workspaces(
+    ordered_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<WorkspaceInfo>, String> {
+    let list = {
+        let mut workspaces = state.workspaces.lock().await;
+        apply_workspace_reorder(&mut workspaces, &ordered_ids);
+        workspaces.values().cloned().collect::<Vec<_>>()
+    };
+    write_workspaces(&state.storage_path, &list)?;
+    list_workspaces(None, None, None, state).await
+}
+
 #[tauri::command]
 pub(crate) async fn update_workspace_codex_bin(
     id: String,
     codex_bin: Option<String>,
+    verify: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<WorkspaceInfo, String> {
+    validate_workspace_codex_bin(codex_bin.as_deref(), verify.unwrap_or(false)).await?;
     let (entry_snapshot, list) = {
         let mut workspaces = state.workspaces.lock().await;
         let entry_snapshot = match workspaces.get_mut(&id) {
@@ -840,13 +1161,16 @@ pub(crate) async fn update_workspace_codex_bin(
     };
     write_workspaces(&state.storage_path, &list)?;
 
-    let connected = state.sessions.lock().await.contains_key(&id);
+    let session = state.sessions.lock().await.get(&id).cloned();
+    let connected = session.is_some();
+    let watching = is_watching(session.as_ref()).await;
     Ok(WorkspaceInfo {
         id: entry_snapshot.id,
         name: entry_snapshot.name,
         path: entry_snapshot.path,
         codex_bin: entry_snapshot.codex_bin,
         connected,
+        watching,
         kind: entry_snapshot.kind,
         parent_id: entry_snapshot.parent_id,
         worktree: entry_snapshot.worktree,
@@ -859,6 +1183,14 @@ pub(crate) async fn connect_workspace(
     id: String,
     state: State<'_, AppState>,
     app: AppHandle,
+) -> Result<(), String> {
+    connect_workspace_inner(id, &state, app).await
+}
+
+pub(crate) async fn connect_workspace_inner(
+    id: String,
+    state: &AppState,
+    app: AppHandle,
 ) -> Result<(), String> {
     let (entry, parent_path) = {
         let workspaces = state.workspaces.lock().await;
@@ -876,27 +1208,124 @@ pub(crate) async fn connect_workspace(
             .ok_or("workspace not found")?
     };
 
-    let default_bin = {
+    let (default_bin, default_args) = {
         let settings = state.app_settings.lock().await;
-        settings.codex_bin.clone()
+        (settings.codex_bin.clone(), settings.codex_args.clone())
     };
     let codex_home = resolve_workspace_codex_home(&entry, parent_path.as_deref());
-    let session = spawn_workspace_session(entry.clone(), default_bin, app, codex_home).await?;
-    state.sessions.lock().await.insert(entry.id, session);
+    let session =
+        spawn_workspace_session(entry.clone(), default_bin, default_args, app, codex_home).await?;
+    insert_session(&state.sessions, entry.id, session).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn connect_all_workspaces(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<HashMap<String, WorkspaceConnectResult>, String> {
+    let ids: Vec<String> = {
+        let workspaces = state.workspaces.lock().await;
+        let sessions = state.sessions.lock().await;
+        workspaces
+            .values()
+            .filter(|entry| !entry.kind.is_worktree() && !sessions.contains_key(&entry.id))
+            .map(|entry| entry.id.clone())
+            .collect()
+    };
+    let limiter = Arc::new(Semaphore::new(CONNECT_ALL_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for id in ids {
+        let app = app.clone();
+        let limiter = Arc::clone(&limiter);
+        tasks.spawn(async move {
+            let _permit = limiter.acquire_owned().await;
+            let state = app.state::<AppState>();
+            let result = connect_workspace_inner(id.clone(), &state, app.clone()).await;
+            (id, result)
+        });
+    }
+    let mut results = HashMap::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((id, result)) => {
+                results.insert(id, WorkspaceConnectResult::from(result));
+            }
+            Err(err) => logging::error("workspaces", format!("connect task failed: {err}")),
+        }
+    }
+    Ok(results)
+}
+
+#[tauri::command]
+pub(crate) async fn disconnect_all_workspaces(state: State<'_, AppState>) -> Result<(), String> {
+    let sessions: Vec<_> = {
+        let mut sessions = state.sessions.lock().await;
+        sessions.drain().map(|(_, session)| session).collect()
+    };
+    for session in sessions {
+        let mut child = session.child.lock().await;
+        let _ = child.kill().await;
+    }
     Ok(())
 }
 
 #[tauri::command]
 pub(crate) async fn list_workspace_files(
     workspace_id: String,
+    max_depth: Option<usize>,
+    max_entries: Option<usize>,
     state: State<'_, AppState>,
-) -> Result<Vec<String>, String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?;
-    let root = PathBuf::from(&entry.path);
-    Ok(list_workspace_files_inner(&root, usize::MAX))
+) -> Result<WorkspaceFileListing, String> {
+    let root = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        PathBuf::from(&entry.path)
+    };
+    let max_entries = max_entries.unwrap_or(DEFAULT_MAX_WORKSPACE_FILES);
+    tokio::task::spawn_blocking(move || list_workspace_files_inner(&root, max_depth, max_entries))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn read_workspace_file(
+    workspace_id: String,
+    relative_path: String,
+    max_bytes: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceFileContent, String> {
+    let root = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        PathBuf::from(&entry.path)
+    };
+    let max_bytes = max_bytes.unwrap_or(DEFAULT_MAX_FILE_BYTES);
+    tokio::task::spawn_blocking(move || read_workspace_file_inner(&root, &relative_path, max_bytes))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn search_workspace(
+    workspace_id: String,
+    query: String,
+    is_regex: bool,
+    max_results: usize,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceSearchResult, String> {
+    let root = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        PathBuf::from(&entry.path)
+    };
+    search_workspace_inner(root, query, is_regex, max_results).await
 }
 
 #[tauri::command]
@@ -917,13 +1346,65 @@ pub(crate) async fn open_workspace_in(
     }
 }
 
+#[tauri::command]
+pub(crate) async fn open_in_editor(
+    workspace_id: String,
+    relative_path: String,
+    line: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    let root = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or("workspace not found")?;
+        PathBuf::from(&entry.path)
+    };
+    let preferred_editor = {
+        let settings = state.app_settings.lock().await;
+        settings.preferred_editor.clone()
+    };
+    let full_path = resolve_workspace_file(&root, &relative_path)?;
+    let path = full_path.to_string_lossy().to_string();
+    // An empty setting or variable means "unset", not "stop looking".
+    let non_empty = |value: String| (!value.trim().is_empty()).then_some(value);
+    let editor = preferred_editor
+        .and_then(non_empty)
+        .or_else(|| std::env::var("VISUAL").ok().and_then(non_empty))
+        .or_else(|| std::env::var("EDITOR").ok().and_then(non_empty));
+    if let Some((program, args)) =
+        editor.and_then(|editor| editor_command(&editor, &path, line))
+    {
+        let spawned = Command::new(&program)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Ok(mut child) = spawned {
+            // Waited on in the background so the editor doesn't linger as
+            // a zombie once it closes.
+            tokio::spawn(async move {
+                let _ = child.wait().await;
+            });
+            return Ok(());
+        }
+    }
+    app.opener()
+        .open_path(path, None::<&str>)
+        .map_err(|error| format!("Failed to open file: {error}"))
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
     use std::path::PathBuf;
 
     use super::{
-        apply_workspace_settings_update, build_clone_destination_path, sanitize_clone_dir_name,
+        apply_thread_pin, apply_workspace_rename, apply_workspace_settings_update,
+        build_clone_destination_path, editor_command,
+        list_workspace_files_inner, sanitize_clone_dir_name,
         sanitize_worktree_name, sort_workspaces,
     };
     use crate::storage::{read_workspaces, write_workspaces};
@@ -934,6 +1415,19 @@ mod tests {
         workspace_with_id_and_kind(name, name, sort_order, WorkspaceKind::Main)
     }
 
+    fn workspace_entry(id: &str, path: &str) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }
+    }
+
     fn workspace_with_id_and_kind(
         name: &str,
         id: &str,
@@ -955,6 +1449,7 @@ mod tests {
             name: name.to_string(),
             path: "/tmp".to_string(),
             connected: false,
+            watching: false,
             codex_bin: None,
             kind,
             parent_id,
@@ -964,6 +1459,12 @@ mod tests {
                 sort_order,
                 group_id: None,
                 git_root: None,
+                pinned_threads: Vec::new(),
+                muted: false,
+                auto_approve: Default::default(),
+                max_access_mode: None,
+                watch_files: false,
+                codex_args: None,
             },
         }
     }
@@ -1097,17 +1598,7 @@ mod tests {
     #[test]
     fn update_workspace_settings_persists_sort_and_group() {
         let id = "workspace-1".to_string();
-        let entry = WorkspaceEntry {
-            id: id.clone(),
-            name: "Workspace".to_string(),
-            path: "/tmp".to_string(),
-            codex_bin: None,
-            kind: WorkspaceKind::Main,
-            parent_id: None,
-            worktree: None,
-            settings: WorkspaceSettings::default(),
-        };
-        let mut workspaces = HashMap::from([(id.clone(), entry)]);
+        let mut workspaces = HashMap::from([(id.clone(), workspace_entry(&id, "/tmp"))]);
 
         let mut settings = WorkspaceSettings::default();
         settings.sort_order = Some(3);
@@ -1136,4 +1627,82 @@ mod tests {
         assert!(stored.settings.sidebar_collapsed);
         assert_eq!(stored.settings.git_root.as_deref(), Some("/tmp"));
     }
+
+    #[test]
+    fn editor_command_passes_line_in_each_editor_style() {
+        let (program, args) =
+            editor_command("code -n", "/repo/src/main.rs", Some(12)).expect("code");
+        assert_eq!(program, "code");
+        assert_eq!(args, vec!["-n", "--goto", "/repo/src/main.rs:12"]);
+
+        let (_, args) = editor_command("/usr/local/bin/idea", "/repo/a.rs", Some(3)).expect("idea");
+        assert_eq!(args, vec!["--line", "3", "/repo/a.rs"]);
+
+        let (_, args) = editor_command("mvim", "/repo/a.rs", Some(7)).expect("mvim");
+        assert_eq!(args, vec!["+7", "/repo/a.rs"]);
+
+        let (_, args) = editor_command("zed", "/repo/a.rs", None).expect("zed");
+        assert_eq!(args, vec!["/repo/a.rs"]);
+
+        assert!(editor_command("nvim", "/repo/a.rs", Some(1)).is_none());
+        assert!(editor_command("  ", "/repo/a.rs", None).is_none());
+    }
+
+    #[test]
+    fn thread_pins_toggle_without_duplicates() {
+        let mut workspaces = HashMap::from([("w1".to_string(), workspace_entry("w1", "/tmp"))]);
+
+        apply_thread_pin(&mut workspaces, "w1", "t1", true).expect("pin t1");
+        apply_thread_pin(&mut workspaces, "w1", "t2", true).expect("pin t2");
+        let updated = apply_thread_pin(&mut workspaces, "w1", "t1", true).expect("pin t1 again");
+        assert_eq!(updated.settings.pinned_threads, vec!["t2", "t1"]);
+
+        let updated = apply_thread_pin(&mut workspaces, "w1", "t2", false).expect("unpin");
+        assert_eq!(updated.settings.pinned_threads, vec!["t1"]);
+        assert!(apply_thread_pin(&mut workspaces, "missing", "t1", true).is_err());
+    }
+
+    #[test]
+    fn rename_workspace_trims_and_rejects_empty_names() {
+        let mut entry = workspace_entry("w1", "/tmp");
+        entry.name = "Old".to_string();
+        let mut workspaces = HashMap::from([("w1".to_string(), entry)]);
+
+        let error = apply_workspace_rename(&mut workspaces, "w1", "   ").expect_err("empty");
+        assert_eq!(error, "Workspace name cannot be empty.");
+        assert_eq!(workspaces["w1"].name, "Old");
+
+        let renamed = apply_workspace_rename(&mut workspaces, "w1", "  New name ").expect("rename");
+        assert_eq!(renamed.name, "New name");
+        assert_eq!(workspaces["w1"].name, "New name");
+        assert!(apply_workspace_rename(&mut workspaces, "missing", "Name").is_err());
+    }
+
+    #[test]
+    fn list_workspace_files_respects_gitignore_depth_and_cap() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-test-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("src/nested")).expect("create dirs");
+        std::fs::write(root.join(".gitignore"), "ignored.txt\n").expect("write gitignore");
+        std::fs::write(root.join("ignored.txt"), "x").expect("write ignored");
+        std::fs::write(root.join("README.md"), "x").expect("write readme");
+        std::fs::write(root.join("src/lib.rs"), "x").expect("write lib");
+        std::fs::write(root.join("src/nested/deep.rs"), "x").expect("write deep");
+
+        let listing = list_workspace_files_inner(&root, None, usize::MAX);
+        assert!(!listing.truncated);
+        assert_eq!(
+            listing.files,
+            vec![".gitignore", "README.md", "src/lib.rs", "src/nested/deep.rs"]
+        );
+
+        let shallow = list_workspace_files_inner(&root, Some(2), usize::MAX);
+        assert!(!shallow.files.iter().any(|path| path == "src/nested/deep.rs"));
+        assert!(shallow.files.iter().any(|path| path == "src/lib.rs"));
+
+        let capped = list_workspace_files_inner(&root, None, 2);
+        assert!(capped.truncated);
+        assert_eq!(capped.files.len(), 2);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
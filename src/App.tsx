@@ -270,12 +270,6 @@ function MainApp() {
   const isWindowFocused = useWindowFocusState();
   const nextTestSoundIsError = useRef(false);
 
-  useAgentSoundNotifications({
-    enabled: appSettings.notificationSoundsEnabled,
-    isWindowFocused,
-    onDebug: addDebugEntry,
-  });
-
   const handleTestNotificationSound = useCallback(() => {
     const useError = nextTestSoundIsError.current;
     nextTestSoundIsError.current = !useError;
@@ -316,6 +310,23 @@ function MainApp() {
     onUpdateAppSettings: queueSaveSettings,
   });
 
+  const mutedWorkspaceIds = useMemo(
+    () =>
+      new Set(
+        workspaces
+          .filter((workspace) => workspace.settings.muted)
+          .map((workspace) => workspace.id),
+      ),
+    [workspaces],
+  );
+
+  useAgentSoundNotifications({
+    enabled: appSettings.notificationSoundsEnabled,
+    isWindowFocused,
+    mutedWorkspaceIds,
+    onDebug: addDebugEntry,
+  });
+
   useEffect(() => {
     setAccessMode((prev) =>
       prev === "current" ? appSettings.defaultAccessMode : prev
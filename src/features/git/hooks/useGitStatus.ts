@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import type { GitFileStatus, WorkspaceInfo } from "../../../types";
 import { getGitStatus } from "../../../services/tauri";
+import { subscribeGitStatusChanged } from "../../../services/events";
 
 type GitStatusState = {
   branchName: string;
@@ -29,6 +30,9 @@ export function useGitStatus(activeWorkspace: WorkspaceInfo | null) {
   const workspaceIdRef = useRef<string | null>(activeWorkspace?.id ?? null);
   const cachedStatusRef = useRef<Map<string, GitStatusState>>(new Map());
   const workspaceId = activeWorkspace?.id ?? null;
+  const isWatched = Boolean(
+    activeWorkspace?.connected && activeWorkspace.watching,
+  );
 
   const resolveBranchName = useCallback(
     (incoming: string | undefined, cached: GitStatusState | undefined) => {
@@ -110,12 +114,47 @@ export function useGitStatus(activeWorkspace: WorkspaceInfo | null) {
     };
 
     fetchStatus();
+    if (isWatched) {
+      // The backend watcher sends git-status-changed instead.
+      return;
+    }
     const interval = window.setInterval(fetchStatus, REFRESH_INTERVAL_MS);
 
     return () => {
       window.clearInterval(interval);
     };
-  }, [refresh, workspaceId]);
+  }, [isWatched, refresh, workspaceId]);
+
+  useEffect(() => {
+    if (!workspaceId || !isWatched) {
+      return;
+    }
+    let unlisten: (() => void) | null = null;
+    let canceled = false;
+    subscribeGitStatusChanged((event) => {
+      if (event.workspaceId === workspaceId) {
+        refresh()?.catch(() => {});
+      }
+    })
+      .then((handler) => {
+        if (canceled) {
+          handler();
+          return;
+        }
+        unlisten = handler;
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+      if (unlisten) {
+        try {
+          unlisten();
+        } catch {
+          // Ignore double-unlisten when tearing down.
+        }
+      }
+    };
+  }, [isWatched, refresh, workspaceId]);
 
   return { status, refresh };
 }
@@ -10,6 +10,7 @@ const DEFAULT_MIN_DURATION_MS = 60_000; // 1 minute
 type SoundNotificationOptions = {
   enabled: boolean;
   isWindowFocused: boolean;
+  mutedWorkspaceIds?: ReadonlySet<string>;
   minDurationMs?: number;
   onDebug?: (entry: DebugEntry) => void;
 };
@@ -25,6 +26,7 @@ function buildTurnKey(workspaceId: string, turnId: string) {
 export function useAgentSoundNotifications({
   enabled,
   isWindowFocused,
+  mutedWorkspaceIds,
   minDurationMs = DEFAULT_MIN_DURATION_MS,
   onDebug,
 }: SoundNotificationOptions) {
@@ -75,11 +77,11 @@ export function useAgentSoundNotifications({
   );
 
   const shouldPlaySound = useCallback(
-    (durationMs: number | null, threadKey: string) => {
+    (durationMs: number | null, workspaceId: string, threadKey: string) => {
       if (durationMs === null) {
         return false;
       }
-      if (!enabled) {
+      if (!enabled || mutedWorkspaceIds?.has(workspaceId)) {
         return false;
       }
       if (durationMs < minDurationMs) {
@@ -95,7 +97,7 @@ export function useAgentSoundNotifications({
       lastPlayedAtByThread.current.set(threadKey, Date.now());
       return true;
     },
-    [enabled, isWindowFocused, minDurationMs],
+    [enabled, isWindowFocused, minDurationMs, mutedWorkspaceIds],
   );
 
   const handleTurnStarted = useCallback(
@@ -116,7 +118,7 @@ export function useAgentSoundNotifications({
     (workspaceId: string, threadId: string, turnId: string) => {
       const durationMs = consumeDuration(workspaceId, threadId, turnId);
       const threadKey = buildThreadKey(workspaceId, threadId);
-      if (!shouldPlaySound(durationMs, threadKey)) {
+      if (!shouldPlaySound(durationMs, workspaceId, threadKey)) {
         return;
       }
       playSound(successSoundUrl, "success");
@@ -136,7 +138,7 @@ export function useAgentSoundNotifications({
       }
       const durationMs = consumeDuration(workspaceId, threadId, turnId);
       const threadKey = buildThreadKey(workspaceId, threadId);
-      if (!shouldPlaySound(durationMs, threadKey)) {
+      if (!shouldPlaySound(durationMs, workspaceId, threadKey)) {
         return;
       }
       playSound(errorSoundUrl, "error");
@@ -162,7 +164,7 @@ export function useAgentSoundNotifications({
     (event: { workspaceId: string; threadId: string }) => {
       const durationMs = consumeDuration(event.workspaceId, event.threadId, "");
       const threadKey = buildThreadKey(event.workspaceId, event.threadId);
-      if (!shouldPlaySound(durationMs, threadKey)) {
+      if (!shouldPlaySound(durationMs, event.workspaceId, threadKey)) {
         return;
       }
       playSound(successSoundUrl, "success");
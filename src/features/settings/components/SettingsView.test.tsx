@@ -19,6 +19,7 @@ vi.mock("@tauri-apps/plugin-dialog", () => ({
 
 const baseSettings: AppSettings = {
   codexBin: null,
+  codexArgs: [],
   backendMode: "local",
   remoteBackendHost: "127.0.0.1:4732",
   remoteBackendToken: null,
@@ -39,6 +40,14 @@ const baseSettings: AppSettings = {
   dictationPreferredLanguage: null,
   dictationHoldKey: null,
   workspaceGroups: [],
+  githubCacheTtlSecs: 60,
+  gitTimeoutSecs: 60,
+  terminalScrollbackBytes: 256 * 1024,
+  preferredEditor: null,
+  desktopNotificationsEnabled: false,
+  dndStart: null,
+  dndEnd: null,
+  localApiEnabled: false,
 };
 
 const createDoctorResult = () => ({
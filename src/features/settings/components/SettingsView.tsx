@@ -819,6 +819,63 @@ export function SettingsView({
                     <span className="settings-toggle-knob" />
                   </button>
                 </div>
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Desktop notifications</div>
+                    <div className="settings-toggle-subtitle">
+                      Show a system notification when an agent finishes while the window is in
+                      the background.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.desktopNotificationsEnabled ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        desktopNotificationsEnabled: !appSettings.desktopNotificationsEnabled,
+                      })
+                    }
+                    aria-pressed={appSettings.desktopNotificationsEnabled}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Do not disturb</div>
+                    <div className="settings-toggle-subtitle">
+                      Hold desktop notifications between these local times. Leave both empty to
+                      turn the schedule off.
+                    </div>
+                  </div>
+                  <div className="settings-scale-controls">
+                    <input
+                      type="time"
+                      className="settings-input"
+                      value={appSettings.dndStart ?? ""}
+                      aria-label="Do not disturb start"
+                      onChange={(event) =>
+                        void onUpdateAppSettings({
+                          ...appSettings,
+                          dndStart: event.target.value || null,
+                        })
+                      }
+                    />
+                    <input
+                      type="time"
+                      className="settings-input"
+                      value={appSettings.dndEnd ?? ""}
+                      aria-label="Do not disturb end"
+                      onChange={(event) =>
+                        void onUpdateAppSettings({
+                          ...appSettings,
+                          dndEnd: event.target.value || null,
+                        })
+                      }
+                    />
+                  </div>
+                </div>
                 <div className="settings-sound-actions">
                   <button
                     type="button"
@@ -1370,6 +1427,28 @@ export function SettingsView({
                     <span className="settings-toggle-knob" />
                   </button>
                 </div>
+                <div className="settings-toggle-row">
+                  <div>
+                    <div className="settings-toggle-title">Local event stream</div>
+                    <div className="settings-toggle-subtitle">
+                      Stream app-server events over HTTP on a random localhost port for scripts
+                      and dashboards.
+                    </div>
+                  </div>
+                  <button
+                    type="button"
+                    className={`settings-toggle ${appSettings.localApiEnabled ? "on" : ""}`}
+                    onClick={() =>
+                      void onUpdateAppSettings({
+                        ...appSettings,
+                        localApiEnabled: !appSettings.localApiEnabled,
+                      })
+                    }
+                    aria-pressed={appSettings.localApiEnabled}
+                  >
+                    <span className="settings-toggle-knob" />
+                  </button>
+                </div>
                 <div className="settings-toggle-row">
                   <div>
                     <div className="settings-toggle-title">Background terminal</div>
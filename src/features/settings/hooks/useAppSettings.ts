@@ -7,6 +7,7 @@ const allowedThemes = new Set(["system", "light", "dark"]);
 
 const defaultSettings: AppSettings = {
   codexBin: null,
+  codexArgs: [],
   backendMode: "local",
   remoteBackendHost: "127.0.0.1:4732",
   remoteBackendToken: null,
@@ -27,6 +28,14 @@ const defaultSettings: AppSettings = {
   dictationPreferredLanguage: null,
   dictationHoldKey: "alt",
   workspaceGroups: [],
+  githubCacheTtlSecs: 60,
+  gitTimeoutSecs: 60,
+  terminalScrollbackBytes: 256 * 1024,
+  preferredEditor: null,
+  desktopNotificationsEnabled: false,
+  dndStart: null,
+  dndEnd: null,
+  localApiEnabled: false,
 };
 
 function normalizeAppSettings(settings: AppSettings): AppSettings {
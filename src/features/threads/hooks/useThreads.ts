@@ -1247,9 +1247,14 @@ export function useThreads({
           threadActivityRef.current = next;
           saveThreadActivity(next);
         }
+        const pinnedIds = new Set(workspace.settings?.pinnedThreads ?? []);
         uniqueThreads.sort((a, b) => {
           const aId = String(a?.id ?? "");
           const bId = String(b?.id ?? "");
+          const pinnedOrder = Number(pinnedIds.has(bId)) - Number(pinnedIds.has(aId));
+          if (pinnedOrder !== 0) {
+            return pinnedOrder;
+          }
           const aCreated = getThreadTimestamp(a);
           const bCreated = getThreadTimestamp(b);
           const aActivity = Math.max(nextActivityByThread[aId] ?? 0, aCreated);
@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import type { DebugEntry, WorkspaceInfo } from "../../../types";
 import { getWorkspaceFiles } from "../../../services/tauri";
+import { subscribeWorkspaceFilesChanged } from "../../../services/events";
 
 type UseWorkspaceFilesOptions = {
   activeWorkspace: WorkspaceInfo | null;
@@ -19,6 +20,7 @@ export function useWorkspaceFiles({
   const REFRESH_INTERVAL_MS = 5000;
   const workspaceId = activeWorkspace?.id ?? null;
   const isConnected = Boolean(activeWorkspace?.connected);
+  const isWatched = Boolean(activeWorkspace?.watching);
 
   const refreshFiles = useCallback(async () => {
     if (!workspaceId || !isConnected) {
@@ -47,7 +49,7 @@ export function useWorkspaceFiles({
         payload: response,
       });
       if (requestWorkspaceId === workspaceId) {
-        setFiles(Array.isArray(response) ? response : []);
+        setFiles(Array.isArray(response?.files) ? response.files : []);
         lastFetchedWorkspaceId.current = requestWorkspaceId;
       }
     } catch (error) {
@@ -84,7 +86,7 @@ export function useWorkspaceFiles({
   }, [files.length, isConnected, refreshFiles, workspaceId]);
 
   useEffect(() => {
-    if (!workspaceId || !isConnected) {
+    if (!workspaceId || !isConnected || isWatched) {
       return;
     }
 
@@ -95,7 +97,42 @@ export function useWorkspaceFiles({
     return () => {
       window.clearInterval(interval);
     };
-  }, [isConnected, refreshFiles, workspaceId]);
+  }, [isConnected, isWatched, refreshFiles, workspaceId]);
+
+  useEffect(() => {
+    if (!workspaceId || !isConnected || !isWatched) {
+      return;
+    }
+    let unlisten: (() => void) | null = null;
+    let canceled = false;
+    subscribeWorkspaceFilesChanged((event) => {
+      // Edits to existing files leave the file list unchanged.
+      if (
+        event.workspaceId === workspaceId &&
+        (event.added.length > 0 || event.deleted.length > 0)
+      ) {
+        refreshFiles().catch(() => {});
+      }
+    })
+      .then((handler) => {
+        if (canceled) {
+          handler();
+          return;
+        }
+        unlisten = handler;
+      })
+      .catch(() => {});
+    return () => {
+      canceled = true;
+      if (unlisten) {
+        try {
+          unlisten();
+        } catch {
+          // Ignore double-unlisten when tearing down.
+        }
+      }
+    };
+  }, [isConnected, isWatched, refreshFiles, workspaceId]);
 
   const fileOptions = useMemo(() => files.filter(Boolean), [files]);
 
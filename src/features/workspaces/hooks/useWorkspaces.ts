@@ -328,6 +328,8 @@ export function useWorkspaces(options: UseWorkspacesOptions = {}) {
     setWorkspaces((prev) =>
       prev.map((entry) => (entry.id === id ? { ...entry, connected: true } : entry)),
     );
+    // Whether the file watcher started is only known to the backend.
+    void refreshWorkspaces();
   }
 
   const updateWorkspaceSettings = useCallback(
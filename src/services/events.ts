@@ -1,5 +1,12 @@
 import { listen } from "@tauri-apps/api/event";
-import type { AppServerEvent, DictationEvent, DictationModelStatus } from "../types";
+import type {
+  AppServerEvent,
+  AppSettings,
+  DictationEvent,
+  DictationModelStatus,
+  DictationResult,
+  GitProgressEvent,
+} from "../types";
 
 export type Unsubscribe = () => void;
 
@@ -9,6 +16,36 @@ export type TerminalOutputEvent = {
   data: string;
 };
 
+export type TerminalClosedEvent = {
+  workspaceId: string;
+  terminalId: string;
+};
+
+export type SessionExitedEvent = {
+  workspaceId: string;
+  exitCode: number | null;
+  stderrTail: string[];
+};
+
+export type WorkspaceFilesChangedEvent = {
+  workspaceId: string;
+  added: string[];
+  modified: string[];
+  deleted: string[];
+};
+
+export type GitStatusChangedEvent = {
+  workspaceId: string;
+};
+
+export type TurnProgressEvent = {
+  workspaceId: string;
+  threadId: string;
+  turnId: string | null;
+  phase: "thinking" | "tool" | "writing" | "done";
+  toolName: string | null;
+};
+
 export async function subscribeAppServerEvents(
   onEvent: (event: AppServerEvent) => void,
 ): Promise<Unsubscribe> {
@@ -33,6 +70,22 @@ export async function subscribeDictationEvents(
   });
 }
 
+export async function subscribeDictationResults(
+  onEvent: (event: DictationResult) => void,
+): Promise<Unsubscribe> {
+  return listen<DictationResult>("dictation-result", (event) => {
+    onEvent(event.payload);
+  });
+}
+
+export async function subscribeGitProgress(
+  onEvent: (event: GitProgressEvent) => void,
+): Promise<Unsubscribe> {
+  return listen<GitProgressEvent>("git-progress", (event) => {
+    onEvent(event.payload);
+  });
+}
+
 export async function subscribeTerminalOutput(
   onEvent: (event: TerminalOutputEvent) => void,
 ): Promise<Unsubscribe> {
@@ -40,3 +93,54 @@ export async function subscribeTerminalOutput(
     onEvent(event.payload);
   });
 }
+
+export async function subscribeTerminalClosed(
+  onEvent: (event: TerminalClosedEvent) => void,
+): Promise<Unsubscribe> {
+  return listen<TerminalClosedEvent>("terminal-closed", (event) => {
+    onEvent(event.payload);
+  });
+}
+
+export async function subscribeSessionExited(
+  onEvent: (event: SessionExitedEvent) => void,
+): Promise<Unsubscribe> {
+  return listen<SessionExitedEvent>("session-exited", (event) => {
+    onEvent(event.payload);
+  });
+}
+
+export async function subscribeWorkspaceFilesChanged(
+  onEvent: (event: WorkspaceFilesChangedEvent) => void,
+): Promise<Unsubscribe> {
+  return listen<WorkspaceFilesChangedEvent>(
+    "workspace-files-changed",
+    (event) => {
+      onEvent(event.payload);
+    },
+  );
+}
+
+export async function subscribeGitStatusChanged(
+  onEvent: (event: GitStatusChangedEvent) => void,
+): Promise<Unsubscribe> {
+  return listen<GitStatusChangedEvent>("git-status-changed", (event) => {
+    onEvent(event.payload);
+  });
+}
+
+export async function subscribeTurnProgress(
+  onEvent: (event: TurnProgressEvent) => void,
+): Promise<Unsubscribe> {
+  return listen<TurnProgressEvent>("turn-progress", (event) => {
+    onEvent(event.payload);
+  });
+}
+
+export async function subscribeSettingsChanged(
+  onEvent: (settings: AppSettings) => void,
+): Promise<Unsubscribe> {
+  return listen<AppSettings>("settings-changed", (event) => {
+    onEvent(event.payload);
+  });
+}
@@ -28,6 +28,20 @@ describe("tauri invoke wrappers", () => {
     expect(invokeMock).toHaveBeenCalledWith("add_workspace", {
       path: "/tmp/project",
       codex_bin: null,
+      rejectDuplicate: false,
+    });
+  });
+
+  it("passes rejectDuplicate through for addWorkspace", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ id: "ws-1" });
+
+    await addWorkspace("/tmp/project", null, { rejectDuplicate: true });
+
+    expect(invokeMock).toHaveBeenCalledWith("add_workspace", {
+      path: "/tmp/project",
+      codex_bin: null,
+      rejectDuplicate: true,
     });
   });
 
@@ -57,6 +71,19 @@ describe("tauri invoke wrappers", () => {
 
     expect(invokeMock).toHaveBeenCalledWith("get_github_issues", {
       workspaceId: "ws-2",
+      forceRefresh: false,
+    });
+  });
+
+  it("passes forceRefresh through for GitHub issues", async () => {
+    const invokeMock = vi.mocked(invoke);
+    invokeMock.mockResolvedValueOnce({ total: 0, issues: [] });
+
+    await getGitHubIssues("ws-2", true);
+
+    expect(invokeMock).toHaveBeenCalledWith("get_github_issues", {
+      workspaceId: "ws-2",
+      forceRefresh: true,
     });
   });
 
@@ -93,11 +120,14 @@ describe("tauri invoke wrappers", () => {
       workspaceId: "ws-4",
       threadId: "thread-1",
       text: "hello",
-      model: null,
-      effort: null,
-      accessMode: "full-access",
-      images: ["image.png"],
-      collaborationMode: null,
+      options: {
+        model: null,
+        effort: null,
+        accessMode: "full-access",
+        images: ["image.png"],
+        collaborationMode: null,
+        requestId: null,
+      },
     });
   });
 
@@ -1,15 +1,36 @@
 import { invoke } from "@tauri-apps/api/core";
-import { open } from "@tauri-apps/plugin-dialog";
+import { open, save } from "@tauri-apps/plugin-dialog";
 import type {
   AppSettings,
+  BranchInfo,
   CodexDoctorResult,
+  ConfigBundle,
   DictationModelStatus,
   DictationSessionState,
   LocalUsageSnapshot,
+  LogEntry,
+  LogLevel,
+  ModelInfo,
+  PendingServerRequest,
+  RunnerInfo,
+  SessionMetrics,
+  SettingsFieldError,
+  TerminalSessionInfo,
+  TurnCompletion,
+  TurnUsage,
+  WorkspaceFileContent,
+  WorkspaceFileListing,
+  WorkspaceHealth,
   WorkspaceInfo,
+  WorkspaceListOptions,
+  WorkspaceSearchResult,
   WorkspaceSettings,
+  WorkspaceThreadPage,
+  WorkspaceThreadSummary,
 } from "../types";
 import type {
+  GitBlameResponse,
+  GitConflictFile,
   GitFileDiff,
   GitFileStatus,
   GitHubIssuesResponse,
@@ -17,6 +38,8 @@ import type {
   GitHubPullRequestDiff,
   GitHubPullRequestsResponse,
   GitLogResponse,
+  GitPushResponse,
+  GitSyncResponse,
   ReviewTarget,
 } from "../types";
 
@@ -44,15 +67,26 @@ export async function pickImageFiles(): Promise<string[]> {
   return Array.isArray(selection) ? selection : [selection];
 }
 
-export async function listWorkspaces(): Promise<WorkspaceInfo[]> {
-  return invoke<WorkspaceInfo[]>("list_workspaces");
+export async function listWorkspaces(
+  options: WorkspaceListOptions = {},
+): Promise<WorkspaceInfo[]> {
+  return invoke<WorkspaceInfo[]>("list_workspaces", {
+    kindFilter: options.kindFilter ?? null,
+    connectedOnly: options.connectedOnly ?? null,
+    sort: options.sort ?? null,
+  });
 }
 
 export async function addWorkspace(
   path: string,
   codex_bin: string | null,
+  options?: { rejectDuplicate?: boolean },
 ): Promise<WorkspaceInfo> {
-  return invoke<WorkspaceInfo>("add_workspace", { path, codex_bin });
+  return invoke<WorkspaceInfo>("add_workspace", {
+    path,
+    codex_bin,
+    rejectDuplicate: options?.rejectDuplicate ?? false,
+  });
 }
 
 export async function addClone(
@@ -81,11 +115,37 @@ export async function updateWorkspaceSettings(
   return invoke<WorkspaceInfo>("update_workspace_settings", { id, settings });
 }
 
+export async function setThreadPinned(
+  workspaceId: string,
+  threadId: string,
+  pinned: boolean,
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("set_thread_pinned", { workspaceId, threadId, pinned });
+}
+
+export async function reorderWorkspaces(
+  orderedIds: string[],
+): Promise<WorkspaceInfo[]> {
+  return invoke<WorkspaceInfo[]>("reorder_workspaces", { orderedIds });
+}
+
 export async function updateWorkspaceCodexBin(
   id: string,
   codex_bin: string | null,
+  options?: { verify?: boolean },
+): Promise<WorkspaceInfo> {
+  return invoke<WorkspaceInfo>("update_workspace_codex_bin", {
+    id,
+    codex_bin,
+    verify: options?.verify ?? false,
+  });
+}
+
+export async function renameWorkspace(
+  id: string,
+  name: string,
 ): Promise<WorkspaceInfo> {
-  return invoke<WorkspaceInfo>("update_workspace_codex_bin", { id, codex_bin });
+  return invoke<WorkspaceInfo>("rename_workspace", { id, name });
 }
 
 export async function removeWorkspace(id: string): Promise<void> {
@@ -104,10 +164,65 @@ export async function openWorkspaceIn(path: string, app: string): Promise<void>
   return invoke("open_workspace_in", { path, app });
 }
 
+export async function openInEditor(
+  workspaceId: string,
+  relativePath: string,
+  line?: number | null,
+): Promise<void> {
+  return invoke("open_in_editor", { workspaceId, relativePath, line: line ?? null });
+}
+
+export async function getLogs(
+  level?: LogLevel | null,
+  limit?: number | null,
+): Promise<LogEntry[]> {
+  return invoke<LogEntry[]>("get_logs", { level: level ?? null, limit: limit ?? null });
+}
+
+export async function collectDiagnostics(): Promise<Record<string, unknown>> {
+  return invoke<Record<string, unknown>>("collect_diagnostics");
+}
+
+export async function saveDiagnostics(): Promise<string | null> {
+  const path = await save({
+    defaultPath: "codex-monitor-diagnostics.json",
+    filters: [{ name: "JSON", extensions: ["json"] }],
+  });
+  if (!path) {
+    return null;
+  }
+  await invoke("save_diagnostics", { path });
+  return path;
+}
+
+export async function getRunnerInfo(): Promise<RunnerInfo> {
+  return invoke<RunnerInfo>("runner_info");
+}
+
+export async function getWorkspaceHealth(
+  workspaceId: string,
+): Promise<WorkspaceHealth> {
+  return invoke<WorkspaceHealth>("workspace_health", { workspaceId });
+}
+
+export async function getWorkspaceStderr(workspaceId: string): Promise<string[]> {
+  return invoke<string[]>("workspace_stderr", { workspaceId });
+}
+
 export async function connectWorkspace(id: string): Promise<void> {
   return invoke("connect_workspace", { id });
 }
 
+export async function connectAllWorkspaces(): Promise<
+  Record<string, { ok: boolean; error?: string | null }>
+> {
+  return invoke("connect_all_workspaces");
+}
+
+export async function disconnectAllWorkspaces(): Promise<void> {
+  return invoke("disconnect_all_workspaces");
+}
+
 export async function startThread(workspaceId: string) {
   return invoke<any>("start_thread", { workspaceId });
 }
@@ -122,17 +237,76 @@ export async function sendUserMessage(
     accessMode?: "read-only" | "current" | "full-access";
     images?: string[];
     collaborationMode?: Record<string, unknown> | null;
+    requestId?: string | null;
   },
 ) {
   return invoke("send_user_message", {
     workspaceId,
     threadId,
     text,
-    model: options?.model ?? null,
-    effort: options?.effort ?? null,
-    accessMode: options?.accessMode ?? null,
-    images: options?.images ?? null,
-    collaborationMode: options?.collaborationMode ?? null,
+    options: {
+      model: options?.model ?? null,
+      effort: options?.effort ?? null,
+      accessMode: options?.accessMode ?? null,
+      images: options?.images ?? null,
+      collaborationMode: options?.collaborationMode ?? null,
+      requestId: options?.requestId ?? null,
+    },
+  });
+}
+
+export async function sendUserMessageBlocking(
+  workspaceId: string,
+  threadId: string,
+  text: string,
+  options?: {
+    model?: string | null;
+    effort?: string | null;
+    accessMode?: "read-only" | "current" | "full-access";
+    images?: string[];
+    collaborationMode?: Record<string, unknown> | null;
+    timeoutMs?: number | null;
+  },
+): Promise<TurnCompletion> {
+  return invoke<TurnCompletion>("send_user_message_blocking", {
+    workspaceId,
+    threadId,
+    text,
+    options: {
+      model: options?.model ?? null,
+      effort: options?.effort ?? null,
+      accessMode: options?.accessMode ?? null,
+      images: options?.images ?? null,
+      collaborationMode: options?.collaborationMode ?? null,
+    },
+    timeoutMs: options?.timeoutMs ?? null,
+  });
+}
+
+export async function getThreadUsage(
+  workspaceId: string,
+  threadId: string,
+): Promise<TurnUsage> {
+  return invoke<TurnUsage>("thread_usage", { workspaceId, threadId });
+}
+
+export async function setThreadDefaults(
+  threadId: string,
+  defaults: {
+    model?: string | null;
+    effort?: string | null;
+    accessMode?: "read-only" | "current" | "full-access" | null;
+  },
+) {
+  return invoke<{
+    model: string | null;
+    effort: string | null;
+    accessMode: string | null;
+  }>("set_thread_defaults", {
+    threadId,
+    model: defaults.model ?? null,
+    effort: defaults.effort ?? null,
+    accessMode: defaults.accessMode ?? null,
   });
 }
 
@@ -169,6 +343,30 @@ export async function respondToServerRequest(
   });
 }
 
+export async function abortAllTurns(): Promise<{
+  aborted: number;
+  readOnlyUntilMs: number;
+}> {
+  return invoke("abort_all_turns");
+}
+
+export async function listPendingServerRequests(
+  workspaceId: string,
+): Promise<PendingServerRequest[]> {
+  return invoke<PendingServerRequest[]>("list_pending_server_requests", { workspaceId });
+}
+
+export async function cancelRequest(workspaceId: string, requestId: string) {
+  return invoke<{ canceled: boolean; interrupted: boolean }>("cancel_request", {
+    workspaceId,
+    requestId,
+  });
+}
+
+export async function sessionMetrics(workspaceId: string): Promise<SessionMetrics> {
+  return invoke<SessionMetrics>("session_metrics", { workspaceId });
+}
+
 export async function rememberApprovalRule(
   workspaceId: string,
   command: string[],
@@ -196,8 +394,23 @@ export async function listGitRoots(
 
 export async function getGitDiffs(
   workspace_id: string,
+  options?: { paths?: string[] | null; contextLines?: number | null },
 ): Promise<GitFileDiff[]> {
-  return invoke("get_git_diffs", { workspaceId: workspace_id });
+  return invoke("get_git_diffs", {
+    workspaceId: workspace_id,
+    paths: options?.paths ?? null,
+    contextLines: options?.contextLines ?? null,
+  });
+}
+
+export async function getGitDiffStats(workspace_id: string): Promise<{
+  files: GitFileStatus[];
+  stagedFiles: GitFileStatus[];
+  unstagedFiles: GitFileStatus[];
+  totalAdditions: number;
+  totalDeletions: number;
+}> {
+  return invoke("git_diff_stats", { workspaceId: workspace_id });
 }
 
 export async function getGitLog(
@@ -211,6 +424,54 @@ export async function getGitRemote(workspace_id: string): Promise<string | null>
   return invoke("get_git_remote", { workspaceId: workspace_id });
 }
 
+export async function fetchGit(
+  workspaceId: string,
+  remote?: string | null,
+): Promise<GitSyncResponse> {
+  return invoke<GitSyncResponse>("git_fetch", { workspaceId, remote: remote ?? null });
+}
+
+export async function pullGit(
+  workspaceId: string,
+  rebase = false,
+): Promise<GitSyncResponse> {
+  return invoke<GitSyncResponse>("git_pull", { workspaceId, rebase });
+}
+
+export async function pushGit(
+  workspaceId: string,
+  options?: { setUpstream?: boolean; forceWithLease?: boolean },
+): Promise<GitPushResponse> {
+  return invoke<GitPushResponse>("git_push", {
+    workspaceId,
+    setUpstream: options?.setUpstream ?? false,
+    forceWithLease: options?.forceWithLease ?? false,
+  });
+}
+
+export async function getGitConflicts(
+  workspaceId: string,
+): Promise<GitConflictFile[]> {
+  return invoke<GitConflictFile[]>("git_conflicts", { workspaceId });
+}
+
+export async function abortGitMerge(workspaceId: string): Promise<void> {
+  return invoke("git_abort_merge", { workspaceId });
+}
+
+export async function getGitBlame(
+  workspaceId: string,
+  relativePath: string,
+  range?: { startLine?: number; endLine?: number },
+): Promise<GitBlameResponse> {
+  return invoke<GitBlameResponse>("git_blame", {
+    workspaceId,
+    relativePath,
+    startLine: range?.startLine ?? null,
+    endLine: range?.endLine ?? null,
+  });
+}
+
 export async function stageGitFile(workspaceId: string, path: string) {
   return invoke("stage_git_file", { workspaceId, path });
 }
@@ -229,14 +490,19 @@ export async function revertGitAll(workspaceId: string) {
 
 export async function getGitHubIssues(
   workspace_id: string,
+  forceRefresh = false,
 ): Promise<GitHubIssuesResponse> {
-  return invoke("get_github_issues", { workspaceId: workspace_id });
+  return invoke("get_github_issues", { workspaceId: workspace_id, forceRefresh });
 }
 
 export async function getGitHubPullRequests(
   workspace_id: string,
+  forceRefresh = false,
 ): Promise<GitHubPullRequestsResponse> {
-  return invoke("get_github_pull_requests", { workspaceId: workspace_id });
+  return invoke("get_github_pull_requests", {
+    workspaceId: workspace_id,
+    forceRefresh,
+  });
 }
 
 export async function getGitHubPullRequestDiff(
@@ -259,6 +525,30 @@ export async function getGitHubPullRequestComments(
   });
 }
 
+export async function postGitHubPullRequestComment(
+  workspace_id: string,
+  prNumber: number,
+  body: string,
+): Promise<string> {
+  return invoke("post_github_pr_comment", {
+    workspaceId: workspace_id,
+    prNumber,
+    body,
+  });
+}
+
+export async function postGitHubIssueComment(
+  workspace_id: string,
+  issueNumber: number,
+  body: string,
+): Promise<string> {
+  return invoke("post_github_issue_comment", {
+    workspaceId: workspace_id,
+    issueNumber,
+    body,
+  });
+}
+
 export async function localUsageSnapshot(
   days?: number,
 ): Promise<LocalUsageSnapshot> {
@@ -269,12 +559,28 @@ export async function getModelList(workspaceId: string) {
   return invoke<any>("model_list", { workspaceId });
 }
 
+export async function getModels(
+  workspaceId: string,
+  options?: { force?: boolean },
+): Promise<ModelInfo[]> {
+  return invoke<ModelInfo[]>("models_list", {
+    workspaceId,
+    force: options?.force ?? null,
+  });
+}
+
 export async function getCollaborationModes(workspaceId: string) {
   return invoke<any>("collaboration_mode_list", { workspaceId });
 }
 
-export async function getAccountRateLimits(workspaceId: string) {
-  return invoke<any>("account_rate_limits", { workspaceId });
+export async function getAccountRateLimits(
+  workspaceId: string,
+  options?: { force?: boolean },
+) {
+  return invoke<any>("account_rate_limits", {
+    workspaceId,
+    force: options?.force ?? null,
+  });
 }
 
 export async function getSkillsList(workspaceId: string) {
@@ -285,6 +591,22 @@ export async function getPromptsList(workspaceId: string) {
   return invoke<any>("prompts_list", { workspaceId });
 }
 
+export async function searchPrompts(workspaceId: string, query: string) {
+  return invoke<any>("prompts_search", { workspaceId, query });
+}
+
+export async function getPrompt(workspaceId: string, name: string) {
+  return invoke<any>("prompts_get", { workspaceId, name });
+}
+
+export async function renderPrompt(
+  workspaceId: string,
+  name: string,
+  args: Record<string, string>,
+) {
+  return invoke<string>("prompts_render", { workspaceId, name, args });
+}
+
 export async function getWorkspacePromptsDir(workspaceId: string) {
   return invoke<string>("prompts_workspace_dir", { workspaceId });
 }
@@ -356,14 +678,80 @@ export async function updateAppSettings(settings: AppSettings): Promise<AppSetti
   return invoke<AppSettings>("update_app_settings", { settings });
 }
 
+export async function isDndActive(): Promise<boolean> {
+  return invoke<boolean>("is_dnd_active");
+}
+
+export async function localApiInfo(): Promise<{
+  enabled: boolean;
+  port: number | null;
+  token: string | null;
+  eventsUrl: string | null;
+}> {
+  return invoke("local_api_info");
+}
+
+export async function exportConfig(options?: {
+  includeSecrets?: boolean;
+}): Promise<ConfigBundle> {
+  return invoke<ConfigBundle>("export_config", {
+    includeSecrets: options?.includeSecrets ?? null,
+  });
+}
+
+export async function importConfig(
+  bundle: ConfigBundle,
+  merge: boolean,
+): Promise<ConfigBundle> {
+  return invoke<ConfigBundle>("import_config", { bundle, merge });
+}
+
+export async function validateAppSettings(
+  settings: AppSettings,
+): Promise<SettingsFieldError[]> {
+  return invoke<SettingsFieldError[]>("validate_app_settings", { settings });
+}
+
 export async function runCodexDoctor(
   codexBin: string | null,
 ): Promise<CodexDoctorResult> {
   return invoke<CodexDoctorResult>("codex_doctor", { codexBin });
 }
 
-export async function getWorkspaceFiles(workspaceId: string) {
-  return invoke<string[]>("list_workspace_files", { workspaceId });
+export async function getWorkspaceFiles(
+  workspaceId: string,
+  options?: { maxDepth?: number | null; maxEntries?: number | null },
+) {
+  return invoke<WorkspaceFileListing>("list_workspace_files", {
+    workspaceId,
+    maxDepth: options?.maxDepth ?? null,
+    maxEntries: options?.maxEntries ?? null,
+  });
+}
+
+export async function readWorkspaceFile(
+  workspaceId: string,
+  relativePath: string,
+  maxBytes?: number | null,
+) {
+  return invoke<WorkspaceFileContent>("read_workspace_file", {
+    workspaceId,
+    relativePath,
+    maxBytes: maxBytes ?? null,
+  });
+}
+
+export async function searchWorkspace(
+  workspaceId: string,
+  query: string,
+  options?: { isRegex?: boolean; maxResults?: number },
+) {
+  return invoke<WorkspaceSearchResult>("search_workspace", {
+    workspaceId,
+    query,
+    isRegex: options?.isRegex ?? false,
+    maxResults: options?.maxResults ?? 200,
+  });
 }
 
 export async function listGitBranches(workspaceId: string) {
@@ -378,6 +766,26 @@ export async function createGitBranch(workspaceId: string, name: string) {
   return invoke("create_git_branch", { workspaceId, name });
 }
 
+export async function deleteGitBranch(
+  workspaceId: string,
+  name: string,
+  force = false,
+): Promise<BranchInfo[]> {
+  return invoke<BranchInfo[]>("delete_git_branch", { workspaceId, name, force });
+}
+
+export async function renameGitBranch(
+  workspaceId: string,
+  oldName: string,
+  newName: string,
+): Promise<BranchInfo[]> {
+  return invoke<BranchInfo[]>("rename_git_branch", {
+    workspaceId,
+    old: oldName,
+    new: newName,
+  });
+}
+
 function withModelId(modelId?: string | null) {
   return modelId ? { modelId } : {};
 }
@@ -437,8 +845,23 @@ export async function openTerminalSession(
   terminalId: string,
   cols: number,
   rows: number,
-): Promise<{ id: string }> {
-  return invoke("terminal_open", { workspaceId, terminalId, cols, rows });
+  options?: {
+    shell?: string | null;
+    cwd?: string | null;
+    initialCommand?: string | null;
+  },
+): Promise<TerminalSessionInfo> {
+  return invoke("terminal_open", {
+    workspaceId,
+    terminalId,
+    cols,
+    rows,
+    options: {
+      shell: options?.shell ?? null,
+      cwd: options?.cwd ?? null,
+      initialCommand: options?.initialCommand ?? null,
+    },
+  });
 }
 
 export async function writeTerminalSession(
@@ -458,6 +881,19 @@ export async function resizeTerminalSession(
   return invoke("terminal_resize", { workspaceId, terminalId, cols, rows });
 }
 
+export async function listTerminalSessions(
+  workspaceId: string,
+): Promise<TerminalSessionInfo[]> {
+  return invoke("terminal_list", { workspaceId });
+}
+
+export async function getTerminalSnapshot(
+  workspaceId: string,
+  terminalId: string,
+): Promise<string> {
+  return invoke("terminal_snapshot", { workspaceId, terminalId });
+}
+
 export async function closeTerminalSession(
   workspaceId: string,
   terminalId: string,
@@ -473,10 +909,49 @@ export async function listThreads(
   return invoke<any>("list_threads", { workspaceId, cursor, limit });
 }
 
-export async function resumeThread(workspaceId: string, threadId: string) {
-  return invoke<any>("resume_thread", { workspaceId, threadId });
+export async function listWorkspaceThreads(
+  workspaceId: string,
+  cursor?: string | null,
+  limit?: number | null,
+): Promise<WorkspaceThreadPage> {
+  return invoke<WorkspaceThreadPage>("list_workspace_threads", {
+    workspaceId,
+    cursor: cursor ?? null,
+    limit: limit ?? null,
+  });
+}
+
+export async function searchThreads(
+  workspaceId: string,
+  query: string,
+  limit?: number | null,
+): Promise<WorkspaceThreadSummary[]> {
+  return invoke<WorkspaceThreadSummary[]>("search_threads", {
+    workspaceId,
+    query,
+    limit: limit ?? null,
+  });
+}
+
+export async function resumeThread(
+  workspaceId: string,
+  threadId: string,
+  requestId?: string,
+) {
+  return invoke<any>("resume_thread", {
+    workspaceId,
+    threadId,
+    requestId: requestId ?? null,
+  });
 }
 
 export async function archiveThread(workspaceId: string, threadId: string) {
   return invoke<any>("archive_thread", { workspaceId, threadId });
 }
+
+export async function deleteThread(workspaceId: string, threadId: string) {
+  return invoke<{ threadId: string; deleted: boolean; removedFiles: number }>(
+    "delete_thread",
+    { workspaceId, threadId },
+  );
+}
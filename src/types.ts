@@ -3,8 +3,25 @@ export type WorkspaceSettings = {
   sortOrder?: number | null;
   groupId?: string | null;
   gitRoot?: string | null;
+  pinnedThreads?: string[];
+  muted?: boolean;
+  autoApprove?: AutoApprovePolicy;
+  maxAccessMode?: AccessMode | null;
+  watchFiles?: boolean;
+  codexArgs?: string[] | null;
 };
 
+export type WorkspaceListOptions = {
+  kindFilter?: "all" | "main" | "worktree";
+  connectedOnly?: boolean;
+  sort?: "sortOrder" | "name" | "lastActivity";
+};
+
+export type AutoApprovePolicy =
+  | { mode: "never" }
+  | { mode: "readOnlyCommands" }
+  | { mode: "matchingPatterns"; patterns: string[] };
+
 export type WorkspaceGroup = {
   id: string;
   name: string;
@@ -23,6 +40,7 @@ export type WorkspaceInfo = {
   name: string;
   path: string;
   connected: boolean;
+  watching?: boolean;
   codex_bin?: string | null;
   kind?: WorkspaceKind;
   parentId?: string | null;
@@ -73,8 +91,21 @@ export type AccessMode = "read-only" | "current" | "full-access";
 export type BackendMode = "local" | "remote";
 export type ThemePreference = "system" | "light" | "dark";
 
+export type ConfigBundle = {
+  version: number;
+  exportedAtMs: number;
+  settings: AppSettings;
+  workspaces: Record<string, unknown>[];
+};
+
+export type SettingsFieldError = {
+  field: string;
+  message: string;
+};
+
 export type AppSettings = {
   codexBin: string | null;
+  codexArgs: string[];
   backendMode: BackendMode;
   remoteBackendHost: string;
   remoteBackendToken: string | null;
@@ -95,6 +126,14 @@ export type AppSettings = {
   dictationPreferredLanguage: string | null;
   dictationHoldKey: string | null;
   workspaceGroups: WorkspaceGroup[];
+  githubCacheTtlSecs: number;
+  gitTimeoutSecs: number;
+  terminalScrollbackBytes: number;
+  preferredEditor: string | null;
+  desktopNotificationsEnabled: boolean;
+  dndStart: string | null;
+  dndEnd: string | null;
+  localApiEnabled: boolean;
 };
 
 export type CodexDoctorResult = {
@@ -116,6 +155,23 @@ export type ApprovalRequest = {
   params: Record<string, unknown>;
 };
 
+export type PendingServerRequest = {
+  requestId: number;
+  method: string;
+  threadId: string | null;
+  description: string;
+  params: Record<string, unknown>;
+  receivedAtMs: number;
+};
+
+export type SessionMetrics = {
+  requestsSent: number;
+  responsesReceived: number;
+  errors: number;
+  timeouts: number;
+  avgLatencyMs: number | null;
+};
+
 export type GitFileStatus = {
   path: string;
   status: string;
@@ -145,6 +201,49 @@ export type GitLogResponse = {
   upstream: string | null;
 };
 
+export type GitSyncResponse = {
+  ahead: number;
+  behind: number;
+  upstream: string | null;
+};
+
+export type GitPushResponse = {
+  remote: string;
+  remoteUrl: string | null;
+  upstream: string | null;
+};
+
+export type GitConflictRange = {
+  startLine: number;
+  endLine: number;
+};
+
+export type GitConflictFile = {
+  path: string;
+  status: string;
+  markers: GitConflictRange[];
+};
+
+export type GitBlameLine = {
+  sha: string;
+  author: string;
+  timestamp: number;
+  summary: string;
+  line: number;
+  content: string;
+};
+
+export type GitBlameResponse = {
+  lines: GitBlameLine[];
+  truncated: boolean;
+};
+
+export type GitProgressEvent = {
+  workspaceId: string;
+  operation: string;
+  line: string;
+};
+
 export type GitHubIssue = {
   number: number;
   title: string;
@@ -155,6 +254,7 @@ export type GitHubIssue = {
 export type GitHubIssuesResponse = {
   total: number;
   issues: GitHubIssue[];
+  fetchedAtMs?: number;
 };
 
 export type GitHubUser = {
@@ -177,6 +277,7 @@ export type GitHubPullRequest = {
 export type GitHubPullRequestsResponse = {
   total: number;
   pullRequests: GitHubPullRequest[];
+  fetchedAtMs?: number;
 };
 
 export type GitHubPullRequestDiff = {
@@ -286,6 +387,19 @@ export type ModelOption = {
   isDefault: boolean;
 };
 
+export type ModelInfo = {
+  id: string;
+  model: string;
+  displayName: string;
+  description: string;
+  supportedEfforts: string[];
+  defaultEffort: string | null;
+  isDefault: boolean;
+  supportsEffort: boolean;
+  contextWindow: number | null;
+  supportsImages: boolean | null;
+};
+
 export type CollaborationModeOption = {
   id: string;
   label: string;
@@ -309,6 +423,7 @@ export type CustomPromptOption = {
   argumentHint?: string;
   content: string;
   scope?: "workspace" | "global";
+  arguments?: string[];
 };
 
 export type BranchInfo = {
@@ -350,7 +465,100 @@ export type DictationEvent =
   | { type: "error"; message: string }
   | { type: "canceled"; message: string };
 
+export type WorkspaceThreadSummary = {
+  id: string;
+  preview: string;
+  cwd: string;
+  updatedAt: number;
+  pinned: boolean;
+};
+
+export type WorkspaceThreadPage = {
+  threads: WorkspaceThreadSummary[];
+  nextCursor: string | null;
+};
+
+export type DictationResult = {
+  text: string;
+  detectedLanguage: string | null;
+  confidence: number | null;
+  durationMs: number;
+};
+
 export type DictationTranscript = {
   id: string;
   text: string;
 };
+
+export type TerminalSessionInfo = {
+  id: string;
+  cols: number;
+  rows: number;
+  alive: boolean;
+};
+
+export type WorkspaceFileListing = {
+  files: string[];
+  truncated: boolean;
+};
+
+export type WorkspaceFileContent = {
+  content: string;
+  truncated: boolean;
+  isBinary: boolean;
+  size: number;
+};
+
+export type WorkspaceSearchMatch = {
+  path: string;
+  line: number;
+  column: number;
+  text: string;
+};
+
+export type WorkspaceSearchResult = {
+  matches: WorkspaceSearchMatch[];
+  truncated: boolean;
+};
+
+export type WorkspaceHealth = {
+  connected: boolean;
+  codexPid: number | null;
+  uptimeSecs: number;
+  lastEventMs: number | null;
+  pendingRequests: number;
+};
+
+export type TurnUsage = {
+  inputTokens: number;
+  outputTokens: number;
+  total: number;
+};
+
+export type TurnCompletion = {
+  threadId: string;
+  turnId: string | null;
+  text: string;
+  tokenUsage: Record<string, unknown> | null;
+};
+
+export type RunnerInfo = {
+  runnerId: string;
+  version: string;
+  platform: string;
+  os: string;
+  hostname: string | null;
+  startedAtMs: number;
+  workspaceCount: number;
+  connectedCount: number;
+  cloudProvider: string | null;
+};
+
+export type LogLevel = "debug" | "info" | "warn" | "error";
+
+export type LogEntry = {
+  timestampMs: number;
+  level: LogLevel;
+  tag: string;
+  message: string;
+};